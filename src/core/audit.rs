@@ -0,0 +1,151 @@
+use std::{
+    fmt::Display,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditAction {
+    Attach { name: String },
+    FirstScan { value: String, results: usize },
+    NextScan { value: String, results: usize },
+    Write { address: u64, value: String },
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Attach { name } => write!(f, "attach {name}"),
+            Self::FirstScan { value, results } => {
+                write!(f, "first scan for `{value}`, {results} results")
+            }
+            Self::NextScan { value, results } => {
+                write!(f, "next scan for `{value}`, {results} results")
+            }
+            Self::Write { address, value } => write!(f, "write `{value}` to 0x{address:x}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// Seconds since the unix epoch
+    pub time: u64,
+    pub pid: u32,
+    pub action: AuditAction,
+}
+
+impl Display for AuditEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\tpid {}\t{}",
+            format_utc(self.time),
+            self.pid,
+            self.action
+        )
+    }
+}
+
+/// Everything done to target processes during the session, entries are only ever appended
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub fn record(&mut self, pid: u32, action: AuditAction) {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.entries.push(AuditEntry { time, pid, action });
+    }
+
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Writes one line per entry to `path`, returns how many were written
+    pub fn export(&self, path: &Path) -> std::io::Result<usize> {
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| format!("{entry}\n"))
+            .collect();
+        std::fs::write(path, content)?;
+        Ok(self.entries.len())
+    }
+}
+
+/// File the audit log is exported to, in the working directory
+pub fn get_export_path() -> String {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format!("cheat-engine-rs-audit-{time}.log")
+}
+
+/// `2026-01-31T23:59:59Z` for seconds since the unix epoch
+fn format_utc(time: u64) -> String {
+    let (days, seconds) = (time / 86400, time % 86400);
+
+    // civil date from days since 1970-01-01, March based years put leap days last
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    pub fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_798_761_599), "2026-12-31T23:59:59Z");
+    }
+
+    #[test]
+    pub fn test_audit_log() {
+        let mut log = AuditLog::default();
+        log.record(
+            42,
+            AuditAction::Attach {
+                name: "game".to_owned(),
+            },
+        );
+        log.record(
+            42,
+            AuditAction::Write {
+                address: 0x1000,
+                value: "99".to_owned(),
+            },
+        );
+
+        assert_eq!(log.entries().len(), 2);
+        let line = log.entries()[1].to_string();
+        assert!(line.ends_with("\tpid 42\twrite `99` to 0x1000"), "{line}");
+
+        let path = std::env::temp_dir().join(format!("audit-test-{}.log", std::process::id()));
+        assert_eq!(log.export(&path).unwrap(), 2);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.lines().next().unwrap().ends_with("\tattach game"));
+    }
+}
@@ -0,0 +1,203 @@
+use serde::Deserialize;
+use std::{fmt::Display, path::PathBuf};
+
+use crate::core::{
+    mem::{MemoryAccess, MemoryRegionPerms},
+    scan::{CompareMode, DisplayFormat, StructType, ValueType},
+};
+
+const CONFIG_ENV: &str = "CHEAT_ENGINE_RS_CONFIG";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    Read(String),
+    Parse(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "Could not read config file: {e}"),
+            Self::Parse(e) => write!(f, "Invalid config file: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub scan: ScanDefaults,
+    pub display: DisplayFormat,
+    pub ui: UiOptions,
+    pub rpc: RpcOptions,
+    /// Composite value types, selectable next to the built-in ones
+    pub structs: Vec<StructType>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiOptions {
+    /// Marks errors, changed values and static addresses with symbols and
+    /// bold/inverse styles too, so nothing is told apart by color alone
+    pub high_contrast: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcOptions {
+    /// Unix socket the TUI takes JSON-RPC requests on, off when not set
+    pub socket: Option<PathBuf>,
+}
+
+/// Initial state of the scan view
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanDefaults {
+    pub value_type: Option<ValueType>,
+    pub permissions: Option<Vec<MemoryRegionPerms>>,
+    pub read_size: Option<usize>,
+    pub comparison: Option<CompareMode>,
+    /// Tolerance of the `within_percent` comparison
+    pub percent: Option<f64>,
+    /// Fixed tolerance of the `rounded` comparison instead of rounding to the typed decimals
+    pub epsilon: Option<f64>,
+    /// Ring the bell once a next scan leaves fewer results than this
+    pub notify_below: Option<usize>,
+    /// Alignment of first scan results in bytes, defaults to the size of numeric types
+    pub alignment: Option<usize>,
+    /// Results kept with their details, matches past it are still narrowed down by next scans
+    pub max_results: Option<usize>,
+    /// Unknown initial value scans of more memory than this many MiB keep their snapshot on disk
+    pub memory_budget_mb: Option<u64>,
+    /// KiB read at once by first scans
+    pub block_size_kb: Option<usize>,
+    /// How the memory of the process is read and written
+    pub backend: Option<MemoryAccess>,
+    /// Start with the "Skip file-backed mappings" option ticked
+    pub skip_file_backed: Option<bool>,
+}
+
+impl Config {
+    pub fn parse(content: &str) -> Result<Config, ConfigError> {
+        let config: Config =
+            toml::from_str(content).map_err(|e| ConfigError::Parse(e.message().to_owned()))?;
+        for struct_type in &config.structs {
+            struct_type.check().map_err(ConfigError::Parse)?;
+        }
+        Ok(config)
+    }
+
+    /// Loads the config file, a missing file means the built-in defaults
+    pub fn load() -> Result<Config, ConfigError> {
+        let Some(path) = config_path() else {
+            return Ok(Config::default());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Config::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Read(format!("{}: {e}", path.display()))),
+        }
+    }
+}
+
+/// `$CHEAT_ENGINE_RS_CONFIG`, otherwise `cheat-engine-rs/config.toml`
+/// inside `$XDG_CONFIG_HOME` or `~/.config`
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("cheat-engine-rs").join("config.toml"))
+}
+
+mod test {
+    #[test]
+    pub fn test_parse_config() {
+        use super::*;
+
+        let config = Config::parse(
+            r#"
+[scan]
+value_type = "u32"
+permissions = ["read", "write"]
+read_size = 16
+comparison = "within_percent"
+percent = 5.0
+epsilon = 0.01
+notify_below = 10
+alignment = 1
+max_results = 5000
+memory_budget_mb = 512
+block_size_kb = 1024
+backend = "native"
+skip_file_backed = true
+
+[display]
+integers = "hex"
+thousands_separator = true
+
+[ui]
+high_contrast = true
+
+[rpc]
+socket = "/tmp/cheat-engine-rs.sock"
+
+[[structs]]
+name = "player"
+fields = [
+    { name = "hp", type = "u32" },
+    { name = "x", type = "f32" },
+    { name = "flags", type = "bit" },
+]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.scan.value_type, Some(ValueType::U32));
+        assert_eq!(
+            config.scan.permissions,
+            Some(vec![MemoryRegionPerms::Read, MemoryRegionPerms::Write])
+        );
+        assert_eq!(config.scan.read_size, Some(16));
+        assert_eq!(config.scan.comparison, Some(CompareMode::WithinPercent));
+        assert_eq!(config.scan.percent, Some(5.0));
+        assert_eq!(config.scan.epsilon, Some(0.01));
+        assert_eq!(config.scan.notify_below, Some(10));
+        assert_eq!(config.scan.alignment, Some(1));
+        assert_eq!(config.scan.max_results, Some(5000));
+        assert_eq!(config.scan.memory_budget_mb, Some(512));
+        assert_eq!(config.scan.block_size_kb, Some(1024));
+        assert_eq!(config.scan.backend, Some(MemoryAccess::Native));
+        assert_eq!(config.scan.skip_file_backed, Some(true));
+        assert_eq!(
+            config.display.integers,
+            crate::core::scan::IntegerFormat::Hex
+        );
+        assert!(config.display.thousands_separator);
+        assert!(config.ui.high_contrast);
+        assert_eq!(
+            config.rpc.socket,
+            Some(PathBuf::from("/tmp/cheat-engine-rs.sock"))
+        );
+        assert_eq!(config.structs.len(), 1);
+        assert_eq!(config.structs[0].name, "player");
+        assert_eq!(config.structs[0].get_size(), 9);
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(Config::parse("[scan]\nvalue_type = \"u24\"").is_err());
+        assert!(Config::parse("[scan]\nunknown = 1").is_err());
+        assert!(Config::parse("[scan]\nbackend = \"unknown\"").is_err());
+        assert!(
+            Config::parse(
+                "[[structs]]\nname = \"s\"\nfields = [{ name = \"a\", type = \"string\" }]"
+            )
+            .is_err()
+        );
+        assert!(Config::parse("[[structs]]\nname = \"s\"\nfields = []").is_err());
+    }
+}
@@ -0,0 +1,479 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use memmap2::{MmapMut, MmapOptions};
+
+use crate::core::mem::{
+    DEFAULT_SEARCH_PERMS, MemoryBackend, MemoryError, MemoryRegion, MemoryRegionPerms,
+    READ_BLOCK_SIZE, parse_maps_line,
+};
+use crate::core::proc::layout_pointer_width;
+
+/// Pid scans of a dump run under, no live process has it on Linux
+pub const DUMP_PID: u32 = 0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpError {
+    Io(String),
+    Map(usize, String),
+    Size(u64, u64),
+    Memory(MemoryError),
+}
+
+impl Display for DumpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not access the dump: {e}"),
+            Self::Map(line, content) => {
+                write!(f, "Region map line {line}: can not parse `{content}`")
+            }
+            Self::Size(expected, actual) => write!(
+                f,
+                "The region map lists {expected} bytes but the dump holds {actual}"
+            ),
+            Self::Memory(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Region map written next to a full dump, `game.dump` gets `game.dump.maps`
+pub fn map_path(path: &Path) -> PathBuf {
+    let mut map_path = path.as_os_str().to_owned();
+    map_path.push(".maps");
+    PathBuf::from(map_path)
+}
+
+/// Memory of a process that is gone, read from a dump on disk so the scans work
+/// without a live target. Writes only change the copy in memory, the file is left as is
+pub struct DumpMemory {
+    data: RwLock<MmapMut>,
+    /// Regions sorted by address with the offset of their bytes in the dump
+    regions: Vec<(MemoryRegion, usize)>,
+}
+
+impl DumpMemory {
+    /// A full dump, the regions listed in its region map (`/proc/<pid>/maps` lines)
+    /// are stored back to back in the same order
+    pub fn open(path: &Path) -> Result<Self, DumpError> {
+        let map_path = map_path(path);
+        let content = std::fs::read_to_string(&map_path)
+            .map_err(|e| DumpError::Io(format!("{}: {e}", map_path.display())))?;
+
+        let mut regions = Vec::new();
+        let mut offset = 0;
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let region = parse_maps_line(line)
+                .ok()
+                .filter(|region| region.end > region.start)
+                .ok_or_else(|| DumpError::Map(i + 1, line.to_owned()))?;
+            let size = (region.end - region.start) as usize;
+            regions.push((region, offset));
+            offset += size;
+        }
+        Self::load(path, regions, offset as u64)
+    }
+
+    /// A single region dumped as is, its first byte is at `base`
+    pub fn open_region(path: &Path, base: u64) -> Result<Self, DumpError> {
+        let size = std::fs::metadata(path)
+            .map_err(|e| DumpError::Io(e.to_string()))?
+            .len();
+        let region = MemoryRegion {
+            start: base,
+            end: base + size,
+            perms: vec![MemoryRegionPerms::Read, MemoryRegionPerms::Write],
+            path: None,
+            offset: 0,
+            inode: 0,
+            shared: false,
+        };
+        Self::load(path, vec![(region, 0)], size)
+    }
+
+    fn load(
+        path: &Path,
+        mut regions: Vec<(MemoryRegion, usize)>,
+        size: u64,
+    ) -> Result<Self, DumpError> {
+        let file = File::open(path).map_err(|e| DumpError::Io(e.to_string()))?;
+        let file_size = file
+            .metadata()
+            .map_err(|e| DumpError::Io(e.to_string()))?
+            .len();
+        if file_size != size {
+            return Err(DumpError::Size(size, file_size));
+        }
+
+        let data = if size == 0 {
+            MmapMut::map_anon(0)
+        } else {
+            // SAFETY: the mapping is private, pages the scans change are copied instead of
+            // written back. Pages that are left alone follow the file if it changes on disk
+            unsafe { MmapOptions::new().map_copy(&file) }
+        }
+        .map_err(|e| DumpError::Io(e.to_string()))?;
+
+        regions.sort_by_key(|(region, _)| region.start);
+        Ok(DumpMemory {
+            data: RwLock::new(data),
+            regions,
+        })
+    }
+
+    /// Region holding all of `addr..addr + size` and the offset of `addr` in the dump
+    fn locate(&self, addr: usize, size: usize) -> Option<(&MemoryRegion, usize)> {
+        let addr = addr as u64;
+        let i = self
+            .regions
+            .partition_point(|(region, _)| region.start <= addr)
+            .checked_sub(1)?;
+        let (region, offset) = &self.regions[i];
+        (addr.checked_add(size as u64)? <= region.end)
+            .then(|| (region, offset + (addr - region.start) as usize))
+    }
+
+    fn write_to(&self, addr: usize, value: &[u8], protected: bool) -> Result<(), MemoryError> {
+        let offset = self
+            .locate(addr, value.len())
+            .filter(|(region, _)| protected || region.perms.contains(&MemoryRegionPerms::Write))
+            .map(|(_, offset)| offset)
+            .ok_or(MemoryError::MemWrite(libc::EFAULT))?;
+        let mut data = self.data.write().map_err(|_| MemoryError::MemWrite(-1))?;
+        data[offset..offset + value.len()].copy_from_slice(value);
+        Ok(())
+    }
+}
+
+impl MemoryBackend for DumpMemory {
+    fn regions(
+        &self,
+        _pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        let search_perms = search_perms.unwrap_or(&DEFAULT_SEARCH_PERMS);
+        Ok(self
+            .regions
+            .iter()
+            .map(|(region, _)| region)
+            .filter(|region| region.is_selected(start, end, search_perms))
+            .cloned()
+            .collect())
+    }
+
+    fn read(&self, _pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        let (_, offset) = self
+            .locate(addr, size)
+            .ok_or(MemoryError::MemRead(libc::EFAULT))?;
+        let data = self.data.read().map_err(|_| MemoryError::MemRead(-1))?;
+        Ok(data[offset..offset + size].to_vec())
+    }
+
+    /// Regions that are not writable in the dump can not be written either,
+    /// like in the process it was taken from
+    fn write(&self, _pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.write_to(addr, value, false)
+    }
+
+    fn write_protected(&self, _pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.write_to(addr, value, true)
+    }
+
+    /// Guessed from the layout, the executable of the process is not around to tell
+    fn pointer_width(&self, _pid: u32) -> Option<usize> {
+        let (region, _) = self.regions.iter().max_by_key(|(region, _)| region.end)?;
+        Some(layout_pointer_width(region.end))
+    }
+}
+
+/// `/proc/<pid>/maps` line of a region, the device is not kept
+fn format_maps_line(region: &MemoryRegion) -> String {
+    let flag = |perm, c| {
+        if region.perms.contains(&perm) { c } else { '-' }
+    };
+    format!(
+        "{:x}-{:x} {}{}{}{} {:08x} 00:00 {} {}",
+        region.start,
+        region.end,
+        flag(MemoryRegionPerms::Read, 'r'),
+        flag(MemoryRegionPerms::Write, 'w'),
+        flag(MemoryRegionPerms::Execute, 'x'),
+        if region.shared { 's' } else { 'p' },
+        region.offset,
+        region.inode,
+        region.path.as_deref().unwrap_or(""),
+    )
+    .trim_end()
+    .to_owned()
+}
+
+/// Writes every readable region of the process to `path` and its region map next to it,
+/// regions that can not be read in full (guard pages, `[vvar]`) are left out. Regions are
+/// copied `READ_BLOCK_SIZE` bytes at a time. Returns how many regions were dumped
+pub fn write_dump(memory: &dyn MemoryBackend, pid: u32, path: &Path) -> Result<usize, DumpError> {
+    let regions = memory
+        .regions(pid, None, None, Some(&[MemoryRegionPerms::Read]))
+        .map_err(DumpError::Memory)?;
+
+    let create = |path: &Path| {
+        File::create(path)
+            .map(BufWriter::new)
+            .map_err(|e| DumpError::Io(format!("{}: {e}", path.display())))
+    };
+    let mut data = create(path)?;
+    let mut map = create(&map_path(path))?;
+
+    let io_error = |e: std::io::Error| DumpError::Io(e.to_string());
+    let mut count = 0;
+    // end of the last region dumped in full, a region that reads short is cut off here
+    let mut dumped = 0;
+    for region in regions {
+        let mut complete = true;
+        let mut addr = region.start;
+        while addr < region.end {
+            let size = (region.end - addr).min(READ_BLOCK_SIZE as u64) as usize;
+            match memory.read(pid, addr as usize, size) {
+                Ok(bytes) if bytes.len() == size => data.write_all(&bytes).map_err(io_error)?,
+                _ => {
+                    complete = false;
+                    break;
+                }
+            }
+            addr += size as u64;
+        }
+
+        if complete {
+            writeln!(map, "{}", format_maps_line(&region)).map_err(io_error)?;
+            dumped += region.end - region.start;
+            count += 1;
+        } else if addr > region.start {
+            data.seek(SeekFrom::Start(dumped)).map_err(io_error)?;
+            data.get_ref().set_len(dumped).map_err(io_error)?;
+        }
+    }
+    data.flush()
+        .and_then(|()| map.flush())
+        .map_err(|e| DumpError::Io(e.to_string()))?;
+    Ok(count)
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn write_test_dump(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.dump", std::process::id()));
+        let mut data = vec![0_u8; 0x3000];
+        data[0x10..0x14].copy_from_slice(&31337_u32.to_le_bytes());
+        data[0x1020..0x1024].copy_from_slice(&31337_u32.to_le_bytes());
+        data[0x2ff0..0x2ff4].copy_from_slice(&31337_u32.to_le_bytes());
+        std::fs::write(&path, data).unwrap();
+        std::fs::write(
+            map_path(&path),
+            "55d0c2a00000-55d0c2a01000 r--p 00001000 fd:00 5678 /usr/bin/game\n\
+             55d0c2a3b000-55d0c2a3c000 rw-p 00000000 00:00 0 [heap]\n\
+             7ffc4a1b8000-7ffc4a1b9000 rw-p 00000000 00:00 0 [stack]\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[allow(dead_code)]
+    fn remove_test_dump(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(map_path(path));
+    }
+
+    #[test]
+    pub fn test_dump_memory() {
+        use super::*;
+
+        let path = write_test_dump("dump-test");
+        let memory = DumpMemory::open(&path).unwrap();
+
+        let regions = memory
+            .regions(DUMP_PID, None, None, Some(&[MemoryRegionPerms::Read]))
+            .unwrap();
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0].path.as_deref(), Some("/usr/bin/game"));
+        assert_eq!(regions[0].file_offset(0x55d0c2a00010), Some(0x1010));
+        assert_eq!(memory.regions(DUMP_PID, None, None, None).unwrap().len(), 2);
+
+        assert_eq!(
+            memory.read(DUMP_PID, 0x55d0c2a3b020, 4).unwrap(),
+            31337_u32.to_le_bytes()
+        );
+        assert!(memory.read(DUMP_PID, 0x55d0c2a3bffe, 4).is_err());
+        assert!(memory.read(DUMP_PID, 0x1000, 4).is_err());
+
+        memory.write(DUMP_PID, 0x55d0c2a3b020, &[1, 2]).unwrap();
+        assert_eq!(
+            memory.read(DUMP_PID, 0x55d0c2a3b020, 2).unwrap(),
+            vec![1, 2]
+        );
+        assert!(memory.write(DUMP_PID, 0x55d0c2a00010, &[1]).is_err());
+        memory
+            .write_protected(DUMP_PID, 0x55d0c2a00010, &[1])
+            .unwrap();
+        // the file on disk is left as is
+        assert_eq!(std::fs::read(&path).unwrap()[0x10], 0x69);
+
+        std::fs::write(
+            map_path(&path),
+            "55d0c2a3b000-55d0c2a3c000 rw-p 0 00:00 0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            DumpMemory::open(&path).err(),
+            Some(DumpError::Size(0x1000, 0x3000))
+        );
+        std::fs::write(map_path(&path), "not a region\n").unwrap();
+        assert!(matches!(DumpMemory::open(&path), Err(DumpError::Map(1, _))));
+
+        let memory = DumpMemory::open_region(&path, 0x10000).unwrap();
+        assert_eq!(
+            memory.read(DUMP_PID, 0x12ff0, 4).unwrap(),
+            31337_u32.to_le_bytes()
+        );
+
+        remove_test_dump(&path);
+    }
+
+    #[test]
+    pub fn test_scan_dump() {
+        use super::*;
+        use crate::core::scan::{Scan, ValueType};
+
+        let path = write_test_dump("dump-scan-test");
+        let mut scan = Scan::with_backend(
+            Box::new(DumpMemory::open(&path).unwrap()),
+            DUMP_PID,
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scan.pointer_width(), 8);
+        assert_eq!(scan.named_ranges().unwrap()[0].name, "heap");
+
+        let results = scan.init().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].address, 0x55d0c2a3b020);
+        assert_eq!(results[1].address, 0x7ffc4a1b8ff0);
+
+        scan.update_value(0x55d0c2a3b020, "31338").unwrap();
+        scan.set_value_from_str("31338").unwrap();
+        let results = scan.next_scan().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].address, 0x55d0c2a3b020);
+
+        remove_test_dump(&path);
+    }
+
+    #[test]
+    pub fn test_write_dump() {
+        use super::*;
+
+        let path = write_test_dump("dump-source-test");
+        let memory = DumpMemory::open(&path).unwrap();
+        let copy_path =
+            std::env::temp_dir().join(format!("dump-copy-test-{}.dump", std::process::id()));
+
+        assert_eq!(write_dump(&memory, DUMP_PID, &copy_path).unwrap(), 3);
+        assert_eq!(
+            std::fs::read(&copy_path).unwrap(),
+            std::fs::read(&path).unwrap()
+        );
+        assert_eq!(
+            std::fs::read_to_string(map_path(&copy_path)).unwrap(),
+            "55d0c2a00000-55d0c2a01000 r--p 00001000 00:00 5678 /usr/bin/game\n\
+             55d0c2a3b000-55d0c2a3c000 rw-p 00000000 00:00 0 [heap]\n\
+             7ffc4a1b8000-7ffc4a1b9000 rw-p 00000000 00:00 0 [stack]\n"
+        );
+
+        remove_test_dump(&path);
+        remove_test_dump(&copy_path);
+    }
+
+    /// Dump that can not be read at `hole`, like a region with a guard page in it
+    #[allow(dead_code)]
+    struct HoleMemory(DumpMemory, u64);
+
+    impl MemoryBackend for HoleMemory {
+        fn regions(
+            &self,
+            pid: u32,
+            start: Option<u64>,
+            end: Option<u64>,
+            search_perms: Option<&[MemoryRegionPerms]>,
+        ) -> Result<Vec<MemoryRegion>, MemoryError> {
+            self.0.regions(pid, start, end, search_perms)
+        }
+
+        fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+            assert!(size <= READ_BLOCK_SIZE);
+            if (addr as u64..(addr + size) as u64).contains(&self.1) {
+                return Err(MemoryError::MemRead(libc::EFAULT));
+            }
+            self.0.read(pid, addr, size)
+        }
+
+        fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+            self.0.write(pid, addr, value)
+        }
+    }
+
+    #[test]
+    pub fn test_write_dump_short_region() {
+        use super::*;
+
+        let size = 2 * READ_BLOCK_SIZE + 0x1000;
+        let path = std::env::temp_dir().join(format!("dump-hole-test-{}.dump", std::process::id()));
+        let mut data = vec![1_u8; 0x1000];
+        data.extend(vec![2_u8; size]);
+        data.extend(vec![3_u8; 0x1000]);
+        std::fs::write(&path, data).unwrap();
+        std::fs::write(
+            map_path(&path),
+            format!(
+                "10000-11000 rw-p 0 00:00 0\n\
+                 20000-{:x} rw-p 0 00:00 0\n\
+                 7ffc4a1b8000-7ffc4a1b9000 rw-p 0 00:00 0 [stack]\n",
+                0x20000 + size
+            ),
+        )
+        .unwrap();
+        // the last block of the second region fails after two were copied
+        let memory = HoleMemory(
+            DumpMemory::open(&path).unwrap(),
+            0x20000 + 2 * READ_BLOCK_SIZE as u64,
+        );
+        let copy_path =
+            std::env::temp_dir().join(format!("dump-hole-copy-test-{}.dump", std::process::id()));
+
+        assert_eq!(write_dump(&memory, DUMP_PID, &copy_path).unwrap(), 2);
+        let mut expected = vec![1_u8; 0x1000];
+        expected.extend(vec![3_u8; 0x1000]);
+        assert_eq!(std::fs::read(&copy_path).unwrap(), expected);
+        assert_eq!(
+            std::fs::read_to_string(map_path(&copy_path)).unwrap(),
+            "10000-11000 rw-p 00000000 00:00 0\n\
+             7ffc4a1b8000-7ffc4a1b9000 rw-p 00000000 00:00 0 [stack]\n"
+        );
+
+        remove_test_dump(&path);
+        remove_test_dump(&copy_path);
+    }
+}
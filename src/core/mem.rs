@@ -1,4 +1,6 @@
+#[cfg(not(windows))]
 use process_memory::*;
+use serde::Deserialize;
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -9,10 +11,12 @@ pub enum MemoryError {
     ProcessAttach(i32),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MemoryRegionPerms {
     Read,
     Write,
+    Execute,
 }
 
 pub const DEFAULT_SEARCH_PERMS: [MemoryRegionPerms; 1] = [MemoryRegionPerms::Write];
@@ -30,12 +34,78 @@ impl Display for MemoryError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MemoryRegion {
     pub start: u64,
     pub end: u64,
     #[allow(dead_code)]
     pub perms: Vec<MemoryRegionPerms>,
+    /// Backing pathname of the mapping (file path or pseudo name like `[heap]`), if known
+    pub path: Option<String>,
+    /// Offset of the mapping in its backing file
+    pub offset: u64,
+    /// Inode of the backing file, 0 for anonymous memory or where it is not known
+    #[allow(dead_code)]
+    pub inode: u64,
+    /// Mapped shared with other processes (shared memory, framebuffers) instead of
+    /// private or copy-on-write
+    pub shared: bool,
+}
+
+impl MemoryRegion {
+    /// Overlaps the address range and has one of the permissions searched for
+    pub fn is_selected(
+        &self,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: &[MemoryRegionPerms],
+    ) -> bool {
+        self.end >= start.unwrap_or(0)
+            && self.start <= end.unwrap_or(u64::MAX)
+            && search_perms.iter().any(|p| self.perms.contains(p))
+    }
+
+    /// Offset of `address` in the file the region maps, `None` for memory that is not
+    /// backed by a file (heap, stacks, anonymous mappings)
+    pub fn file_offset(&self, address: u64) -> Option<u64> {
+        self.is_file_backed()
+            .then(|| self.offset + address.saturating_sub(self.start))
+    }
+
+    /// Maps a file on disk, pseudo mappings like `[heap]` and anonymous memory are not.
+    /// Neither are memfds, they are listed with a path but only live in memory
+    pub fn is_file_backed(&self) -> bool {
+        self.path
+            .as_ref()
+            .is_some_and(|path| path.starts_with('/') && !path.starts_with("/memfd:"))
+    }
+
+    /// File name of the module the region maps (`libgame.so`), `None` unless it is file-backed
+    pub fn module_name(&self) -> Option<&str> {
+        let path = self.path.as_deref().filter(|path| path.starts_with('/'))?;
+        // files replaced on disk keep being mapped under their old name
+        let path = path.strip_suffix(" (deleted)").unwrap_or(path);
+        path.rsplit('/').next()
+    }
+
+    /// Heap, stack or anonymous memory, where the state of a program almost always lives.
+    /// Android names its anonymous mappings (`[anon:libc_malloc]`), they count too
+    pub fn is_heap_or_stack(&self) -> bool {
+        self.path.as_deref().is_none_or(|path| {
+            path == "[heap]" || path.starts_with("[stack") || path.starts_with("[anon:")
+        })
+    }
+
+    /// File-backed mapping of a shared object (`libc.so.6`, `libfoo.dylib`, `user32.dll`, ...)
+    pub fn is_shared_library(&self) -> bool {
+        self.path.as_ref().is_some_and(|path| {
+            let name = path.rsplit('/').next().unwrap_or("");
+            path.starts_with('/')
+                && (name.contains(".so")
+                    || name.ends_with(".dylib")
+                    || name.to_ascii_lowercase().ends_with(".dll"))
+        })
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -44,33 +114,38 @@ pub fn get_memory_regions(
     start: Option<u64>,
     end: Option<u64>,
     search_perms: Option<&[MemoryRegionPerms]>,
+) -> Result<Vec<MemoryRegion>, MemoryError> {
+    let search_perms = search_perms.unwrap_or(&DEFAULT_SEARCH_PERMS);
+
+    let task = get_task_port(pid).map_err(MemoryError::NoPermission)?;
+    match get_task_regions(pid, task, start, end, search_perms) {
+        Err(MemoryError::MemRead(_)) => {
+            // the cached port may belong to a process that exited, ask for a new one once
+            forget_task_port(pid);
+            let task = get_task_port(pid).map_err(MemoryError::NoPermission)?;
+            get_task_regions(pid, task, start, end, search_perms)
+        }
+        regions => regions,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_task_regions(
+    pid: u32,
+    task: mach_sys::port::mach_port_name_t,
+    start: Option<u64>,
+    end: Option<u64>,
+    search_perms: &[MemoryRegionPerms],
 ) -> Result<Vec<MemoryRegion>, MemoryError> {
     use mach_sys::{
         kern_return::{KERN_INVALID_ADDRESS, KERN_SUCCESS},
-        port::mach_port_name_t,
-        traps::{mach_task_self, task_for_pid},
         vm::mach_vm_region,
-        vm_prot::{VM_PROT_READ, VM_PROT_WRITE},
+        vm_prot::{VM_PROT_EXECUTE, VM_PROT_READ, VM_PROT_WRITE},
         vm_region::{VM_REGION_BASIC_INFO_64, vm_region_info_t},
         vm_types::{mach_vm_address_t, mach_vm_size_t, vm_map_t},
     };
     use mach_sys::{port::mach_port_t, vm_region::vm_region_basic_info_data_64_t};
 
-    let search_perms = search_perms.unwrap_or(&DEFAULT_SEARCH_PERMS);
-
-    let task: mach_port_name_t = 0;
-    let kret = unsafe {
-        task_for_pid(
-            mach_task_self() as mach_port_name_t,
-            pid as i32,
-            &task as *const u32 as *mut u32,
-        )
-    };
-
-    if kret != KERN_SUCCESS {
-        return Err(MemoryError::NoPermission(kret));
-    }
-
     let mut regions = Vec::new();
     let mut address: mach_vm_address_t = start.unwrap_or(1);
     let end: mach_vm_address_t = end.unwrap_or(u64::MAX);
@@ -102,7 +177,7 @@ pub fn get_memory_regions(
             return Err(MemoryError::MemRead(kr));
         }
 
-        let mut perms = Vec::with_capacity(2);
+        let mut perms = Vec::with_capacity(3);
         if info.protection & VM_PROT_READ != 0 {
             perms.push(MemoryRegionPerms::Read);
         }
@@ -111,11 +186,20 @@ pub fn get_memory_regions(
             perms.push(MemoryRegionPerms::Write);
         }
 
+        if info.protection & VM_PROT_EXECUTE != 0 {
+            perms.push(MemoryRegionPerms::Execute);
+        }
+
         if search_perms.iter().filter(|p| perms.contains(p)).count() > 0 {
+            let path = region_file_name(pid, address);
             regions.push(MemoryRegion {
                 start: address,
                 end: address + size,
                 perms,
+                offset: if path.is_some() { info.offset } else { 0 },
+                path,
+                inode: 0,
+                shared: info.shared != 0,
             });
         }
 
@@ -125,7 +209,66 @@ pub fn get_memory_regions(
     Ok(regions)
 }
 
-#[cfg(target_os = "linux")]
+/// Path of the file mapped at `address`, `None` for anonymous memory
+#[cfg(target_os = "macos")]
+fn region_file_name(pid: u32, address: u64) -> Option<String> {
+    let mut name = [0u8; libc::PATH_MAX as usize];
+    let len = unsafe {
+        libc::proc_regionfilename(
+            pid as i32,
+            address,
+            name.as_mut_ptr() as *mut libc::c_void,
+            name.len() as u32,
+        )
+    };
+    if len <= 0 {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&name[..len as usize]).into_owned())
+}
+
+/// Task ports by pid, `task_for_pid` is too slow to run for every read of a scan
+#[cfg(target_os = "macos")]
+static TASK_PORTS: std::sync::OnceLock<
+    std::sync::Mutex<std::collections::HashMap<u32, mach_sys::port::mach_port_name_t>>,
+> = std::sync::OnceLock::new();
+
+#[cfg(target_os = "macos")]
+fn get_task_port(pid: u32) -> Result<mach_sys::port::mach_port_name_t, i32> {
+    use mach_sys::{
+        kern_return::KERN_SUCCESS,
+        port::mach_port_name_t,
+        traps::{mach_task_self, task_for_pid},
+    };
+
+    let ports = TASK_PORTS.get_or_init(Default::default);
+    if let Some(task) = ports.lock().unwrap().get(&pid) {
+        return Ok(*task);
+    }
+
+    let mut task: mach_port_name_t = 0;
+    let kret = unsafe { task_for_pid(mach_task_self() as mach_port_name_t, pid as i32, &mut task) };
+    if kret != KERN_SUCCESS {
+        return Err(kret);
+    }
+
+    ports.lock().unwrap().insert(pid, task);
+    Ok(task)
+}
+
+/// Drops the cached task port, the next call asks the kernel for a new one
+#[cfg(target_os = "macos")]
+fn forget_task_port(pid: u32) {
+    use mach_sys::{mach_port::mach_port_deallocate, traps::mach_task_self};
+
+    if let Some(ports) = TASK_PORTS.get()
+        && let Some(task) = ports.lock().unwrap().remove(&pid)
+    {
+        unsafe { mach_port_deallocate(mach_task_self(), task) };
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn get_memory_regions(
     pid: u32,
     start: Option<u64>,
@@ -142,210 +285,2196 @@ pub fn get_memory_regions(
         .map_err(|e| MemoryError::NoPermission(e.raw_os_error().unwrap_or(-1) as i32))?;
     let reader = io::BufReader::new(file);
 
-    let start_addr = start.unwrap_or(0);
-    let end_addr = end.unwrap_or(u64::MAX);
-
     let mut regions = Vec::new();
 
     for line in reader.lines() {
         let line = line.map_err(|_| MemoryError::MemRead(0))?;
-        // 00400000-00452000 r-xp 00000000 fd:00
-        let mut parts = line.split_whitespace();
-        let range = parts.next().ok_or_else(|| MemoryError::MemRead(0))?;
-        let perms = parts.next().unwrap_or("");
-
-        let mut range_split = range.split('-');
-        let start_str = range_split.next().ok_or_else(|| MemoryError::MemRead(0))?;
-        let end_str = range_split.next().ok_or_else(|| MemoryError::MemRead(0))?;
-        let start_addr_val =
-            u64::from_str_radix(start_str, 16).map_err(|_| MemoryError::MemRead(0))?;
-        let end_addr_val = u64::from_str_radix(end_str, 16).map_err(|_| MemoryError::MemRead(0))?;
-
-        // Filter by address range
-        if end_addr_val < start_addr || start_addr_val > end_addr {
-            continue;
+        let region = parse_maps_line(&line)?;
+        if region.is_selected(start, end, search_perms) {
+            regions.push(region);
         }
+    }
+
+    Ok(regions)
+}
+
+/// Parses a `/proc/<pid>/maps` line, also the format of the region map of a dump
+pub fn parse_maps_line(line: &str) -> Result<MemoryRegion, MemoryError> {
+    // 00400000-00452000 r-xp 00000000 fd:00 1234 /usr/bin/program
+    let mut parts = line.split_whitespace();
+    let range = parts.next().ok_or_else(|| MemoryError::MemRead(0))?;
+    let perms = parts.next().unwrap_or("");
+
+    let mut range_split = range.split('-');
+    let start_str = range_split.next().ok_or_else(|| MemoryError::MemRead(0))?;
+    let end_str = range_split.next().ok_or_else(|| MemoryError::MemRead(0))?;
+    let start = u64::from_str_radix(start_str, 16).map_err(|_| MemoryError::MemRead(0))?;
+    let end = u64::from_str_radix(end_str, 16).map_err(|_| MemoryError::MemRead(0))?;
+
+    let mut region_perms = Vec::with_capacity(3);
+    // the fourth flag is `s` for shared and `p` for private mappings
+    let shared = perms.get(3..4) == Some("s");
+    let perms = perms.get(..3).ok_or(MemoryError::MemRead(0))?;
+
+    if perms.contains('r') {
+        region_perms.push(MemoryRegionPerms::Read);
+    }
+
+    if perms.contains('w') {
+        region_perms.push(MemoryRegionPerms::Write);
+    }
+
+    if perms.contains('x') {
+        region_perms.push(MemoryRegionPerms::Execute);
+    }
+
+    let offset = parts
+        .next()
+        .and_then(|offset| u64::from_str_radix(offset, 16).ok())
+        .unwrap_or(0);
+    // skip the device, the rest of the line after the inode is the pathname
+    let inode = parts
+        .nth(1)
+        .and_then(|inode| inode.parse().ok())
+        .unwrap_or(0);
+    let path = parts.collect::<Vec<_>>().join(" ");
+
+    Ok(MemoryRegion {
+        start,
+        end,
+        perms: region_perms,
+        path: if path.is_empty() { None } else { Some(path) },
+        offset,
+        inode,
+        shared,
+    })
+}
+
+/// The kernel32 calls used on Windows, declared by hand instead of pulling in a bindings crate
+#[cfg(windows)]
+mod kernel32 {
+    pub type Handle = isize;
+
+    pub const PROCESS_QUERY_INFORMATION: u32 = 0x0400;
+    pub const PROCESS_VM_OPERATION: u32 = 0x0008;
+    pub const PROCESS_VM_READ: u32 = 0x0010;
+    pub const PROCESS_VM_WRITE: u32 = 0x0020;
+    pub const MEM_COMMIT: u32 = 0x1000;
+    pub const MEM_PRIVATE: u32 = 0x20000;
+    pub const MEM_MAPPED: u32 = 0x40000;
+    pub const ERROR_PARTIAL_COPY: i32 = 299;
+    pub const ERROR_NOACCESS: i32 = 998;
+    pub const STILL_ACTIVE: u32 = 259;
+
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct MemoryBasicInformation {
+        pub base_address: usize,
+        pub allocation_base: usize,
+        pub allocation_protect: u32,
+        #[cfg(target_pointer_width = "64")]
+        pub partition_id: u16,
+        pub region_size: usize,
+        pub state: u32,
+        pub protect: u32,
+        pub kind: u32,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        pub fn OpenProcess(access: u32, inherit: i32, pid: u32) -> Handle;
+        pub fn CloseHandle(handle: Handle) -> i32;
+        pub fn GetExitCodeProcess(process: Handle, code: *mut u32) -> i32;
+        pub fn VirtualQueryEx(
+            process: Handle,
+            address: usize,
+            info: *mut MemoryBasicInformation,
+            length: usize,
+        ) -> usize;
+        pub fn ReadProcessMemory(
+            process: Handle,
+            address: usize,
+            buffer: *mut u8,
+            size: usize,
+            read: *mut usize,
+        ) -> i32;
+        pub fn WriteProcessMemory(
+            process: Handle,
+            address: usize,
+            buffer: *const u8,
+            size: usize,
+            written: *mut usize,
+        ) -> i32;
+        pub fn K32GetMappedFileNameW(
+            process: Handle,
+            address: usize,
+            filename: *mut u16,
+            size: u32,
+        ) -> u32;
+        pub fn VirtualProtectEx(
+            process: Handle,
+            address: usize,
+            size: usize,
+            protect: u32,
+            old_protect: *mut u32,
+        ) -> i32;
+    }
+}
+
+#[cfg(windows)]
+pub fn get_memory_regions(
+    pid: u32,
+    start: Option<u64>,
+    end: Option<u64>,
+    search_perms: Option<&[MemoryRegionPerms]>,
+) -> Result<Vec<MemoryRegion>, MemoryError> {
+    use kernel32::*;
 
-        let mut region_perms = Vec::with_capacity(2);
-        let perms = &perms[..3];
+    let search_perms = search_perms.unwrap_or(&DEFAULT_SEARCH_PERMS);
+    let mut handle = get_process_handle(pid).map_err(MemoryError::NoPermission)?;
+    // the cached handle keeps an exited process around, open the pid again once
+    let mut exit_code = 0;
+    if unsafe { GetExitCodeProcess(handle, &mut exit_code) } == 0 || exit_code != STILL_ACTIVE {
+        forget_process_handle(pid);
+        handle = get_process_handle(pid).map_err(MemoryError::NoPermission)?;
+    }
 
-        if perms.contains('r') {
-            region_perms.push(MemoryRegionPerms::Read);
+    let mut regions = Vec::new();
+    let mut address = start.unwrap_or(0) as usize;
+    let end = end.unwrap_or(u64::MAX);
+
+    while (address as u64) <= end {
+        let mut info = MemoryBasicInformation::default();
+        let size = std::mem::size_of::<MemoryBasicInformation>();
+        // fails with ERROR_INVALID_PARAMETER past the highest user address
+        if unsafe { VirtualQueryEx(handle, address, &mut info, size) } != size {
+            break;
         }
 
-        if perms.contains('w') {
-            region_perms.push(MemoryRegionPerms::Write);
+        let region = MemoryRegion {
+            start: info.base_address as u64,
+            end: (info.base_address + info.region_size) as u64,
+            perms: protection_perms(info.protect),
+            path: None,
+            offset: 0,
+            inode: 0,
+            shared: info.kind == MEM_MAPPED,
+        };
+        // reserved and free ranges have no pages to read
+        if info.state == MEM_COMMIT && region.is_selected(start, Some(end), search_perms) {
+            let path = if info.kind != MEM_PRIVATE {
+                get_mapped_file_name(handle, info.base_address)
+            } else {
+                None
+            };
+            regions.push(MemoryRegion { path, ..region });
         }
 
-        if search_perms
-            .iter()
-            .filter(|p| region_perms.contains(p))
-            .count()
-            > 0
-        {
-            regions.push(MemoryRegion {
-                start: start_addr_val,
-                end: end_addr_val,
-                perms: region_perms,
-            });
+        match info.base_address.checked_add(info.region_size) {
+            Some(next) if next > address => address = next,
+            _ => break,
         }
     }
 
     Ok(regions)
 }
 
-pub fn read_memory_address(pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
-    let handle = (pid as Pid)
-        .try_into_process_handle()
-        .map_err(|e| MemoryError::ProcessAttach(e.raw_os_error().unwrap_or(-1)))?;
+/// Path of the file mapped at `address` in NT form, with `/` separators
+/// (`/Device/HarddiskVolume3/Windows/System32/ntdll.dll`) like the paths of the other platforms
+#[cfg(windows)]
+fn get_mapped_file_name(handle: kernel32::Handle, address: usize) -> Option<String> {
+    let mut name = [0u16; 1024];
+    let len = unsafe {
+        kernel32::K32GetMappedFileNameW(handle, address, name.as_mut_ptr(), name.len() as u32)
+    };
+    if len == 0 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&name[..len as usize]).replace('\\', "/"))
+}
 
-    let mut result = vec![0; size];
-    handle.copy_address(addr, &mut result).map_err(|e| {
-        // in linux it can attach to process, but not read the memory
-        // so this is a 'hack' to make it like MacOS
-        if std::env::consts::OS == "linux" && e.raw_os_error().unwrap_or(-1) == 1 {
-            return MemoryError::ProcessAttach(1);
-        }
-        MemoryError::MemRead(e.raw_os_error().unwrap_or(-1))
-    })?;
+/// Permissions of a Windows page protection, guard and no-access pages are left unreadable
+/// as touching them faults in the target
+#[cfg_attr(not(windows), allow(dead_code))]
+fn protection_perms(protect: u32) -> Vec<MemoryRegionPerms> {
+    const PAGE_NOACCESS: u32 = 0x01;
+    const PAGE_GUARD: u32 = 0x100;
+    const READABLE: u32 = 0x02 | 0x04 | 0x08 | 0x20 | 0x40 | 0x80;
+    const WRITABLE: u32 = 0x04 | 0x08 | 0x40 | 0x80;
+    const EXECUTABLE: u32 = 0x10 | 0x20 | 0x40 | 0x80;
+
+    let mut perms = Vec::with_capacity(3);
+    if protect & (PAGE_NOACCESS | PAGE_GUARD) != 0 {
+        return perms;
+    }
+    if protect & READABLE != 0 {
+        perms.push(MemoryRegionPerms::Read);
+    }
+    if protect & WRITABLE != 0 {
+        perms.push(MemoryRegionPerms::Write);
+    }
+    if protect & EXECUTABLE != 0 {
+        perms.push(MemoryRegionPerms::Execute);
+    }
+    perms
+}
 
-    Ok(result)
+/// Process handles by pid, opening the process for every read of a scan is too slow
+#[cfg(windows)]
+static PROCESS_HANDLES: std::sync::OnceLock<
+    std::sync::Mutex<std::collections::HashMap<u32, kernel32::Handle>>,
+> = std::sync::OnceLock::new();
+
+#[cfg(windows)]
+fn get_process_handle(pid: u32) -> Result<kernel32::Handle, i32> {
+    use kernel32::*;
+
+    let handles = PROCESS_HANDLES.get_or_init(Default::default);
+    if let Some(handle) = handles.lock().unwrap().get(&pid) {
+        return Ok(*handle);
+    }
+
+    let access =
+        PROCESS_QUERY_INFORMATION | PROCESS_VM_OPERATION | PROCESS_VM_READ | PROCESS_VM_WRITE;
+    let handle = unsafe { OpenProcess(access, 0, pid) };
+    if handle == 0 {
+        return Err(last_os_error());
+    }
+
+    handles.lock().unwrap().insert(pid, handle);
+    Ok(handle)
 }
 
-pub fn write_memory_address(pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
-    let handle = (pid as Pid)
-        .try_into_process_handle()
-        .map_err(|e| MemoryError::ProcessAttach(e.raw_os_error().unwrap_or(-1)))?;
+/// Closes the cached handle, the next call opens the process again
+#[cfg(windows)]
+fn forget_process_handle(pid: u32) {
+    if let Some(handles) = PROCESS_HANDLES.get()
+        && let Some(handle) = handles.lock().unwrap().remove(&pid)
+    {
+        unsafe { kernel32::CloseHandle(handle) };
+    }
+}
 
-    handle
-        .put_address(addr, value)
-        .map_err(|e| MemoryError::MemWrite(e.raw_os_error().unwrap_or(-1)))?;
+#[cfg(windows)]
+fn last_os_error() -> i32 {
+    std::io::Error::last_os_error().raw_os_error().unwrap_or(-1)
+}
 
-    Ok(())
+/// Address range covering a well known part of the process (heap, main stack, a module)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedRange {
+    pub name: String,
+    pub start: u64,
+    pub end: u64,
 }
 
-mod test {
-    #[allow(unused_imports)]
-    use super::*;
-    #[allow(unused_imports)]
-    use std::process::{Command, Stdio};
+/// Returns the heap, the main stack and the range of every loaded module
+pub fn get_named_ranges(pid: u32) -> Result<Vec<NamedRange>, MemoryError> {
+    let regions = get_memory_regions(
+        pid,
+        None,
+        None,
+        Some(&[MemoryRegionPerms::Read, MemoryRegionPerms::Write]),
+    )?;
+    Ok(group_named_ranges(&regions))
+}
 
-    #[test]
-    pub fn test_get_regions_error() {
-        let result = get_memory_regions(0, None, None, None);
+fn group_named_ranges(regions: &[MemoryRegion]) -> Vec<NamedRange> {
+    let mut ranges: Vec<NamedRange> = Vec::new();
+    let mut modules: Vec<NamedRange> = Vec::new();
 
-        assert!(result.is_err());
+    for region in regions {
+        let Some(path) = &region.path else {
+            continue;
+        };
 
-        if let Err(e) = result {
-            match e {
-                MemoryError::NoPermission(_) => assert!(true),
-                _ => assert!(false),
+        // files replaced on disk keep being mapped under their old name
+        let path = path.strip_suffix(" (deleted)").unwrap_or(path);
+        let (group, name) = match path {
+            "[heap]" => (&mut ranges, "heap"),
+            "[stack]" => (&mut ranges, "main stack"),
+            p if p.starts_with('/') => (&mut modules, p.rsplit('/').next().unwrap_or(p)),
+            p => match android_range_name(p) {
+                Some(name) => (&mut ranges, name),
+                None => continue,
+            },
+        };
+
+        // a module is mapped as several consecutive regions
+        match group.iter_mut().find(|r| r.name == name) {
+            Some(range) => {
+                range.start = range.start.min(region.start);
+                range.end = range.end.max(region.end);
             }
-        } else {
-            assert!(false)
+            None => group.push(NamedRange {
+                name: name.to_owned(),
+                start: region.start,
+                end: region.end,
+            }),
         }
     }
 
-    #[test]
-    #[ignore = "requires root"]
-    pub fn test_get_regions_success() {
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+    ranges.extend(modules);
+    ranges
+}
 
-        match proc {
-            Err(e) => assert!(false, "Error running simple program: {e}"),
-            Ok(child) => {
-                let proc = crate::core::utils::ChildGuard(child);
-                let regions = get_memory_regions(proc.0.id(), None, None, None);
-                assert!(regions.is_ok());
-                let regions = regions.unwrap();
-                assert_ne!(regions.len(), 0);
-            }
-        }
+/// Android has no `[heap]`, bionic names its anonymous mappings after the allocator
+/// (`[anon:libc_malloc]`, `[anon:scudo:primary]`) and ART after the space of the Java heap
+/// (`[anon:dalvik-main space (region space)]`)
+fn android_range_name(path: &str) -> Option<&'static str> {
+    let name = path.strip_prefix("[anon:")?.strip_suffix(']')?;
+    if name == "libc_malloc" || name.starts_with("scudo:") || name.starts_with("jemalloc") {
+        Some("native heap")
+    } else if name.starts_with("dalvik-") && name.contains("space") {
+        Some("java heap")
+    } else {
+        None
     }
+}
 
-    #[test]
-    #[ignore = "requires root"]
-    pub fn test_get_regions_readonly_success() {
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+/// Memory usage of a single mapping as reported by `/proc/<pid>/smaps`, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RegionStats {
+    pub rss: u64,
+    pub swap: u64,
+    pub shared: u64,
+    pub private: u64,
+}
 
-        match proc {
-            Err(e) => assert!(false, "Error running simple program: {e}"),
-            Ok(child) => {
-                let proc = crate::core::utils::ChildGuard(child);
-                let regions =
-                    get_memory_regions(proc.0.id(), None, None, Some(&[MemoryRegionPerms::Read]));
-                assert!(regions.is_ok());
-                let regions = regions.unwrap();
-                assert_ne!(regions.len(), 0);
-            }
-        }
-    }
+/// Returns every mapping of the process together with its smaps statistics
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_region_stats(pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+    let content = std::fs::read_to_string(format!("/proc/{pid}/smaps"))
+        .map_err(|e| MemoryError::NoPermission(e.raw_os_error().unwrap_or(-1)))?;
+    parse_smaps(&content)
+}
 
-    #[test]
-    #[ignore = "requires root"]
-    pub fn test_get_regions_with_range_success() {
-        use std::process::{Command, Stdio};
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn get_region_stats(_pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+    // smaps is only exposed through procfs
+    Err(MemoryError::MemRead(-1))
+}
 
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_smaps(content: &str) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+    let mut regions: Vec<(MemoryRegion, RegionStats)> = Vec::new();
 
-        match proc {
-            Err(e) => assert!(false, "Error running simple program: {e}"),
-            Ok(child) => {
-                let proc = crate::core::utils::ChildGuard(child);
-                let regions = get_memory_regions(proc.0.id(), Some(u64::MAX), None, None);
-                assert!(regions.is_ok());
-                let regions = regions.unwrap();
-                assert_eq!(regions.len(), 0);
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(key) = parts.next() else {
+            continue;
+        };
 
-                let regions = get_memory_regions(proc.0.id(), None, Some(0), None);
-                assert!(regions.is_ok());
-                let regions = regions.unwrap();
-                assert_eq!(regions.len(), 0);
-            }
+        // every mapping starts with a maps formatted header followed by `Key: value kB` lines
+        if !key.ends_with(':') {
+            regions.push((parse_maps_line(line)?, RegionStats::default()));
+            continue;
+        }
+
+        let Some((_, stats)) = regions.last_mut() else {
+            return Err(MemoryError::MemRead(0));
+        };
+        let Some(kb) = parts.next().and_then(|v| v.parse::<u64>().ok()) else {
+            continue;
+        };
+        let bytes = kb * 1024;
+
+        match key {
+            "Rss:" => stats.rss = bytes,
+            "Swap:" => stats.swap = bytes,
+            "Shared_Clean:" | "Shared_Dirty:" => stats.shared += bytes,
+            "Private_Clean:" | "Private_Dirty:" => stats.private += bytes,
+            _ => {}
         }
     }
 
-    #[test]
-    #[ignore = "requires root"]
-    pub fn test_read_memory_address_success() {
-        use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
+    Ok(regions)
+}
 
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap();
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageState {
+    Resident,
+    Swapped,
+    NotPresent,
+}
 
-        let mut proc = crate::core::utils::ChildGuard(proc);
-        let stdout = proc.0.stdout.take().expect("child had no stdout");
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
+#[cfg(windows)]
+pub fn get_page_size() -> u64 {
+    0x1000
+}
 
-        reader.read_line(&mut line).unwrap();
+#[cfg(not(windows))]
+pub fn get_page_size() -> u64 {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 { size as u64 } else { 0x1000 }
+}
 
-        let hex_str = line.trim();
-        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
-            .expect("failed to parse hex");
+/// Returns the state of every page in `start..end` using `/proc/<pid>/pagemap`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_page_states(pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+    use std::fs::File;
+    use std::os::unix::fs::FileExt;
+
+    const PAGE_PRESENT: u64 = 1 << 63;
+    const PAGE_SWAPPED: u64 = 1 << 62;
+    /// Entries read at once, large ranges are read in several reads
+    const CHUNK_PAGES: u64 = 4096;
+
+    let page_size = get_page_size();
+    let file = File::open(format!("/proc/{pid}/pagemap"))
+        .map_err(|e| MemoryError::NoPermission(e.raw_os_error().unwrap_or(-1)))?;
+
+    // every page has a single 64 bit entry
+    let (first_page, end_page) = (start / page_size, end.div_ceil(page_size));
+    let mut states = Vec::with_capacity(end_page.saturating_sub(first_page) as usize);
+    let mut entries = vec![0; CHUNK_PAGES as usize * 8];
+    let mut page = first_page;
+    while page < end_page {
+        let count = (end_page - page).min(CHUNK_PAGES) as usize;
+        let entries = &mut entries[..count * 8];
+        file.read_exact_at(entries, page * 8)
+            .map_err(|e| MemoryError::MemRead(e.raw_os_error().unwrap_or(-1)))?;
+        states.extend(entries.chunks_exact(8).map(|entry| {
+            let entry = u64::from_le_bytes(entry.try_into().unwrap());
+            if entry & PAGE_PRESENT != 0 {
+                PageState::Resident
+            } else if entry & PAGE_SWAPPED != 0 {
+                PageState::Swapped
+            } else {
+                PageState::NotPresent
+            }
+        }));
+        page += count as u64;
+    }
+
+    Ok(states)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn get_page_states(_pid: u32, _start: u64, _end: u64) -> Result<Vec<PageState>, MemoryError> {
+    // page residency is only exposed through procfs
+    Err(MemoryError::MemRead(-1))
+}
+
+/// Command that lifts the Yama restriction until the next reboot
+pub const PTRACE_SCOPE_FIX_CMD: &str = "echo 0 | sudo tee /proc/sys/kernel/yama/ptrace_scope";
+
+/// Reads the Yama ptrace restriction level, `None` when Yama is not enabled
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_ptrace_scope() -> Option<u8> {
+    std::fs::read_to_string("/proc/sys/kernel/yama/ptrace_scope")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn get_ptrace_scope() -> Option<u8> {
+    None
+}
+
+/// Explains why attaching is blocked by the given ptrace scope,
+/// `None` when the scope does not get in the way for the current user
+pub fn ptrace_scope_hint(scope: u8, is_root: bool) -> Option<&'static str> {
+    match scope {
+        1 if !is_root => Some(
+            "Yama ptrace_scope is 1: only parent processes can be attached. Run with sudo or set it to 0",
+        ),
+        2 if !is_root => {
+            Some("Yama ptrace_scope is 2: only root can attach to processes. Run with sudo")
+        }
+        3 => Some("Yama ptrace_scope is 3: attaching is disabled until reboot"),
+        _ => None,
+    }
+}
+
+/// Reading other processes only needs the debug privilege of an elevated prompt on Windows,
+/// which is not checked up front
+#[cfg(windows)]
+pub fn is_root() -> bool {
+    false
+}
+
+#[cfg(not(windows))]
+pub fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Command that signs the binary with the entitlements file shipped in the repository
+pub const MACOS_SIGN_CMD: &str =
+    "codesign --force --sign - --entitlements entitlements.plist target/release/cheat-engine-rs";
+
+/// What decides whether the task ports of other processes can be obtained on macOS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacosSetup {
+    pub is_root: bool,
+    /// `com.apple.security.cs.debugger`, attaches to debuggable builds without root
+    pub debugger_entitlement: bool,
+    /// `None` when `csrutil` could not tell
+    pub sip_enabled: Option<bool>,
+}
+
+impl MacosSetup {
+    pub fn can_attach(&self) -> bool {
+        self.is_root || self.debugger_entitlement
+    }
+
+    /// Instructions for everything that gets in the way of attaching, empty when nothing does
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = vec![];
+        if !self.can_attach() {
+            steps.push("Run it as root: sudo ./target/release/cheat-engine-rs".to_owned());
+            steps.push(format!(
+                "Or sign it with the debugger entitlement to attach to debug builds without root: {MACOS_SIGN_CMD}"
+            ));
+        }
+        if self.sip_enabled == Some(true) {
+            steps.push(
+                "SIP is enabled: Apple system processes and hardened runtime apps can not be attached, even as root"
+                    .to_owned(),
+            );
+        }
+        steps
+    }
+}
+
+/// Checks root, the code signature and SIP, `None` on systems without task ports
+#[cfg(target_os = "macos")]
+pub fn get_macos_setup() -> Option<MacosSetup> {
+    use std::process::Command;
+
+    let sip_enabled = Command::new("csrutil")
+        .arg("status")
+        .output()
+        .ok()
+        .and_then(|output| parse_csrutil_status(&String::from_utf8_lossy(&output.stdout)));
+
+    // codesign prints the entitlements plist of the running binary
+    let debugger_entitlement = std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            Command::new("codesign")
+                .args(["--display", "--entitlements", "-", "--xml"])
+                .arg(exe)
+                .output()
+                .ok()
+        })
+        .is_some_and(|output| {
+            String::from_utf8_lossy(&output.stdout).contains("com.apple.security.cs.debugger")
+        });
+
+    Some(MacosSetup {
+        is_root: is_root(),
+        debugger_entitlement,
+        sip_enabled,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn get_macos_setup() -> Option<MacosSetup> {
+    None
+}
+
+/// System Integrity Protection status: enabled.
+#[cfg(target_os = "macos")]
+fn parse_csrutil_status(output: &str) -> Option<bool> {
+    let (_, status) = output.split_once("status:")?;
+    match status.trim().trim_end_matches('.') {
+        "enabled" => Some(true),
+        "disabled" => Some(false),
+        // custom configurations only turn off parts of SIP
+        _ => None,
+    }
+}
+
+/// Bytes read at once by scans, mach reads have a higher fixed cost than `process_vm_readv`
+/// so macOS reads larger blocks
+#[cfg(target_os = "macos")]
+pub const READ_BLOCK_SIZE: usize = 0x100000;
+#[cfg(not(target_os = "macos"))]
+pub const READ_BLOCK_SIZE: usize = 0x10000;
+
+/// Reads through the cached task port instead of opening a new one for every read
+#[cfg(target_os = "macos")]
+pub fn read_memory_address(pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+    use mach_sys::{
+        kern_return::{KERN_INVALID_ADDRESS, KERN_PROTECTION_FAILURE, KERN_SUCCESS},
+        vm::mach_vm_read_overwrite,
+        vm_types::{mach_vm_address_t, mach_vm_size_t, vm_map_t},
+    };
+
+    let task = get_task_port(pid).map_err(MemoryError::ProcessAttach)?;
+
+    let mut result = vec![0; size];
+    let mut read: mach_vm_size_t = 0;
+    let kr = unsafe {
+        mach_vm_read_overwrite(
+            task as vm_map_t,
+            addr as mach_vm_address_t,
+            size as mach_vm_size_t,
+            result.as_mut_ptr() as mach_vm_address_t,
+            &mut read,
+        )
+    };
+
+    if kr != KERN_SUCCESS {
+        // unmapped or protected pages are expected while scanning, anything else means
+        // the port is no longer usable
+        if kr != KERN_INVALID_ADDRESS && kr != KERN_PROTECTION_FAILURE {
+            forget_task_port(pid);
+        }
+        return Err(MemoryError::MemRead(kr));
+    }
+    if read as usize != size {
+        return Err(MemoryError::MemRead(kr));
+    }
+
+    Ok(result)
+}
+
+/// Reads through the cached process handle instead of opening the process for every read
+#[cfg(windows)]
+pub fn read_memory_address(pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+    use kernel32::*;
+
+    let handle = get_process_handle(pid).map_err(MemoryError::ProcessAttach)?;
+
+    let mut result = vec![0; size];
+    let mut read = 0;
+    if unsafe { ReadProcessMemory(handle, addr, result.as_mut_ptr(), size, &mut read) } == 0 {
+        let e = last_os_error();
+        // unreadable pages are expected while scanning, anything else means the handle
+        // is no longer usable
+        if e != ERROR_PARTIAL_COPY && e != ERROR_NOACCESS {
+            forget_process_handle(pid);
+        }
+        return Err(MemoryError::MemRead(e));
+    }
+    if read != size {
+        return Err(MemoryError::MemRead(ERROR_PARTIAL_COPY));
+    }
+
+    Ok(result)
+}
+
+/// Reads with `process_vm_readv` directly, `copy_address` of process-memory takes a short
+/// read for a complete one and leaves the bytes past the first unreadable page zeroed
+#[cfg(not(any(target_os = "macos", windows)))]
+pub fn read_memory_address(pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+    process_vm_read(pid, addr, size)
+}
+
+/// Requests of a batched read closer than this are read as one range
+const BATCH_GAP: usize = 64;
+/// Largest range read for a group of requests, a failing range is retried request by request
+const BATCH_SPAN: usize = 0x1000;
+
+/// Nearby requests read as one range, `requests` are indices into the requests of the batch
+struct ReadGroup {
+    start: usize,
+    end: usize,
+    requests: Vec<usize>,
+}
+
+/// Groups `(address, size)` requests that are close to each other
+fn group_requests(requests: &[(usize, usize)]) -> Vec<ReadGroup> {
+    let mut order: Vec<usize> = (0..requests.len()).collect();
+    order.sort_by_key(|&i| requests[i].0);
+
+    let mut groups: Vec<ReadGroup> = Vec::new();
+    for i in order {
+        let (start, size) = requests[i];
+        let end = start.saturating_add(size);
+        match groups.last_mut() {
+            Some(group)
+                if start <= group.end.saturating_add(BATCH_GAP)
+                    && end.max(group.end) - group.start <= BATCH_SPAN =>
+            {
+                group.end = group.end.max(end);
+                group.requests.push(i);
+            }
+            _ => groups.push(ReadGroup {
+                start,
+                end,
+                requests: vec![i],
+            }),
+        }
+    }
+    groups
+}
+
+/// Copies the values of a group out of its range, `data` starts at `group.start`
+fn split_group(
+    group: &ReadGroup,
+    data: &[u8],
+    requests: &[(usize, usize)],
+    values: &mut [Option<Vec<u8>>],
+) {
+    for &i in &group.requests {
+        let (address, size) = requests[i];
+        let offset = address - group.start;
+        values[i] = Some(data[offset..offset + size].to_vec());
+    }
+}
+
+/// Reads the requests of a group whose range could not be read one by one,
+/// only losing the ones that are unreadable themselves
+fn read_group_requests(
+    pid: u32,
+    group: &ReadGroup,
+    requests: &[(usize, usize)],
+    values: &mut [Option<Vec<u8>>],
+) -> Result<(), MemoryError> {
+    for &i in &group.requests {
+        let (address, size) = requests[i];
+        match read_memory_address(pid, address, size) {
+            Ok(value) => values[i] = Some(value),
+            Err(MemoryError::ProcessAttach(e)) => return Err(MemoryError::ProcessAttach(e)),
+            Err(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Reads many `(address, size)` requests with few syscalls, nearby addresses are read
+/// as one range. Unreadable requests are `None`, only attach errors fail the whole batch
+pub fn read_memory_addresses(
+    pid: u32,
+    requests: &[(usize, usize)],
+) -> Result<Vec<Option<Vec<u8>>>, MemoryError> {
+    let mut values = vec![None; requests.len()];
+    read_groups(pid, &group_requests(requests), requests, &mut values)?;
+    Ok(values)
+}
+
+/// Reads the ranges of up to `IOV_MAX` groups with a single `process_vm_readv`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_groups(
+    pid: u32,
+    groups: &[ReadGroup],
+    requests: &[(usize, usize)],
+    values: &mut [Option<Vec<u8>>],
+) -> Result<(), MemoryError> {
+    const IOV_MAX: usize = 1024;
+
+    let mut next = 0;
+    while next < groups.len() {
+        let batch = &groups[next..groups.len().min(next + IOV_MAX)];
+        let mut data = vec![0_u8; batch.iter().map(|g| g.end - g.start).sum()];
+        let mut local = Vec::with_capacity(batch.len());
+        let mut remote = Vec::with_capacity(batch.len());
+        let mut offset = 0;
+        for group in batch {
+            let len = group.end - group.start;
+            local.push(libc::iovec {
+                iov_base: data[offset..].as_mut_ptr() as *mut libc::c_void,
+                iov_len: len,
+            });
+            remote.push(libc::iovec {
+                iov_base: group.start as *mut libc::c_void,
+                iov_len: len,
+            });
+            offset += len;
+        }
+
+        let read = unsafe {
+            libc::process_vm_readv(
+                pid as libc::pid_t,
+                local.as_ptr(),
+                local.len() as libc::c_ulong,
+                remote.as_ptr(),
+                remote.len() as libc::c_ulong,
+                0,
+            )
+        };
+        // the same error `read_memory_address` reports as an attach error
+        let read = if read < 0 {
+            let errno = std::io::Error::last_os_error().raw_os_error().unwrap_or(-1);
+            if errno == libc::EPERM {
+                return Err(MemoryError::ProcessAttach(1));
+            }
+            0
+        } else {
+            read as usize
+        };
+
+        // partial reads stop at the first range that could not be read completely
+        let mut offset = 0;
+        let mut done = 0;
+        for group in batch {
+            let len = group.end - group.start;
+            if offset + len > read {
+                break;
+            }
+            split_group(group, &data[offset..offset + len], requests, values);
+            offset += len;
+            done += 1;
+        }
+        if let Some(group) = batch.get(done) {
+            read_group_requests(pid, group, requests, values)?;
+            done += 1;
+        }
+        next += done;
+    }
+    Ok(())
+}
+
+/// Reads every group's range with a single read, mach reads have no vectored version
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn read_groups(
+    pid: u32,
+    groups: &[ReadGroup],
+    requests: &[(usize, usize)],
+    values: &mut [Option<Vec<u8>>],
+) -> Result<(), MemoryError> {
+    for group in groups {
+        match read_memory_address(pid, group.start, group.end - group.start) {
+            Ok(data) => split_group(group, &data, requests, values),
+            Err(MemoryError::ProcessAttach(e)) => return Err(MemoryError::ProcessAttach(e)),
+            Err(_) => read_group_requests(pid, group, requests, values)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn write_memory_address(pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+    use kernel32::*;
+
+    let handle = get_process_handle(pid).map_err(MemoryError::ProcessAttach)?;
+
+    let mut written = 0;
+    let ok = unsafe { WriteProcessMemory(handle, addr, value.as_ptr(), value.len(), &mut written) };
+    if ok == 0 {
+        return Err(MemoryError::MemWrite(last_os_error()));
+    }
+    if written != value.len() {
+        return Err(MemoryError::MemWrite(ERROR_PARTIAL_COPY));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn write_memory_address(pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+    let handle = (pid as Pid)
+        .try_into_process_handle()
+        .map_err(|e| MemoryError::ProcessAttach(e.raw_os_error().unwrap_or(-1)))?;
+
+    handle
+        .put_address(addr, value)
+        .map_err(|e| MemoryError::MemWrite(e.raw_os_error().unwrap_or(-1)))?;
+
+    Ok(())
+}
+
+/// Writes to pages that are not writable. `/proc/<pid>/mem` writes the way debuggers set
+/// breakpoints, the kernel copies the page for the write and its protection never changes
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn write_protected_memory_address(
+    pid: u32,
+    addr: usize,
+    value: &[u8],
+) -> Result<(), MemoryError> {
+    ProcMemMemory::default().write(pid, addr, value)
+}
+
+/// Writes to pages that are not writable, they are made writable (copy-on-write for
+/// mapped files) for the write and get their protection back after it
+#[cfg(target_os = "macos")]
+pub fn write_protected_memory_address(
+    pid: u32,
+    addr: usize,
+    value: &[u8],
+) -> Result<(), MemoryError> {
+    use mach_sys::{
+        kern_return::KERN_SUCCESS,
+        vm::mach_vm_protect,
+        vm_prot::{VM_PROT_COPY, VM_PROT_READ, VM_PROT_WRITE},
+    };
+
+    let task = get_task_port(pid).map_err(MemoryError::ProcessAttach)?;
+    let region = get_task_regions(
+        pid,
+        task,
+        Some(addr as u64),
+        Some(addr as u64),
+        &[MemoryRegionPerms::Read],
+    )?
+    .into_iter()
+    .find(|region| region.start <= addr as u64 && (addr as u64) < region.end)
+    .ok_or(MemoryError::MemWrite(libc::EFAULT))?;
+    let mut protection = 0;
+    for (perm, prot) in [
+        (MemoryRegionPerms::Read, VM_PROT_READ),
+        (MemoryRegionPerms::Write, VM_PROT_WRITE),
+        (
+            MemoryRegionPerms::Execute,
+            mach_sys::vm_prot::VM_PROT_EXECUTE,
+        ),
+    ] {
+        if region.perms.contains(&perm) {
+            protection |= prot;
+        }
+    }
+
+    let page_size = get_page_size() as u64;
+    let start = addr as u64 - addr as u64 % page_size;
+    let size = (addr as u64 + value.len() as u64 - start).div_ceil(page_size) * page_size;
+    let writable = protection | VM_PROT_WRITE | VM_PROT_COPY;
+    let kr = unsafe { mach_vm_protect(task, start, size, 0, writable) };
+    if kr != KERN_SUCCESS {
+        return Err(MemoryError::MemWrite(kr));
+    }
+    let written = write_memory_address(pid, addr, value);
+    let kr = unsafe { mach_vm_protect(task, start, size, 0, protection) };
+    written?;
+    if kr != KERN_SUCCESS {
+        return Err(MemoryError::MemWrite(kr));
+    }
+    Ok(())
+}
+
+/// Writes to pages that are not writable, they are made writable for the write and get
+/// their protection back after it
+#[cfg(windows)]
+pub fn write_protected_memory_address(
+    pid: u32,
+    addr: usize,
+    value: &[u8],
+) -> Result<(), MemoryError> {
+    use kernel32::*;
+    const PAGE_EXECUTE_READWRITE: u32 = 0x40;
+
+    let handle = get_process_handle(pid).map_err(MemoryError::ProcessAttach)?;
+    let mut old_protect = 0;
+    let ok = unsafe {
+        VirtualProtectEx(
+            handle,
+            addr,
+            value.len(),
+            PAGE_EXECUTE_READWRITE,
+            &mut old_protect,
+        )
+    };
+    if ok == 0 {
+        return Err(MemoryError::MemWrite(last_os_error()));
+    }
+    let written = write_memory_address(pid, addr, value);
+    let mut ignored = 0;
+    let ok = unsafe { VirtualProtectEx(handle, addr, value.len(), old_protect, &mut ignored) };
+    written?;
+    if ok == 0 {
+        return Err(MemoryError::MemWrite(last_os_error()));
+    }
+    Ok(())
+}
+
+/// Access to the memory of processes, scans go through it instead of the functions above
+/// so the scan logic stays the same whichever way the memory is reached
+pub trait MemoryBackend: Send + Sync {
+    /// Mappings overlapping `start..=end` with one of the permissions searched for,
+    /// writable mappings when `search_perms` is `None`
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError>;
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError>;
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError>;
+
+    /// Writes to pages that are not writable, the protection is only lifted for the write.
+    /// Fails when the backend can not do it
+    fn write_protected(&self, _pid: u32, _addr: usize, _value: &[u8]) -> Result<(), MemoryError> {
+        Err(MemoryError::MemWrite(-1))
+    }
+
+    /// Reads many `(address, size)` requests, one read each unless the backend batches them.
+    /// Unreadable requests are `None`, only attach errors fail the whole batch
+    fn read_many(
+        &self,
+        pid: u32,
+        requests: &[(usize, usize)],
+    ) -> Result<Vec<Option<Vec<u8>>>, MemoryError> {
+        requests
+            .iter()
+            .map(|&(addr, size)| match self.read(pid, addr, size) {
+                Ok(value) => Ok(Some(value)),
+                Err(MemoryError::ProcessAttach(e)) => Err(MemoryError::ProcessAttach(e)),
+                Err(_) => Ok(None),
+            })
+            .collect()
+    }
+
+    /// Every mapping with its memory usage, fails when the backend can not tell
+    fn region_stats(&self, _pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        Err(MemoryError::MemRead(-1))
+    }
+
+    /// State of every page in `start..end`, fails when the backend can not tell
+    fn page_states(
+        &self,
+        _pid: u32,
+        _start: u64,
+        _end: u64,
+    ) -> Result<Vec<PageState>, MemoryError> {
+        Err(MemoryError::MemRead(-1))
+    }
+
+    /// The heap, the main stack and the range of every loaded module
+    fn named_ranges(&self, pid: u32) -> Result<Vec<NamedRange>, MemoryError> {
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        Ok(group_named_ranges(&self.regions(
+            pid,
+            None,
+            None,
+            Some(&perms),
+        )?))
+    }
+
+    /// Size of a pointer in the target in bytes, `None` when it can not be told
+    fn pointer_width(&self, pid: u32) -> Option<usize> {
+        crate::core::proc::get_pointer_width(pid)
+    }
+}
+
+impl std::fmt::Debug for dyn MemoryBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MemoryBackend")
+    }
+}
+
+/// Processes of this machine, read and written with the system calls of the platform
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeMemory;
+
+impl MemoryBackend for NativeMemory {
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        get_memory_regions(pid, start, end, search_perms)
+    }
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        read_memory_address(pid, addr, size)
+    }
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        write_memory_address(pid, addr, value)
+    }
+
+    fn write_protected(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        write_protected_memory_address(pid, addr, value)
+    }
+
+    fn read_many(
+        &self,
+        pid: u32,
+        requests: &[(usize, usize)],
+    ) -> Result<Vec<Option<Vec<u8>>>, MemoryError> {
+        read_memory_addresses(pid, requests)
+    }
+
+    fn region_stats(&self, pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        get_region_stats(pid)
+    }
+
+    fn page_states(&self, pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+        get_page_states(pid, start, end)
+    }
+}
+
+/// Reads like the native backend and writes with `process_vm_writev` directly, short writes
+/// fail with `EFAULT` instead of passing for complete ones and a process that exited is
+/// reported as an attach error
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessVmMemory;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl MemoryBackend for ProcessVmMemory {
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        get_memory_regions(pid, start, end, search_perms)
+    }
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        process_vm_read(pid, addr, size)
+    }
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        let local = libc::iovec {
+            iov_base: value.as_ptr() as *mut libc::c_void,
+            iov_len: value.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: addr as *mut libc::c_void,
+            iov_len: value.len(),
+        };
+        let pid = process_vm_pid(pid).map_err(MemoryError::ProcessAttach)?;
+        let written = unsafe { libc::process_vm_writev(pid, &local, 1, &remote, 1, 0) };
+        match written {
+            -1 => Err(process_vm_error(MemoryError::MemWrite)),
+            written if written as usize != value.len() => Err(MemoryError::MemWrite(libc::EFAULT)),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_protected(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        write_protected_memory_address(pid, addr, value)
+    }
+
+    fn read_many(
+        &self,
+        pid: u32,
+        requests: &[(usize, usize)],
+    ) -> Result<Vec<Option<Vec<u8>>>, MemoryError> {
+        // already scatter-gather reads of up to `IOV_MAX` ranges
+        read_memory_addresses(pid, requests)
+    }
+
+    fn region_stats(&self, pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        get_region_stats(pid)
+    }
+
+    fn page_states(&self, pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+        get_page_states(pid, start, end)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_vm_pid(pid: u32) -> Result<libc::pid_t, i32> {
+    libc::pid_t::try_from(pid)
+        .ok()
+        .filter(|pid| *pid > 0)
+        .ok_or(libc::ESRCH)
+}
+
+/// Reads of the native backend and of `ProcessVmMemory`, a short read fails with `EFAULT`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_vm_read(pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+    let mut result = vec![0; size];
+    let local = libc::iovec {
+        iov_base: result.as_mut_ptr() as *mut libc::c_void,
+        iov_len: size,
+    };
+    let remote = libc::iovec {
+        iov_base: addr as *mut libc::c_void,
+        iov_len: size,
+    };
+    let pid = process_vm_pid(pid).map_err(MemoryError::ProcessAttach)?;
+    let read = unsafe { libc::process_vm_readv(pid, &local, 1, &remote, 1, 0) };
+    match read {
+        -1 => Err(process_vm_error(MemoryError::MemRead)),
+        read if read as usize != size => Err(MemoryError::MemRead(libc::EFAULT)),
+        _ => Ok(result),
+    }
+}
+
+/// `EPERM` and `ESRCH` concern the whole process, anything else the address range
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn process_vm_error(error: fn(i32) -> MemoryError) -> MemoryError {
+    match std::io::Error::last_os_error().raw_os_error().unwrap_or(-1) {
+        errno @ (libc::EPERM | libc::ESRCH) => MemoryError::ProcessAttach(errno),
+        errno => error(errno),
+    }
+}
+
+/// Reads and writes a word at a time with `PTRACE_PEEKDATA` / `PTRACE_POKEDATA`, for systems
+/// where seccomp blocks `process_vm_readv`. The process is attached for every call and stopped
+/// while it is accessed, so this is much slower than the other backends
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug, Default)]
+pub struct PtraceMemory {
+    /// A process can only be attached once, calls from several scan threads take turns
+    lock: std::sync::Mutex<()>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl PtraceMemory {
+    const WORD: usize = std::mem::size_of::<libc::c_long>();
+
+    /// Runs `f` while the process is attached and stopped
+    fn attached<T>(
+        &self,
+        pid: u32,
+        f: impl FnOnce(libc::pid_t) -> Result<T, MemoryError>,
+    ) -> Result<T, MemoryError> {
+        let _lock = self.lock.lock().unwrap();
+        let pid = process_vm_pid(pid).map_err(MemoryError::ProcessAttach)?;
+        let null = std::ptr::null_mut::<libc::c_void>();
+
+        if unsafe { libc::ptrace(libc::PTRACE_ATTACH, pid, null, null) } == -1 {
+            return Err(MemoryError::ProcessAttach(last_errno()));
+        }
+        let mut status = 0;
+        let result = if unsafe { libc::waitpid(pid, &mut status, libc::__WALL) } == -1 {
+            Err(MemoryError::ProcessAttach(last_errno()))
+        } else {
+            f(pid)
+        };
+        unsafe { libc::ptrace(libc::PTRACE_DETACH, pid, null, null) };
+        result
+    }
+
+    fn peek(pid: libc::pid_t, addr: usize) -> Result<[u8; Self::WORD], i32> {
+        // -1 is also a valid word, only errno tells failures apart
+        unsafe { *libc::__errno_location() = 0 };
+        let word = unsafe {
+            libc::ptrace(
+                libc::PTRACE_PEEKDATA,
+                pid,
+                addr as *mut libc::c_void,
+                std::ptr::null_mut::<libc::c_void>(),
+            )
+        };
+        match last_errno() {
+            0 => Ok(word.to_ne_bytes()),
+            errno if word == -1 => Err(errno),
+            _ => Ok(word.to_ne_bytes()),
+        }
+    }
+
+    fn poke(pid: libc::pid_t, addr: usize, word: [u8; Self::WORD]) -> Result<(), i32> {
+        let word = libc::c_long::from_ne_bytes(word);
+        let result = unsafe {
+            libc::ptrace(
+                libc::PTRACE_POKEDATA,
+                pid,
+                addr as *mut libc::c_void,
+                word as *mut libc::c_void,
+            )
+        };
+        if result == -1 {
+            Err(last_errno())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl MemoryBackend for PtraceMemory {
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        get_memory_regions(pid, start, end, search_perms)
+    }
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        let first = addr - addr % Self::WORD;
+        let end = addr
+            .checked_add(size)
+            .ok_or(MemoryError::MemRead(libc::EFAULT))?;
+        self.attached(pid, |pid| {
+            let mut data = Vec::with_capacity(end - first + Self::WORD);
+            for word in (first..end).step_by(Self::WORD) {
+                data.extend(Self::peek(pid, word).map_err(MemoryError::MemRead)?);
+            }
+            Ok(data[addr - first..addr - first + size].to_vec())
+        })
+    }
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        let first = addr - addr % Self::WORD;
+        let end = addr
+            .checked_add(value.len())
+            .ok_or(MemoryError::MemWrite(libc::EFAULT))?;
+        self.attached(pid, |pid| {
+            for word_addr in (first..end).step_by(Self::WORD) {
+                // words only partly covered by the value keep the rest of their bytes
+                let mut word = if word_addr < addr || word_addr + Self::WORD > end {
+                    Self::peek(pid, word_addr).map_err(MemoryError::MemWrite)?
+                } else {
+                    [0; Self::WORD]
+                };
+                for (i, byte) in word.iter_mut().enumerate() {
+                    if let Some(offset) = (word_addr + i).checked_sub(addr)
+                        && let Some(new) = value.get(offset)
+                    {
+                        *byte = *new;
+                    }
+                }
+                Self::poke(pid, word_addr, word).map_err(MemoryError::MemWrite)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// `PTRACE_POKEDATA` writes like a debugger setting breakpoints, read-only pages too
+    fn write_protected(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.write(pid, addr, value)
+    }
+
+    fn region_stats(&self, pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        get_region_stats(pid)
+    }
+
+    fn page_states(&self, pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+        get_page_states(pid, start, end)
+    }
+}
+
+/// Reads and writes with `pread` / `pwrite` on `/proc/<pid>/mem`, a single call covers any
+/// range and the target keeps running while it is read
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug, Default)]
+pub struct ProcMemMemory {
+    /// Open file of the last process, opening it for every read of a scan is too slow
+    file: std::sync::Mutex<Option<(u32, std::sync::Arc<std::fs::File>)>>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl ProcMemMemory {
+    fn file(&self, pid: u32) -> Result<std::sync::Arc<std::fs::File>, MemoryError> {
+        let mut cached = self.file.lock().unwrap();
+        if let Some((cached_pid, file)) = cached.as_ref()
+            && *cached_pid == pid
+        {
+            return Ok(file.clone());
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/proc/{pid}/mem"))
+            .or_else(|_| std::fs::File::open(format!("/proc/{pid}/mem")))
+            .map_err(|e| match e.raw_os_error().unwrap_or(-1) {
+                libc::ENOENT => MemoryError::ProcessAttach(libc::ESRCH),
+                errno => MemoryError::ProcessAttach(errno),
+            })?;
+        let file = std::sync::Arc::new(file);
+        *cached = Some((pid, file.clone()));
+        Ok(file)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl MemoryBackend for ProcMemMemory {
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        get_memory_regions(pid, start, end, search_perms)
+    }
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        use std::os::unix::fs::FileExt;
+
+        let mut result = vec![0; size];
+        // unmapped addresses fail with EIO, a short read stops at the first of them
+        self.file(pid)?
+            .read_exact_at(&mut result, addr as u64)
+            .map_err(|e| MemoryError::MemRead(e.raw_os_error().unwrap_or(libc::EIO)))?;
+        Ok(result)
+    }
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        use std::os::unix::fs::FileExt;
+
+        self.file(pid)?
+            .write_all_at(value, addr as u64)
+            .map_err(|e| MemoryError::MemWrite(e.raw_os_error().unwrap_or(libc::EIO)))
+    }
+
+    /// Writes to `/proc/<pid>/mem` ignore the protection of the page anyway
+    fn write_protected(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.write(pid, addr, value)
+    }
+
+    fn region_stats(&self, pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        get_region_stats(pid)
+    }
+
+    fn page_states(&self, pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+        get_page_states(pid, start, end)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn last_errno() -> i32 {
+    std::io::Error::last_os_error().raw_os_error().unwrap_or(-1)
+}
+
+/// Uses `primary` until it is not permitted to access the process, then `fallback` for good
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub struct FallbackMemory {
+    primary: Box<dyn MemoryBackend>,
+    fallback: Box<dyn MemoryBackend>,
+    use_fallback: std::sync::atomic::AtomicBool,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl FallbackMemory {
+    pub fn new(primary: Box<dyn MemoryBackend>, fallback: Box<dyn MemoryBackend>) -> Self {
+        FallbackMemory {
+            primary,
+            fallback,
+            use_fallback: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn call<T>(
+        &self,
+        f: impl Fn(&dyn MemoryBackend) -> Result<T, MemoryError>,
+    ) -> Result<T, MemoryError> {
+        use std::sync::atomic::Ordering;
+
+        if !self.use_fallback.load(Ordering::Relaxed) {
+            match f(self.primary.as_ref()) {
+                Err(
+                    MemoryError::ProcessAttach(libc::EPERM) | MemoryError::MemWrite(libc::EPERM),
+                ) => self.use_fallback.store(true, Ordering::Relaxed),
+                result => return result,
+            }
+        }
+        f(self.fallback.as_ref())
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl MemoryBackend for FallbackMemory {
+    fn regions(
+        &self,
+        pid: u32,
+        start: Option<u64>,
+        end: Option<u64>,
+        search_perms: Option<&[MemoryRegionPerms]>,
+    ) -> Result<Vec<MemoryRegion>, MemoryError> {
+        self.call(|memory| memory.regions(pid, start, end, search_perms))
+    }
+
+    fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, MemoryError> {
+        self.call(|memory| memory.read(pid, addr, size))
+    }
+
+    fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.call(|memory| memory.write(pid, addr, value))
+    }
+
+    fn write_protected(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), MemoryError> {
+        self.call(|memory| memory.write_protected(pid, addr, value))
+    }
+
+    fn read_many(
+        &self,
+        pid: u32,
+        requests: &[(usize, usize)],
+    ) -> Result<Vec<Option<Vec<u8>>>, MemoryError> {
+        self.call(|memory| memory.read_many(pid, requests))
+    }
+
+    fn region_stats(&self, pid: u32) -> Result<Vec<(MemoryRegion, RegionStats)>, MemoryError> {
+        self.call(|memory| memory.region_stats(pid))
+    }
+
+    fn page_states(&self, pid: u32, start: u64, end: u64) -> Result<Vec<PageState>, MemoryError> {
+        self.call(|memory| memory.page_states(pid, start, end))
+    }
+}
+
+/// How the memory of local processes is accessed, the `backend` option of the config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryAccess {
+    /// The default of the platform
+    #[default]
+    Native,
+    /// `process_vm_writev` for writes too instead of `put_address` of `process-memory`
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ProcessVm,
+    /// `PTRACE_PEEKDATA` / `PTRACE_POKEDATA` only
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Ptrace,
+    /// `pread` / `pwrite` on `/proc/<pid>/mem`
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ProcMem,
+}
+
+impl MemoryAccess {
+    /// On Linux the other backends switch to ptrace once they get `EPERM`
+    pub fn backend(self) -> Box<dyn MemoryBackend> {
+        match self {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::Native => Box::new(FallbackMemory::new(
+                Box::new(NativeMemory),
+                Box::new(PtraceMemory::default()),
+            )),
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            Self::Native => Box::new(NativeMemory),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::ProcessVm => Box::new(FallbackMemory::new(
+                Box::new(ProcessVmMemory),
+                Box::new(PtraceMemory::default()),
+            )),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::Ptrace => Box::new(PtraceMemory::default()),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::ProcMem => Box::new(FallbackMemory::new(
+                Box::new(ProcMemMemory::default()),
+                Box::new(PtraceMemory::default()),
+            )),
+        }
+    }
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::core::scan::test::test_region;
+    #[allow(unused_imports)]
+    use std::process::{Command, Stdio};
+
+    #[test]
+    pub fn test_get_regions_error() {
+        let result = get_memory_regions(0, None, None, None);
+
+        assert!(result.is_err());
+
+        if let Err(e) = result {
+            match e {
+                MemoryError::NoPermission(_) => assert!(true),
+                _ => assert!(false),
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_parse_maps_line() {
+        let region = parse_maps_line(
+            "7f00aa000000-7f00aa021000 rw-p 00000000 00:00 0                  [heap]",
+        )
+        .unwrap();
+        assert_eq!(region.start, 0x7f00aa000000);
+        assert_eq!(region.end, 0x7f00aa021000);
+        assert_eq!(
+            region.perms,
+            vec![MemoryRegionPerms::Read, MemoryRegionPerms::Write]
+        );
+        assert_eq!(region.path.as_deref(), Some("[heap]"));
+        assert_eq!(region.file_offset(0x7f00aa000010), None);
+        assert!(!region.shared);
+
+        let region =
+            parse_maps_line("00400000-00452000 r-xp 00000000 fd:00 1234 /opt/my game/bin").unwrap();
+        assert_eq!(
+            region.perms,
+            vec![MemoryRegionPerms::Read, MemoryRegionPerms::Execute]
+        );
+        assert_eq!(region.path.as_deref(), Some("/opt/my game/bin"));
+        assert_eq!(region.inode, 1234);
+
+        let region = parse_maps_line(
+            "7f00ab000000-7f00ab001000 rw-p 0001c000 fd:00 5678 /usr/lib/libgame.so",
+        )
+        .unwrap();
+        assert_eq!(region.offset, 0x1c000);
+        assert_eq!(region.inode, 5678);
+        assert_eq!(region.file_offset(0x7f00ab000010), Some(0x1c010));
+
+        let region =
+            parse_maps_line("7f00ac000000-7f00ac400000 rw-s 00000000 00:01 42 /dev/fb0").unwrap();
+        assert!(region.shared);
+
+        let region = parse_maps_line("7f00aa021000-7f00aa022000 rw-p 00000000 00:00 0").unwrap();
+        assert_eq!(region.path, None);
+    }
+
+    #[test]
+    pub fn test_group_named_ranges() {
+        let region = |start, end, path| test_region(start, end, &[MemoryRegionPerms::Read], path);
+
+        let ranges = group_named_ranges(&[
+            region(0x1000, 0x2000, Some("/usr/bin/program")),
+            region(0x2000, 0x4000, Some("/usr/bin/program")),
+            region(0x5000, 0x6000, Some("[heap]")),
+            region(0x6000, 0x7000, None),
+            region(0x8000, 0x9000, Some("/usr/lib/libc.so.6")),
+            region(0xa000, 0xb000, Some("[vvar]")),
+            region(0xc000, 0xd000, Some("[stack]")),
+        ]);
+
+        let names: Vec<&str> = ranges.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["heap", "main stack", "program", "libc.so.6"]);
+        assert_eq!((ranges[2].start, ranges[2].end), (0x1000, 0x4000));
+    }
+
+    #[test]
+    pub fn test_group_android_ranges() {
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let region = |start, end, path| test_region(start, end, &perms, Some(path));
+
+        let ranges = group_named_ranges(&[
+            region(
+                0x1000,
+                0x2000,
+                "/apex/com.android.runtime/lib64/bionic/libc.so",
+            ),
+            region(0x3000, 0x4000, "[anon:scudo:primary]"),
+            region(0x5000, 0x6000, "[anon:libc_malloc]"),
+            region(0x7000, 0x8000, "[anon:dalvik-main space (region space)]"),
+            region(0x9000, 0xa000, "[anon:dalvik-LinearAlloc]"),
+            region(0xb000, 0xc000, "[anon:stack_and_tls:1234]"),
+            region(0xd000, 0xe000, "/data/local/tmp/game (deleted)"),
+        ]);
+
+        let names: Vec<&str> = ranges.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["native heap", "java heap", "libc.so", "game"]);
+        assert_eq!((ranges[0].start, ranges[0].end), (0x3000, 0x6000));
+    }
+
+    #[test]
+    pub fn test_region_is_shared_library() {
+        let region = |path| test_region(0, 0x1000, &[MemoryRegionPerms::Read], path);
+
+        assert!(region(Some("/usr/lib/x86_64-linux-gnu/libc.so.6")).is_shared_library());
+        assert!(region(Some("/usr/lib/ld-linux-x86-64.so.2")).is_shared_library());
+        assert!(region(Some("/usr/lib/libfoo.dylib")).is_shared_library());
+        assert!(
+            region(Some("/Device/HarddiskVolume3/Windows/System32/USER32.DLL")).is_shared_library()
+        );
+        assert!(!region(Some("/usr/bin/program")).is_shared_library());
+        assert!(!region(Some("/home/user/.so/program")).is_shared_library());
+        assert!(!region(Some("[heap]")).is_shared_library());
+        assert!(!region(None).is_shared_library());
+    }
+
+    #[test]
+    pub fn test_protection_perms() {
+        use MemoryRegionPerms::*;
+
+        assert_eq!(protection_perms(0x02), vec![Read]);
+        assert_eq!(protection_perms(0x04), vec![Read, Write]);
+        assert_eq!(protection_perms(0x20), vec![Read, Execute]);
+        assert_eq!(protection_perms(0x40), vec![Read, Write, Execute]);
+        assert_eq!(protection_perms(0x10), vec![Execute]);
+        // no access and guard pages
+        assert_eq!(protection_perms(0x01), vec![]);
+        assert_eq!(protection_perms(0x04 | 0x100), vec![]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_parse_smaps() {
+        let content = "\
+00400000-00452000 r-xp 00000000 fd:00 1234 /usr/bin/program
+Size:                328 kB
+Rss:                 200 kB
+Shared_Clean:        120 kB
+Shared_Dirty:          0 kB
+Private_Clean:        64 kB
+Private_Dirty:        16 kB
+Swap:                  8 kB
+VmFlags: rd ex mr mw me dw
+7ffd1000-7ffd2000 rw-p 00000000 00:00 0 [stack]
+Size:                  4 kB
+Rss:                   0 kB
+Swap:                  0 kB
+";
+        let regions = parse_smaps(content).unwrap();
+        assert_eq!(regions.len(), 2);
+
+        let (region, stats) = &regions[0];
+        assert_eq!(region.start, 0x00400000);
+        assert_eq!(region.path.as_deref(), Some("/usr/bin/program"));
+        assert_eq!(
+            *stats,
+            RegionStats {
+                rss: 200 * 1024,
+                swap: 8 * 1024,
+                shared: 120 * 1024,
+                private: 80 * 1024,
+            }
+        );
+
+        let (region, stats) = &regions[1];
+        assert_eq!(region.path.as_deref(), Some("[stack]"));
+        assert_eq!(*stats, RegionStats::default());
+
+        assert!(parse_smaps("Rss: 4 kB").is_err());
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    #[cfg(target_os = "linux")]
+    pub fn test_get_region_stats_success() {
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let proc = crate::core::utils::ChildGuard(proc);
+        let regions = get_region_stats(proc.0.id()).unwrap();
+        assert!(!regions.is_empty());
+        assert!(regions.iter().any(|(_, stats)| stats.rss > 0));
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_get_regions_success() {
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match proc {
+            Err(e) => assert!(false, "Error running simple program: {e}"),
+            Ok(child) => {
+                let proc = crate::core::utils::ChildGuard(child);
+                let regions = get_memory_regions(proc.0.id(), None, None, None);
+                assert!(regions.is_ok());
+                let regions = regions.unwrap();
+                assert_ne!(regions.len(), 0);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_get_regions_readonly_success() {
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match proc {
+            Err(e) => assert!(false, "Error running simple program: {e}"),
+            Ok(child) => {
+                let proc = crate::core::utils::ChildGuard(child);
+                let regions =
+                    get_memory_regions(proc.0.id(), None, None, Some(&[MemoryRegionPerms::Read]));
+                assert!(regions.is_ok());
+                let regions = regions.unwrap();
+                assert_ne!(regions.len(), 0);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_get_regions_with_range_success() {
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match proc {
+            Err(e) => assert!(false, "Error running simple program: {e}"),
+            Ok(child) => {
+                let proc = crate::core::utils::ChildGuard(child);
+                let regions = get_memory_regions(proc.0.id(), Some(u64::MAX), None, None);
+                assert!(regions.is_ok());
+                let regions = regions.unwrap();
+                assert_eq!(regions.len(), 0);
+
+                let regions = get_memory_regions(proc.0.id(), None, Some(0), None);
+                assert!(regions.is_ok());
+                let regions = regions.unwrap();
+                assert_eq!(regions.len(), 0);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    #[cfg(target_os = "linux")]
+    pub fn test_get_page_states_success() {
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let proc = crate::core::utils::ChildGuard(proc);
+        let regions = get_memory_regions(proc.0.id(), None, None, None).unwrap();
+        let region = &regions[0];
+        let states = get_page_states(proc.0.id(), region.start, region.end).unwrap();
+        assert_eq!(
+            states.len() as u64,
+            (region.end - region.start) / get_page_size()
+        );
+    }
+
+    #[test]
+    pub fn test_ptrace_scope_hint() {
+        assert!(ptrace_scope_hint(0, false).is_none());
+        assert!(ptrace_scope_hint(1, false).is_some());
+        assert!(ptrace_scope_hint(1, true).is_none());
+        assert!(ptrace_scope_hint(2, false).is_some());
+        assert!(ptrace_scope_hint(2, true).is_none());
+        assert!(ptrace_scope_hint(3, true).is_some());
+    }
+
+    #[test]
+    pub fn test_macos_setup_steps() {
+        let setup = MacosSetup {
+            is_root: true,
+            debugger_entitlement: false,
+            sip_enabled: Some(false),
+        };
+        assert!(setup.can_attach());
+        assert!(setup.steps().is_empty());
+
+        let setup = MacosSetup {
+            is_root: false,
+            sip_enabled: Some(true),
+            ..setup
+        };
+        assert!(!setup.can_attach());
+        let steps = setup.steps();
+        assert_eq!(steps.len(), 3);
+        assert!(steps[1].ends_with(MACOS_SIGN_CMD));
+        assert!(steps[2].starts_with("SIP is enabled"));
+
+        let setup = MacosSetup {
+            debugger_entitlement: true,
+            sip_enabled: None,
+            ..setup
+        };
+        assert!(setup.can_attach());
+        assert!(setup.steps().is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    pub fn test_parse_csrutil_status() {
+        assert_eq!(
+            parse_csrutil_status("System Integrity Protection status: enabled.\n"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_csrutil_status("System Integrity Protection status: disabled."),
+            Some(false)
+        );
+        assert_eq!(
+            parse_csrutil_status(
+                "System Integrity Protection status: unknown (Custom Configuration)."
+            ),
+            None
+        );
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_read_memory_address_success() {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+
+        reader.read_line(&mut line).unwrap();
+
+        let hex_str = line.trim();
+        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
 
         let value = read_memory_address(proc.0.id(), address, 4).unwrap();
         let value = u32::from_le_bytes(value.try_into().unwrap());
         assert_eq!(value, 31337_u32);
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_process_vm_memory() {
+        use super::*;
+
+        let page_size = get_page_size() as usize;
+        let mut map = memmap2::MmapMut::map_anon(page_size * 2).unwrap();
+        map[page_size - 4..page_size].copy_from_slice(&[1, 2, 3, 4]);
+        let start = map.as_ptr() as usize;
+        unsafe {
+            libc::mprotect(
+                map[page_size..].as_mut_ptr() as *mut libc::c_void,
+                page_size,
+                libc::PROT_NONE,
+            )
+        };
+
+        let pid = std::process::id();
+        let memory = ProcessVmMemory;
+        let end = start + page_size;
+        assert_eq!(memory.read(pid, end - 4, 4), Ok(vec![1, 2, 3, 4]));
+        memory.write(pid, end - 2, &[5, 6]).unwrap();
+        assert_eq!(memory.read(pid, end - 4, 4), Ok(vec![1, 2, 5, 6]));
+
+        // the transfer stops at the protected page
+        assert_eq!(
+            memory.read(pid, end - 4, 8),
+            Err(MemoryError::MemRead(libc::EFAULT))
+        );
+        assert_eq!(
+            memory.write(pid, end - 4, &[0; 8]),
+            Err(MemoryError::MemWrite(libc::EFAULT))
+        );
+        assert_eq!(
+            memory.read(pid, end, 4),
+            Err(MemoryError::MemRead(libc::EFAULT))
+        );
+        assert_eq!(
+            memory.read(0, start, 4),
+            Err(MemoryError::ProcessAttach(libc::ESRCH))
+        );
+        assert_eq!(
+            memory.read(u32::MAX, start, 4),
+            Err(MemoryError::ProcessAttach(libc::ESRCH))
+        );
+
+        assert_eq!(MemoryAccess::default(), MemoryAccess::Native);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_proc_mem_memory() {
+        use super::*;
+
+        let page_size = get_page_size() as usize;
+        let mut map = memmap2::MmapMut::map_anon(page_size * 2).unwrap();
+        map[page_size - 4..page_size].copy_from_slice(&[1, 2, 3, 4]);
+        let start = map.as_ptr() as usize;
+        unsafe {
+            libc::munmap(
+                map[page_size..].as_mut_ptr() as *mut libc::c_void,
+                page_size,
+            )
+        };
+
+        let pid = std::process::id();
+        let memory = ProcMemMemory::default();
+        let end = start + page_size;
+        assert_eq!(memory.read(pid, end - 4, 4), Ok(vec![1, 2, 3, 4]));
+        memory.write(pid, end - 2, &[5, 6]).unwrap();
+        assert_eq!(map[page_size - 4..page_size], [1, 2, 5, 6]);
+        // a whole page at once
+        assert_eq!(memory.read(pid, start, page_size).unwrap().len(), page_size);
+
+        assert!(memory.read(pid, end - 4, 8).is_err());
+        assert!(memory.read(pid, end, 4).is_err());
+        assert!(matches!(
+            memory.read(u32::MAX, start, 4),
+            Err(MemoryError::ProcessAttach(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_write_protected() {
+        use super::*;
+
+        let page_size = get_page_size() as usize;
+        let mut map = memmap2::MmapMut::map_anon(page_size).unwrap();
+        map[..4].copy_from_slice(&[1, 2, 3, 4]);
+        let map = map.make_read_only().unwrap();
+        let start = map.as_ptr() as usize;
+
+        let pid = std::process::id();
+        let memory = NativeMemory;
+        assert!(memory.write(pid, start, &[5, 6]).is_err());
+        memory.write_protected(pid, start, &[5, 6]).unwrap();
+        assert_eq!(map[..4], [5, 6, 3, 4]);
+
+        // the page is still read-only
+        let regions = get_memory_regions(
+            pid,
+            Some(start as u64),
+            Some(start as u64),
+            Some(&[MemoryRegionPerms::Read]),
+        )
+        .unwrap();
+        let region = regions.iter().find(|r| r.start == start as u64).unwrap();
+        assert_eq!(region.perms, vec![MemoryRegionPerms::Read]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_fallback_memory() {
+        use super::*;
+
+        /// Fails every call with the error it was created with
+        struct Failing(MemoryError);
+
+        impl MemoryBackend for Failing {
+            fn regions(
+                &self,
+                _pid: u32,
+                _start: Option<u64>,
+                _end: Option<u64>,
+                _search_perms: Option<&[MemoryRegionPerms]>,
+            ) -> Result<Vec<MemoryRegion>, MemoryError> {
+                Err(self.0.clone())
+            }
+
+            fn read(&self, _pid: u32, _addr: usize, _size: usize) -> Result<Vec<u8>, MemoryError> {
+                Err(self.0.clone())
+            }
+
+            fn write(&self, _pid: u32, _addr: usize, _value: &[u8]) -> Result<(), MemoryError> {
+                Err(self.0.clone())
+            }
+        }
+
+        let memory = FallbackMemory::new(
+            Box::new(Failing(MemoryError::MemRead(libc::EFAULT))),
+            Box::new(Failing(MemoryError::MemRead(libc::EIO))),
+        );
+        assert_eq!(
+            memory.read(1, 0, 4),
+            Err(MemoryError::MemRead(libc::EFAULT))
+        );
+
+        let memory = FallbackMemory::new(
+            Box::new(Failing(MemoryError::ProcessAttach(libc::EPERM))),
+            Box::new(Failing(MemoryError::MemRead(libc::EIO))),
+        );
+        assert_eq!(memory.read(1, 0, 4), Err(MemoryError::MemRead(libc::EIO)));
+        assert_eq!(
+            memory.write(1, 0, &[0]),
+            Err(MemoryError::MemRead(libc::EIO))
+        );
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    #[cfg(target_os = "linux")]
+    pub fn test_ptrace_memory() {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let address = usize::from_str_radix(line.trim().trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let pid = proc.0.id();
+        let memory = PtraceMemory::default();
+        assert_eq!(
+            memory.read(pid, address, 4),
+            Ok(31337_u32.to_le_bytes().to_vec())
+        );
+        // unaligned and spanning words, the bytes around the value are left alone
+        let around = memory.read(pid, address - 3, 10).unwrap();
+        memory.write(pid, address + 1, &[0xaa, 0xbb]).unwrap();
+        let mut expected = around.clone();
+        expected[4..6].copy_from_slice(&[0xaa, 0xbb]);
+        assert_eq!(memory.read(pid, address - 3, 10), Ok(expected));
+        assert!(memory.read(pid, 0, 4).is_err());
+    }
+
+    #[test]
+    pub fn test_group_requests() {
+        use super::*;
+
+        let requests = [
+            (0x2000, 4),
+            (0x1000, 4),
+            (0x1008, 8),
+            (0x1100, 4),
+            (0x1ffe, 4),
+        ];
+        let groups = group_requests(&requests);
+        let ranges: Vec<(usize, usize, Vec<usize>)> = groups
+            .into_iter()
+            .map(|g| (g.start, g.end, g.requests))
+            .collect();
+        // close requests share a range, far ones and ones past the span start a new one
+        assert_eq!(
+            ranges,
+            vec![
+                (0x1000, 0x1010, vec![1, 2]),
+                (0x1100, 0x1104, vec![3]),
+                (0x1ffe, 0x2004, vec![4, 0]),
+            ]
+        );
+        assert!(group_requests(&[]).is_empty());
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_read_memory_addresses_success() {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let address = usize::from_str_radix(line.trim().trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        // the unreadable request does not take the others with it
+        let requests = [(address, 4), (0, 4), (address, 2), (address + 2, 2)];
+        let values = read_memory_addresses(proc.0.id(), &requests).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                Some(31337_u32.to_le_bytes().to_vec()),
+                None,
+                Some(vec![0x69, 0x7a]),
+                Some(vec![0, 0]),
+            ]
+        );
+    }
+
     #[test]
     #[ignore = "requires root"]
     pub fn test_write_memory_address_success() {
@@ -1,4 +1,9 @@
+pub mod audit;
+pub mod config;
+pub mod dump;
 pub mod mem;
 pub mod proc;
+pub mod rpc;
 pub mod scan;
+pub mod script;
 pub mod utils;
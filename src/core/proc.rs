@@ -38,6 +38,262 @@ pub fn get_list(filter: Option<&str>) -> Vec<ProcInfo> {
     proc_list
 }
 
+/// Size of a pointer in the target in bytes, read from the ELF class of its executable or
+/// else guessed from where its memory is mapped, `None` when it can not be told
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_pointer_width(pid: u32) -> Option<usize> {
+    use std::io::Read;
+
+    let mut header = [0; 5];
+    let from_exe = std::fs::File::open(format!("/proc/{pid}/exe"))
+        .and_then(|mut exe| exe.read_exact(&mut header))
+        .ok()
+        .and_then(|_| elf_pointer_width(&header));
+    // the exe link needs more rights than the maps, which are readable for own processes
+    from_exe.or_else(|| {
+        let maps = std::fs::read_to_string(format!("/proc/{pid}/maps")).ok()?;
+        maps_pointer_width(&maps)
+    })
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_pointer_width(_pid: u32) -> Option<usize> {
+    // only 64-bit processes run on current macOS
+    Some(8)
+}
+
+/// 32-bit processes on 64-bit Windows run under WOW64, everything else has the pointer
+/// width of the system. A 32-bit build can not read 64-bit processes anyway
+#[cfg(windows)]
+pub fn get_pointer_width(pid: u32) -> Option<usize> {
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> isize;
+        fn CloseHandle(handle: isize) -> i32;
+        fn IsWow64Process(process: isize, wow64: *mut i32) -> i32;
+    }
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle == 0 {
+        return None;
+    }
+    let mut wow64 = 0;
+    let queried = unsafe { IsWow64Process(handle, &mut wow64) };
+    unsafe { CloseHandle(handle) };
+    if queried == 0 {
+        return None;
+    }
+    Some(if wow64 != 0 {
+        4
+    } else {
+        std::mem::size_of::<usize>()
+    })
+}
+
+/// Tells a process apart from a later one that got the same pid
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessIdentity {
+    /// Start time in the unit of the platform, only compared for equality
+    pub start_time: u64,
+    pub exe_inode: Option<u64>,
+}
+
+/// `None` when the process does not exist (anymore)
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_process_identity(pid: u32) -> Option<ProcessIdentity> {
+    use std::os::unix::fs::MetadataExt;
+
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // the link keeps pointing at the original inode when the executable is replaced on disk
+    let exe_inode = std::fs::metadata(format!("/proc/{pid}/exe"))
+        .ok()
+        .map(|metadata| metadata.ino());
+
+    Some(ProcessIdentity {
+        start_time: parse_start_time(&stat)?,
+        exe_inode,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn get_process_identity(pid: u32) -> Option<ProcessIdentity> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate};
+
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_exe(sysinfo::UpdateKind::OnlyIfNotSet),
+    );
+    let process = sys.process(pid)?;
+
+    Some(ProcessIdentity {
+        start_time: process.start_time(),
+        exe_inode: process.exe().and_then(file_inode),
+    })
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn file_inode(path: &std::path::Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path).ok().map(|metadata| metadata.ino())
+}
+
+#[cfg(windows)]
+fn file_inode(_path: &std::path::Path) -> Option<u64> {
+    // the file index needs an open handle, the start time tells processes apart well enough
+    None
+}
+
+/// Keeps a process stopped until it is dropped, see `pause_process`
+#[derive(Debug)]
+pub struct PausedProcess {
+    pid: u32,
+    /// False when the process was already stopped before, it is left that way
+    resume: bool,
+}
+
+impl Drop for PausedProcess {
+    fn drop(&mut self) {
+        if self.resume {
+            let _ = set_suspended(self.pid, false);
+        }
+    }
+}
+
+/// Stops the process (SIGSTOP, `NtSuspendProcess` on Windows) so its memory holds still,
+/// it continues once the returned guard is dropped. Fails with the OS error code
+pub fn pause_process(pid: u32) -> Result<PausedProcess, i32> {
+    if is_stopped(pid) {
+        return Ok(PausedProcess { pid, resume: false });
+    }
+    set_suspended(pid, true)?;
+
+    // signals are delivered asynchronously, wait a little for the process to stop
+    for _ in 0..if cfg!(unix) { 100 } else { 0 } {
+        if is_stopped(pid) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    Ok(PausedProcess { pid, resume: true })
+}
+
+#[cfg(unix)]
+fn set_suspended(pid: u32, suspended: bool) -> Result<(), i32> {
+    // 0 and negative pids signal whole process groups
+    if pid == 0 || pid > libc::pid_t::MAX as u32 {
+        return Err(libc::ESRCH);
+    }
+    let signal = if suspended {
+        libc::SIGSTOP
+    } else {
+        libc::SIGCONT
+    };
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } != 0 {
+        return Err(std::io::Error::last_os_error().raw_os_error().unwrap_or(-1));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn set_suspended(pid: u32, suspended: bool) -> Result<(), i32> {
+    const PROCESS_SUSPEND_RESUME: u32 = 0x0800;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> isize;
+        fn CloseHandle(handle: isize) -> i32;
+    }
+    // undocumented but stable, suspends every thread of the process at once
+    #[link(name = "ntdll")]
+    unsafe extern "system" {
+        fn NtSuspendProcess(process: isize) -> i32;
+        fn NtResumeProcess(process: isize) -> i32;
+    }
+
+    let handle = unsafe { OpenProcess(PROCESS_SUSPEND_RESUME, 0, pid) };
+    if handle == 0 {
+        return Err(std::io::Error::last_os_error().raw_os_error().unwrap_or(-1));
+    }
+    let status = unsafe {
+        if suspended {
+            NtSuspendProcess(handle)
+        } else {
+            NtResumeProcess(handle)
+        }
+    };
+    unsafe { CloseHandle(handle) };
+
+    if status != 0 { Err(status) } else { Ok(()) }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn is_stopped(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|stat| parse_state(&stat))
+        .is_some_and(|state| state == 'T' || state == 't')
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn is_stopped(_pid: u32) -> bool {
+    // the state is not exposed without procfs, the process is assumed to be running
+    false
+}
+
+/// State of the process, the 3rd field of `/proc/<pid>/stat`
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn parse_state(stat: &str) -> Option<char> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().next()?.chars().next()
+}
+
+/// Start time in clock ticks since boot, the 22nd field of `/proc/<pid>/stat`
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn parse_start_time(stat: &str) -> Option<u64> {
+    // the command name can contain spaces and parentheses, fields are counted after it
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn elf_pointer_width(header: &[u8]) -> Option<usize> {
+    const ELFCLASS32: u8 = 1;
+    const ELFCLASS64: u8 = 2;
+
+    if !header.starts_with(b"\x7fELF") {
+        return None;
+    }
+    match *header.get(4)? {
+        ELFCLASS32 => Some(4),
+        ELFCLASS64 => Some(8),
+        _ => None,
+    }
+}
+
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(dead_code))]
+fn maps_pointer_width(maps: &str) -> Option<usize> {
+    let highest_end = maps
+        .lines()
+        .filter_map(|line| {
+            let (_, end) = line.split_whitespace().next()?.split_once('-')?;
+            u64::from_str_radix(end, 16).ok()
+        })
+        .max()?;
+    Some(layout_pointer_width(highest_end))
+}
+
+/// 64-bit processes always have their stack and vdso above 4 GiB, so a layout that
+/// ends below it is taken for a 32-bit one
+pub fn layout_pointer_width(highest_end: u64) -> usize {
+    if highest_end <= 1 << 32 { 4 } else { 8 }
+}
+
 mod test {
     #[allow(unused_imports)]
     use super::*;
@@ -52,4 +308,85 @@ mod test {
             assert!(proc.name.to_lowercase().starts_with("car"));
         }
     }
+
+    #[test]
+    fn test_elf_pointer_width() {
+        assert_eq!(elf_pointer_width(b"\x7fELF\x01"), Some(4));
+        assert_eq!(elf_pointer_width(b"\x7fELF\x02\x01\x01"), Some(8));
+        assert_eq!(elf_pointer_width(b"\x7fELF\x00"), None);
+        assert_eq!(elf_pointer_width(b"#!/bin/sh"), None);
+        assert_eq!(elf_pointer_width(b"\x7fELF"), None);
+    }
+
+    #[test]
+    fn test_maps_pointer_width() {
+        let maps_32 = "08048000-08049000 r-xp 00000000 08:01 1234 /bin/app\n\
+                       ff9c1000-ff9e2000 rw-p 00000000 00:00 0 [stack]\n\
+                       fffdd000-fffde000 r-xp 00000000 00:00 0 [vdso]";
+        let maps_64 = "55d0c0a00000-55d0c0a01000 r-xp 00000000 08:01 1234 /bin/app\n\
+                       7ffc2a1f3000-7ffc2a214000 rw-p 00000000 00:00 0 [stack]";
+        assert_eq!(maps_pointer_width(maps_32), Some(4));
+        assert_eq!(maps_pointer_width(maps_64), Some(8));
+        assert_eq!(maps_pointer_width(""), None);
+        assert_eq!(maps_pointer_width("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_start_time() {
+        let stat = "1234 (my (game) 2) S 1 1234 1234 0 -1 4194560 1500 0 0 0 30 12 0 0 20 0 4 0 987654 123456789 2000";
+        assert_eq!(parse_start_time(stat), Some(987654));
+        assert_eq!(parse_start_time("1234 (game) S 1"), None);
+        assert_eq!(parse_start_time("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(parse_state("1234 (my (game) 2) T 1 1234"), Some('T'));
+        assert_eq!(parse_state("1234 (game) S 1"), Some('S'));
+        assert_eq!(parse_state("garbage"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pause_process() {
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("sleep")
+            .arg("10")
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+        let proc = crate::core::utils::ChildGuard(proc);
+        let pid = proc.0.id();
+
+        let paused = pause_process(pid).unwrap();
+        assert!(is_stopped(pid));
+        // pausing a stopped process does not resume it early
+        drop(pause_process(pid).unwrap());
+        assert!(is_stopped(pid));
+        drop(paused);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!is_stopped(pid));
+
+        assert_eq!(pause_process(u32::MAX >> 2).err(), Some(libc::ESRCH));
+        assert_eq!(pause_process(0).err(), Some(libc::ESRCH));
+        assert_eq!(pause_process(u32::MAX).err(), Some(libc::ESRCH));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_get_process_identity() {
+        let identity = get_process_identity(std::process::id()).unwrap();
+        assert_eq!(get_process_identity(std::process::id()), Some(identity));
+        assert_eq!(get_process_identity(u32::MAX), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_get_pointer_width() {
+        assert_eq!(
+            get_pointer_width(std::process::id()),
+            Some(size_of::<usize>())
+        );
+    }
 }
@@ -0,0 +1,495 @@
+use serde_json::{Value, json};
+use std::fmt::Display;
+
+use crate::core::scan::{DisplayFormat, Scan, ScanResult, ValueType};
+
+/// Methods `dispatch` knows
+const METHODS: [&str; 9] = [
+    "scan.first",
+    "scan.next",
+    "scan.refresh",
+    "scan.results",
+    "memory.read",
+    "memory.write",
+    "watchlist.list",
+    "watchlist.add",
+    "watchlist.remove",
+];
+
+/// Results returned by `scan.results` when no count is given
+const DEFAULT_RESULT_COUNT: u64 = 100;
+
+/// Largest `memory.read`, the bytes are read in one go and sent back as hex
+const MAX_READ_SIZE: u64 = 4 << 20;
+
+/// Error object of a JSON-RPC 2.0 response
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    const PARSE_ERROR: i64 = -32700;
+    const INVALID_REQUEST: i64 = -32600;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    /// Failures of the scan itself, the message is the scan error
+    const SCAN_ERROR: i64 = -32000;
+
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn scan(message: impl Display) -> Self {
+        Self::new(Self::SCAN_ERROR, message.to_string())
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+}
+
+impl Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcRequest {
+    /// `null` for notifications, they get no response
+    pub id: Value,
+    pub method: String,
+    pub params: Value,
+}
+
+/// Parses one line of the protocol, errors come with the id of the request when it had one
+pub fn parse_request(line: &str) -> Result<RpcRequest, (Value, RpcError)> {
+    let request: Value = serde_json::from_str(line).map_err(|e| {
+        (
+            Value::Null,
+            RpcError::new(RpcError::PARSE_ERROR, e.to_string()),
+        )
+    })?;
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let invalid = |message: &str| {
+        (
+            id.clone(),
+            RpcError::new(RpcError::INVALID_REQUEST, message),
+        )
+    };
+
+    if request.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(invalid("`jsonrpc` has to be \"2.0\""));
+    }
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("`method` is missing"))?;
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    if !params.is_null() && !params.is_object() {
+        return Err(invalid("`params` has to be an object"));
+    }
+
+    Ok(RpcRequest {
+        id,
+        method: method.to_owned(),
+        params,
+    })
+}
+
+/// One line response to the request with `id`
+pub fn format_response(id: Value, result: Result<Value, RpcError>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+    .to_string()
+}
+
+fn param_str<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    optional_str(params, name)?
+        .ok_or_else(|| RpcError::invalid_params(format!("`{name}` is missing")))
+}
+
+fn optional_str<'a>(params: &'a Value, name: &str) -> Result<Option<&'a str>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "`{name}` has to be a string"
+        ))),
+    }
+}
+
+fn optional_u64(params: &Value, name: &str) -> Result<Option<u64>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| RpcError::invalid_params(format!("`{name}` has to be a number"))),
+    }
+}
+
+/// Addresses are numbers or strings, strings can be hex or `module+offset` expressions
+fn param_address(scan: &Scan, params: &Value) -> Result<u64, RpcError> {
+    match params.get("address") {
+        Some(Value::Number(address)) => address
+            .as_u64()
+            .ok_or_else(|| RpcError::invalid_params("`address` has to be positive")),
+        Some(Value::String(address)) => scan.resolve_address(address).map_err(RpcError::scan),
+        _ => Err(RpcError::invalid_params("`address` is missing")),
+    }
+}
+
+fn format_result(result: &ScanResult) -> Value {
+    json!({
+        "address": format!("0x{:x}", result.address),
+        "type": result.value_type.get_name(),
+        "value": result.get_display_string(&DisplayFormat::default()).ok(),
+        "module": result.get_module_name(),
+    })
+}
+
+/// What is left to do for a request `dispatch` accepted
+#[derive(Debug, Clone, PartialEq)]
+pub enum RpcOutcome {
+    /// The `result` member of the response
+    Done(Value),
+    /// The scan is set up for a first scan with the value, the caller runs it in the
+    /// background and responds with `scan_response` once it is done
+    FirstScan(String),
+    /// Like `FirstScan` for a next scan
+    NextScan(String),
+    /// The value was written to the address, the caller records the edit and responds
+    /// with `null`
+    Written(u64, String),
+}
+
+/// Response to a scan `dispatch` left to the caller
+pub fn scan_response(result: Result<usize, impl Display>) -> Result<Value, RpcError> {
+    result
+        .map(|count| json!({ "count": count }))
+        .map_err(RpcError::scan)
+}
+
+/// Runs a method on the attached scan. Scans and writes are left to the caller, so they
+/// run in the background and are recorded like the ones made in the TUI
+pub fn dispatch(
+    scan: Option<&mut Scan>,
+    method: &str,
+    params: &Value,
+) -> Result<RpcOutcome, RpcError> {
+    if !METHODS.contains(&method) {
+        return Err(RpcError::new(
+            RpcError::METHOD_NOT_FOUND,
+            format!("Unknown method: `{method}`"),
+        ));
+    }
+    let scan = scan.ok_or_else(|| RpcError::scan("No process is attached"))?;
+
+    match method {
+        "scan.first" => {
+            let value = param_str(params, "value")?;
+            match optional_str(params, "type")? {
+                Some(name) => {
+                    let value_type = ValueType::from_name(name).ok_or_else(|| {
+                        RpcError::invalid_params(format!("Unknown type: `{name}`"))
+                    })?;
+                    let pointer_width = scan.pointer_width();
+                    scan.set_value_type(value_type.for_pointer_width(pointer_width), Some(value))
+                }
+                None => scan.set_value_from_str(value),
+            }
+            .map_err(RpcError::scan)?;
+            Ok(RpcOutcome::FirstScan(value.to_owned()))
+        }
+        "scan.next" => {
+            let value = optional_str(params, "value")?;
+            if let Some(value) = value {
+                scan.set_value_from_str(value).map_err(RpcError::scan)?;
+            }
+            Ok(RpcOutcome::NextScan(value.unwrap_or_default().to_owned()))
+        }
+        "scan.refresh" => {
+            scan.refresh().map_err(RpcError::scan)?;
+            Ok(RpcOutcome::Done(json!({ "count": scan.match_count() })))
+        }
+        "scan.results" => {
+            let offset = optional_u64(params, "offset")?.unwrap_or(0) as usize;
+            let count = optional_u64(params, "count")?.unwrap_or(DEFAULT_RESULT_COUNT) as usize;
+            let results: Vec<Value> = scan
+                .results
+                .iter()
+                .skip(offset)
+                .take(count)
+                .map(format_result)
+                .collect();
+            Ok(RpcOutcome::Done(
+                json!({ "total": scan.results.len(), "results": results }),
+            ))
+        }
+        "memory.read" => {
+            let address = param_address(scan, params)?;
+            let size = optional_u64(params, "size")?
+                .ok_or_else(|| RpcError::invalid_params("`size` is missing"))?;
+            if size > MAX_READ_SIZE {
+                return Err(RpcError::invalid_params(format!(
+                    "`size` can be at most {MAX_READ_SIZE}"
+                )));
+            }
+            let bytes = scan
+                .read_memory(address, size as usize)
+                .map_err(RpcError::scan)?;
+            Ok(RpcOutcome::Done(json!({ "bytes": hex::encode(bytes) })))
+        }
+        "memory.write" => {
+            let address = param_address(scan, params)?;
+            let value = param_str(params, "value")?;
+            scan.update_value(address, value).map_err(RpcError::scan)?;
+            Ok(RpcOutcome::Written(address, value.to_owned()))
+        }
+        "watchlist.list" => Ok(RpcOutcome::Done(Value::Array(
+            scan.watchlist.iter().map(format_result).collect(),
+        ))),
+        "watchlist.add" => {
+            let address = param_address(scan, params)?;
+            let result = scan
+                .results
+                .iter()
+                .find(|r| r.address == address)
+                .cloned()
+                .ok_or_else(|| RpcError::scan(format!("0x{address:x} is not a result")))?;
+            scan.add_to_watchlist(result);
+            Ok(RpcOutcome::Done(Value::Null))
+        }
+        "watchlist.remove" => {
+            let address = param_address(scan, params)?;
+            scan.remove_from_watchlist(address);
+            Ok(RpcOutcome::Done(Value::Null))
+        }
+        _ => Err(RpcError::new(
+            RpcError::METHOD_NOT_FOUND,
+            format!("Unknown method: `{method}`"),
+        )),
+    }
+}
+
+/// A request waiting for the TUI, which owns the scan
+pub struct RpcCall {
+    pub request: RpcRequest,
+    reply: std::sync::mpsc::Sender<String>,
+}
+
+impl RpcCall {
+    pub fn respond(self, result: Result<Value, RpcError>) {
+        // the connection may be gone already, nobody is left to tell
+        let _ = self.reply.send(format_response(self.request.id, result));
+    }
+}
+
+/// Listens on a Unix socket at `path` that only the current user can connect to.
+/// Every line is a JSON-RPC request, calls are handed to the returned receiver and
+/// their responses written back in order
+#[cfg(unix)]
+pub fn serve(path: &std::path::Path) -> std::io::Result<std::sync::mpsc::Receiver<RpcCall>> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+
+    // a socket left behind by an earlier run, other files are not touched
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let sender = sender.clone();
+            std::thread::spawn(move || serve_connection(stream, sender));
+        }
+    });
+    Ok(receiver)
+}
+
+#[cfg(unix)]
+fn serve_connection(
+    stream: std::os::unix::net::UnixStream,
+    calls: std::sync::mpsc::Sender<RpcCall>,
+) {
+    use std::io::{BufRead, BufReader, Write};
+
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            return;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                let notification = request.id.is_null();
+                let (reply, response) = std::sync::mpsc::channel();
+                if calls.send(RpcCall { request, reply }).is_err() {
+                    return;
+                }
+                let Ok(response) = response.recv() else {
+                    return;
+                };
+                if notification {
+                    continue;
+                }
+                response
+            }
+            Err((id, e)) => format_response(id, Err(e)),
+        };
+        if writeln!(writer, "{response}").is_err() {
+            return;
+        }
+    }
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    pub fn test_parse_request() {
+        let request = parse_request(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "scan.next", "params": {"value": "5"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, json!(1));
+        assert_eq!(request.method, "scan.next");
+        assert_eq!(request.params, json!({ "value": "5" }));
+
+        let request = parse_request(r#"{"jsonrpc": "2.0", "method": "scan.refresh"}"#).unwrap();
+        assert_eq!(request.id, Value::Null);
+        assert_eq!(request.params, Value::Null);
+
+        let code = |line: &str| parse_request(line).unwrap_err().1.code;
+        assert_eq!(code("{"), RpcError::PARSE_ERROR);
+        assert_eq!(
+            code(r#"{"id": 1, "method": "scan.refresh"}"#),
+            RpcError::INVALID_REQUEST
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": 1}"#),
+            RpcError::INVALID_REQUEST
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": 1, "method": "scan.next", "params": [5]}"#),
+            RpcError::INVALID_REQUEST
+        );
+        assert_eq!(
+            parse_request(r#"{"jsonrpc": "1.0", "id": 7}"#)
+                .unwrap_err()
+                .0,
+            json!(7)
+        );
+    }
+
+    #[test]
+    pub fn test_format_response() {
+        assert_eq!(
+            format_response(json!(1), Ok(json!({ "count": 2 }))),
+            r#"{"id":1,"jsonrpc":"2.0","result":{"count":2}}"#
+        );
+        assert_eq!(
+            format_response(json!("a"), Err(RpcError::scan("Invalid value"))),
+            r#"{"error":{"code":-32000,"message":"Invalid value"},"id":"a","jsonrpc":"2.0"}"#
+        );
+    }
+
+    #[test]
+    pub fn test_dispatch_errors() {
+        let error = dispatch(None, "scan.refresh", &Value::Null).unwrap_err();
+        assert_eq!(error.code, RpcError::SCAN_ERROR);
+        let error = dispatch(None, "scan.undo", &Value::Null).unwrap_err();
+        assert_eq!(error.code, RpcError::METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    pub fn test_dispatch() {
+        let value = 0x1234_5678_u32.to_le_bytes();
+        let address = value.as_ptr() as u64;
+        let mut scan =
+            Scan::new(std::process::id(), vec![], ValueType::U32, None, None, None).unwrap();
+        let mut call = |method: &str, params: Value| dispatch(Some(&mut scan), method, &params);
+
+        assert_eq!(
+            call("memory.read", json!({ "address": address, "size": 4 })),
+            Ok(RpcOutcome::Done(json!({ "bytes": "78563412" })))
+        );
+        assert_eq!(
+            call(
+                "memory.read",
+                json!({ "address": format!("0x{address:x}"), "size": 2 })
+            ),
+            Ok(RpcOutcome::Done(json!({ "bytes": "7856" })))
+        );
+        assert_eq!(
+            call("scan.results", Value::Null),
+            Ok(RpcOutcome::Done(json!({ "total": 0, "results": [] })))
+        );
+        assert_eq!(
+            call("watchlist.list", Value::Null),
+            Ok(RpcOutcome::Done(json!([])))
+        );
+        assert_eq!(
+            call("scan.first", json!({ "type": "u16", "value": "7" })),
+            Ok(RpcOutcome::FirstScan("7".to_owned()))
+        );
+        assert_eq!(
+            call("scan.next", Value::Null),
+            Ok(RpcOutcome::NextScan(String::new()))
+        );
+        assert_eq!(scan_response(Ok::<_, String>(3)), Ok(json!({ "count": 3 })));
+
+        let code = |result: Result<RpcOutcome, RpcError>| result.unwrap_err().code;
+        assert_eq!(
+            code(call("memory.read", json!({ "size": 4 }))),
+            RpcError::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(call(
+                "memory.read",
+                json!({ "address": address, "size": "4" })
+            )),
+            RpcError::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(call("scan.first", json!({ "type": "u24", "value": "1" }))),
+            RpcError::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(call(
+                "memory.read",
+                json!({ "address": address, "size": u64::MAX })
+            )),
+            RpcError::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(call("watchlist.add", json!({ "address": address }))),
+            RpcError::SCAN_ERROR
+        );
+        assert_eq!(
+            code(call("scan.undo", Value::Null)),
+            RpcError::METHOD_NOT_FOUND
+        );
+    }
+}
@@ -1,40 +1,464 @@
+use encoding_rs::SHIFT_JIS;
 use memchr::memmem;
+use memmap2::Mmap;
 use rayon::prelude::*;
-use std::{array::TryFromSliceError, str};
+use serde::Deserialize;
+use std::{
+    array::TryFromSliceError,
+    cmp::Ordering,
+    collections::HashSet,
+    fs::{self, File},
+    io::{self, Seek, Write},
+    str,
+    sync::{
+        Mutex,
+        atomic::{self, AtomicU64, AtomicUsize},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use crate::core::mem::{
-    DEFAULT_SEARCH_PERMS, MemoryError, MemoryRegion, MemoryRegionPerms, get_memory_regions,
-    read_memory_address, write_memory_address,
+    DEFAULT_SEARCH_PERMS, MemoryBackend, MemoryError, MemoryRegion, MemoryRegionPerms, NamedRange,
+    NativeMemory, PageState, READ_BLOCK_SIZE, get_page_size,
 };
+use crate::core::proc::{PausedProcess, ProcessIdentity, get_process_identity, pause_process};
+
+/// How memory is compared with the scan value
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareMode {
+    Exact,
+    /// Values that are not the scan value, drops constants on next scans
+    NotEqual,
+    /// Numeric values equal to one of the `,` separated scan values
+    AnyOf,
+    /// Numeric values within a percentage of the scan value
+    WithinPercent,
+    /// Numeric values that round to the scan value at the number of decimals it was typed
+    /// with, or that are within the epsilon when one is set
+    Rounded,
+    /// Values that differ from the ones read by the previous scan
+    Changed,
+    /// Values that are the same as the ones read by the previous scan
+    Unchanged,
+    /// Values that are back to (or still at) the ones read by the first scan
+    SameAsFirst,
+    /// Numeric values that went up since the previous scan
+    Increased,
+    /// Numeric values that went down since the previous scan
+    Decreased,
+    /// Numeric values that went up by exactly the scan value since the previous scan
+    IncreasedBy,
+    /// Numeric values that went down by exactly the scan value since the previous scan
+    DecreasedBy,
+}
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+impl CompareMode {
+    pub fn get_string(&self) -> &str {
+        match self {
+            CompareMode::Exact => "exact",
+            CompareMode::NotEqual => "not equal",
+            CompareMode::AnyOf => "any of",
+            CompareMode::WithinPercent => "within %",
+            CompareMode::Rounded => "rounded",
+            CompareMode::Changed => "changed",
+            CompareMode::Unchanged => "unchanged",
+            CompareMode::SameAsFirst => "same as first",
+            CompareMode::Increased => "increased",
+            CompareMode::Decreased => "decreased",
+            CompareMode::IncreasedBy => "increased by",
+            CompareMode::DecreasedBy => "decreased by",
+        }
+    }
+
+    pub fn next(&self) -> CompareMode {
+        match self {
+            CompareMode::Exact => CompareMode::NotEqual,
+            CompareMode::NotEqual => CompareMode::AnyOf,
+            CompareMode::AnyOf => CompareMode::WithinPercent,
+            CompareMode::WithinPercent => CompareMode::Rounded,
+            CompareMode::Rounded => CompareMode::Changed,
+            CompareMode::Changed => CompareMode::Unchanged,
+            CompareMode::Unchanged => CompareMode::SameAsFirst,
+            CompareMode::SameAsFirst => CompareMode::Increased,
+            CompareMode::Increased => CompareMode::Decreased,
+            CompareMode::Decreased => CompareMode::IncreasedBy,
+            CompareMode::IncreasedBy => CompareMode::DecreasedBy,
+            CompareMode::DecreasedBy => CompareMode::Exact,
+        }
+    }
+
+    pub fn is_numeric_only(&self) -> bool {
+        !matches!(
+            self,
+            CompareMode::Exact
+                | CompareMode::NotEqual
+                | CompareMode::AnyOf
+                | CompareMode::Changed
+                | CompareMode::Unchanged
+                | CompareMode::SameAsFirst
+        )
+    }
+
+    /// Modes that use the scan value, the others only look at the previous values
+    pub fn needs_value(&self) -> bool {
+        matches!(
+            self,
+            CompareMode::Exact
+                | CompareMode::NotEqual
+                | CompareMode::AnyOf
+                | CompareMode::WithinPercent
+                | CompareMode::Rounded
+                | CompareMode::IncreasedBy
+                | CompareMode::DecreasedBy
+        )
+    }
+
+    /// Modes that compare with the values of the previous scan, only usable for next scans
+    pub fn needs_previous(&self) -> bool {
+        !matches!(
+            self,
+            CompareMode::Exact
+                | CompareMode::AnyOf
+                | CompareMode::WithinPercent
+                | CompareMode::Rounded
+        )
+    }
+}
+
+/// Base used to show integer values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegerFormat {
+    #[default]
+    Decimal,
+    Hex,
+}
+
+impl IntegerFormat {
+    pub fn get_string(&self) -> &str {
+        match self {
+            IntegerFormat::Decimal => "decimal",
+            IntegerFormat::Hex => "hex",
+        }
+    }
+
+    pub fn next(&self) -> IntegerFormat {
+        match self {
+            IntegerFormat::Decimal => IntegerFormat::Hex,
+            IntegerFormat::Hex => IntegerFormat::Decimal,
+        }
+    }
+}
+
+/// How numeric values are shown in the results, the watchlist and the value editor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayFormat {
+    pub integers: IntegerFormat,
+    /// Group decimal digits by thousands with `_`
+    pub thousands_separator: bool,
+}
+
+/// Byte order of a value in the target's memory
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endianness {
+    pub fn get_string(&self) -> &str {
+        match self {
+            Endianness::Little => "LE",
+            Endianness::Big => "BE",
+        }
+    }
+
+    pub fn next(&self) -> Endianness {
+        match self {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        }
+    }
+
+    /// Converts between this byte order and the little-endian layout the value types decode,
+    /// works both ways and swaps array elements one by one
+    pub fn convert(&self, value_type: ValueType, value: &[u8]) -> Vec<u8> {
+        let size = value_type.get_size() as usize;
+        if *self == Endianness::Little || size == 0 || !value.len().is_multiple_of(size) {
+            return value.to_vec();
+        }
+
+        // struct fields are swapped one by one
+        if let ValueType::Struct(struct_type) = value_type {
+            return value
+                .chunks(size)
+                .flat_map(|element| {
+                    struct_type
+                        .split(element)
+                        .flat_map(|(field, bytes)| self.convert(field.value_type, bytes))
+                        .collect::<Vec<u8>>()
+                })
+                .collect();
+        }
+
+        value
+            .chunks(size)
+            .flat_map(|element| element.iter().rev().copied())
+            .collect()
+    }
+}
+
+/// What is written after a string that is shorter than the one it replaces,
+/// so the target doesn't keep reading the stale tail of the old string
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StringTerminator {
+    #[default]
+    None,
+    Nul,
+    /// Fills the rest of the old string with NULs
+    Pad,
+}
+
+impl StringTerminator {
+    /// `unit_size` is the size of a code unit of the string, e.g. 2 for UTF-16
+    pub fn terminate(&self, value: &[u8], previous_len: usize, unit_size: usize) -> Vec<u8> {
+        let mut value = value.to_vec();
+        if value.len() >= previous_len {
+            return value;
+        }
+
+        match self {
+            StringTerminator::None => {}
+            StringTerminator::Nul => value.resize(value.len() + unit_size, 0),
+            StringTerminator::Pad => value.resize(previous_len, 0),
+        }
+        value
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValueType {
+    U128,
+    I128,
     U64,
     I64,
     U32,
     I32,
+    U16,
+    I16,
+    U8,
+    I8,
+    F32,
+    F64,
+    /// Address of a 64-bit target, shown and entered as hex
+    Pointer,
+    /// Address of a 32-bit target
+    Pointer32,
     String,
+    /// UTF-16LE string, as used by Windows and many game engines
+    WideString,
+    /// One byte per character string of older western games
+    Latin1String,
+    /// String of Japanese games
+    ShiftJisString,
     Hex,
+    /// Single byte entered and shown as 8 bits, most significant first,
+    /// `*` bits of the scan value match both 0 and 1
+    Bit,
+    /// Composite type from the config file, named in the config instead of here
+    #[serde(skip)]
+    Struct(&'static StructType),
 }
 
 impl ValueType {
+    const ALL: [ValueType; 20] = [
+        ValueType::U128,
+        ValueType::I128,
+        ValueType::U64,
+        ValueType::I64,
+        ValueType::U32,
+        ValueType::I32,
+        ValueType::U16,
+        ValueType::I16,
+        ValueType::U8,
+        ValueType::I8,
+        ValueType::F32,
+        ValueType::F64,
+        ValueType::Pointer,
+        ValueType::Pointer32,
+        ValueType::String,
+        ValueType::WideString,
+        ValueType::Latin1String,
+        ValueType::ShiftJisString,
+        ValueType::Hex,
+        ValueType::Bit,
+    ];
+
+    /// Name used by the config file and edit scripts
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ValueType::U128 => "u128",
+            ValueType::I128 => "i128",
+            ValueType::U64 => "u64",
+            ValueType::I64 => "i64",
+            ValueType::U32 => "u32",
+            ValueType::I32 => "i32",
+            ValueType::U16 => "u16",
+            ValueType::I16 => "i16",
+            ValueType::U8 => "u8",
+            ValueType::I8 => "i8",
+            ValueType::F32 => "f32",
+            ValueType::F64 => "f64",
+            ValueType::Pointer => "pointer",
+            ValueType::Pointer32 => "pointer32",
+            ValueType::String => "string",
+            ValueType::WideString => "widestring",
+            ValueType::Latin1String => "latin1",
+            ValueType::ShiftJisString => "shiftjis",
+            ValueType::Hex => "hex",
+            ValueType::Bit => "bit",
+            ValueType::Struct(struct_type) => &struct_type.name,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ValueType> {
+        Self::ALL.into_iter().find(|t| t.get_name() == name)
+    }
+
     pub fn get_size(&self) -> u64 {
         match self {
-            ValueType::U64 | ValueType::I64 => 8,
-            ValueType::U32 | ValueType::I32 => 4,
-            ValueType::String | ValueType::Hex => 0,
+            ValueType::U128 | ValueType::I128 => 16,
+            ValueType::U64 | ValueType::I64 | ValueType::F64 => 8,
+            ValueType::U32 | ValueType::I32 | ValueType::F32 => 4,
+            ValueType::U16 | ValueType::I16 => 2,
+            ValueType::U8 | ValueType::I8 | ValueType::Bit => 1,
+            ValueType::Pointer => 8,
+            ValueType::Pointer32 => 4,
+            ValueType::String
+            | ValueType::WideString
+            | ValueType::Latin1String
+            | ValueType::ShiftJisString
+            | ValueType::Hex => 0,
+            ValueType::Struct(struct_type) => struct_type.get_size(),
         }
     }
 
+    /// Strings and hex take the length of the scan value
+    pub fn is_variable_size(&self) -> bool {
+        self.get_size() == 0
+    }
+
     pub fn get_string(&self) -> String {
         match self {
+            ValueType::U128 => format!("u128 ({}B)", self.get_size()),
+            ValueType::I128 => format!("i128 ({}B)", self.get_size()),
             ValueType::U64 => format!("u64 ({}B)", self.get_size()),
             ValueType::I64 => format!("i64 ({}B)", self.get_size()),
             ValueType::U32 => format!("u32 ({}B)", self.get_size()),
             ValueType::I32 => format!("i32 ({}B)", self.get_size()),
+            ValueType::U16 => format!("u16 ({}B)", self.get_size()),
+            ValueType::I16 => format!("i16 ({}B)", self.get_size()),
+            ValueType::U8 => format!("u8 ({}B)", self.get_size()),
+            ValueType::I8 => format!("i8 ({}B)", self.get_size()),
+            ValueType::F32 => format!("f32 ({}B)", self.get_size()),
+            ValueType::F64 => format!("f64 ({}B)", self.get_size()),
+            ValueType::Pointer | ValueType::Pointer32 => {
+                format!("pointer ({}B)", self.get_size())
+            }
             ValueType::String => String::from("string"),
+            ValueType::WideString => String::from("string (UTF-16)"),
+            ValueType::Latin1String => String::from("string (Latin-1)"),
+            ValueType::ShiftJisString => String::from("string (Shift-JIS)"),
             ValueType::Hex => String::from("hex"),
+            ValueType::Bit => String::from("bits (1B)"),
+            ValueType::Struct(struct_type) => {
+                format!("{} ({}B)", struct_type.name, self.get_size())
+            }
+        }
+    }
+
+    pub fn get_i128(&self, value: &[u8]) -> Option<i128> {
+        Some(match self {
+            // values past i128::MAX have no i128 representation
+            ValueType::U128 => i128::try_from(u128::from_le_bytes(value.try_into().ok()?)).ok()?,
+            ValueType::I128 => i128::from_le_bytes(value.try_into().ok()?),
+            ValueType::U64 => u64::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::I64 => i64::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::U32 => u32::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::I32 => i32::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::U16 => u16::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::I16 => i16::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::U8 | ValueType::Bit => u8::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::I8 => i8::from_le_bytes(value.try_into().ok()?) as i128,
+            ValueType::F32
+            | ValueType::F64
+            | ValueType::Pointer
+            | ValueType::Pointer32
+            | ValueType::String
+            | ValueType::WideString
+            | ValueType::Latin1String
+            | ValueType::ShiftJisString
+            | ValueType::Hex
+            | ValueType::Struct(_) => {
+                return None;
+            }
+        })
+    }
+
+    /// Address stored in a pointer value
+    pub fn get_pointer(&self, value: &[u8]) -> Option<u64> {
+        Some(match self {
+            ValueType::Pointer => u64::from_le_bytes(value.try_into().ok()?),
+            ValueType::Pointer32 => u32::from_le_bytes(value.try_into().ok()?) as u64,
+            _ => return None,
+        })
+    }
+
+    /// The pointer type matching the pointer width of the target, other types are kept
+    pub fn for_pointer_width(&self, pointer_width: usize) -> ValueType {
+        match self {
+            ValueType::Pointer | ValueType::Pointer32 if pointer_width == 4 => ValueType::Pointer32,
+            ValueType::Pointer | ValueType::Pointer32 => ValueType::Pointer,
+            value_type => *value_type,
+        }
+    }
+
+    /// Numbers are usually stored at addresses that are a multiple of their size
+    pub fn get_default_alignment(&self) -> usize {
+        if let ValueType::Struct(struct_type) = self {
+            return struct_type
+                .fields
+                .iter()
+                .map(|field| field.value_type.get_default_alignment())
+                .max()
+                .unwrap_or(1);
+        }
+        match self.get_size() {
+            0 => 1,
+            size => size as usize,
+        }
+    }
+
+    pub fn get_f64(&self, value: &[u8]) -> Option<f64> {
+        match self {
+            ValueType::F32 => Some(f32::from_le_bytes(value.try_into().ok()?) as f64),
+            ValueType::F64 => Some(f64::from_le_bytes(value.try_into().ok()?)),
+            ValueType::U128 => Some(u128::from_le_bytes(value.try_into().ok()?) as f64),
+            _ => self.get_i128(value).map(|v| v as f64),
+        }
+    }
+
+    /// Number of elements in a value of this type, more than one for array scans
+    pub fn get_array_len(&self, value: &[u8]) -> usize {
+        let size = self.get_size() as usize;
+        if size == 0 || !value.len().is_multiple_of(size) {
+            return 1;
         }
+        (value.len() / size).max(1)
     }
 
     pub fn get_value_string(&self, value: &[u8]) -> Result<String, TryFromSliceError> {
@@ -42,791 +466,5118 @@ impl ValueType {
             return Ok(String::new());
         }
 
+        // arrays are listed element by element
+        if self.get_array_len(value) > 1 {
+            let elements = value
+                .chunks(self.get_size() as usize)
+                .map(|element| self.get_value_string(element))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(elements.join(", "));
+        }
+
         Ok(match self {
+            ValueType::U128 => format!("{}", u128::from_le_bytes(value.try_into()?)),
+            ValueType::I128 => format!("{}", i128::from_le_bytes(value.try_into()?)),
             ValueType::U64 => format!("{}", u64::from_le_bytes(value.try_into()?)),
             ValueType::I64 => format!("{}", i64::from_le_bytes(value.try_into()?)),
             ValueType::U32 => format!("{}", u32::from_le_bytes(value.try_into()?)),
             ValueType::I32 => format!("{}", i32::from_le_bytes(value.try_into()?)),
+            ValueType::U16 => format!("{}", u16::from_le_bytes(value.try_into()?)),
+            ValueType::I16 => format!("{}", i16::from_le_bytes(value.try_into()?)),
+            ValueType::U8 => format!("{}", u8::from_le_bytes(value.try_into()?)),
+            ValueType::I8 => format!("{}", i8::from_le_bytes(value.try_into()?)),
+            ValueType::F32 => format!("{}", f32::from_le_bytes(value.try_into()?)),
+            ValueType::F64 => format!("{}", f64::from_le_bytes(value.try_into()?)),
+            ValueType::Pointer => format!("0x{:x}", u64::from_le_bytes(value.try_into()?)),
+            ValueType::Pointer32 => format!("0x{:x}", u32::from_le_bytes(value.try_into()?)),
             ValueType::String => {
                 let valid_end = str::from_utf8(value)
                     .map(|_| value.len())
                     .unwrap_or_else(|e| e.valid_up_to());
 
-                let s = String::from_utf8_lossy(&value[..valid_end]);
+                escape_string(String::from_utf8_lossy(&value[..valid_end]).chars())
+            }
+            ValueType::WideString => {
+                let units = value
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
 
-                s.chars()
-                    .map(|c| match c {
-                        '\x1b' => String::from("\\x1b"),                       // ANSI escape
-                        c if c.is_control() => format!("\\x{:02x}", c as u32), // other control chars
-                        _ => c.to_string(),
-                    })
-                    .collect::<String>()
+                escape_string(char::decode_utf16(units).map_while(Result::ok))
             }
+            ValueType::Latin1String => escape_string(value.iter().map(|&byte| char::from(byte))),
+            // like UTF-8 strings, decoding stops at the first invalid or cut off character
+            ValueType::ShiftJisString => escape_string(
+                SHIFT_JIS
+                    .decode_without_bom_handling(value)
+                    .0
+                    .chars()
+                    .take_while(|&c| c != char::REPLACEMENT_CHARACTER),
+            ),
             ValueType::Hex => hex::encode(value),
+            ValueType::Bit => format!("{:08b}", u8::from_le_bytes(value.try_into()?)),
+            ValueType::Struct(struct_type) => {
+                struct_type.format_value(value, &DisplayFormat::default())?
+            }
         })
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct ScanResult {
-    pub address: u64,
-    pub value_type: ValueType,
-    pub perms: Vec<MemoryRegionPerms>,
-    pub value: Vec<u8>,
-}
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ScanError {
-    InvalidValue,
-    EmptyValue,
-    InvalidAddress,
-    AddressMismatch,
-    ReadSizeInvalid(usize, usize),
-    Memory(MemoryError),
-    TypeMismatch,
-}
-impl std::fmt::Display for ScanError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::InvalidValue => write!(f, "Invalid scan value provided"),
-            Self::EmptyValue => write!(f, "Value is reqeuired to be set before scan"),
-            Self::InvalidAddress => write!(f, "Invalid address hex"),
-            Self::AddressMismatch => write!(f, "Start address should be smaller than end address"),
-            Self::TypeMismatch => write!(f, "Invalid type for value"),
-            Self::ReadSizeInvalid(min, max) => {
-                write!(f, "Read size should be in range {min}-{max}")
-            }
-            Self::Memory(e) => write!(f, "{e}"),
+    /// Same as `get_value_string` with the display options applied to integers
+    pub fn format_value(
+        &self,
+        value: &[u8],
+        format: &DisplayFormat,
+    ) -> Result<String, TryFromSliceError> {
+        // bits are always shown as bits
+        if value.is_empty() || self.get_size() == 0 || *self == ValueType::Bit {
+            return self.get_value_string(value);
         }
-    }
-}
-
-impl ScanResult {
-    pub fn new(
-        address: u64,
-        value_type: ValueType,
-        value: Vec<u8>,
-        perms: Vec<MemoryRegionPerms>,
-    ) -> Self {
-        ScanResult {
-            address,
-            value_type,
-            perms,
-            value,
+        if let ValueType::Struct(struct_type) = self
+            && self.get_array_len(value) == 1
+        {
+            return struct_type.format_value(value, format);
         }
-    }
-
-    pub fn get_string(&self) -> Result<String, ScanError> {
-        self.value_type
-            .get_value_string(self.value.as_slice())
-            .map_err(|_| ScanError::TypeMismatch)
-    }
-
-    pub fn is_read_only(&self) -> bool {
-        !self.perms.contains(&MemoryRegionPerms::Write)
-    }
-}
-
-#[derive(Debug)]
-pub struct Scan {
-    pub pid: u32,
-    pub value: Vec<u8>,
-    pub value_type: ValueType,
-    pub results: Vec<ScanResult>,
-    pub watchlist: Vec<ScanResult>,
-    read_size: Option<usize>,
-    start_address: Option<u64>,
-    end_address: Option<u64>,
-    memory_permissions: Vec<MemoryRegionPerms>,
-    memory_regions: Vec<MemoryRegion>,
-}
 
-impl Scan {
-    pub fn new(
-        pid: u32,
-        value: Vec<u8>,
-        value_type: ValueType,
-        start_address: Option<u64>,
-        end_address: Option<u64>,
-        memory_permissions: Option<Vec<MemoryRegionPerms>>,
-    ) -> Result<Self, ScanError> {
-        let memory_permissions = memory_permissions.unwrap_or(DEFAULT_SEARCH_PERMS.to_vec());
-        let memory_regions =
-            get_memory_regions(pid, start_address, end_address, Some(&memory_permissions))
-                .map_err(ScanError::Memory)?;
+        if self.get_array_len(value) > 1 {
+            let elements = value
+                .chunks(self.get_size() as usize)
+                .map(|element| self.format_value(element, format))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(elements.join(", "));
+        }
 
-        Ok(Scan {
-            pid,
-            read_size: None,
-            value,
-            start_address,
-            end_address,
-            memory_regions,
-            value_type,
-            memory_permissions,
-            results: vec![],
-            watchlist: vec![],
+        Ok(match format.integers {
+            IntegerFormat::Hex => match self {
+                ValueType::U128 => format!("0x{:x}", u128::from_le_bytes(value.try_into()?)),
+                ValueType::I128 => format!("0x{:x}", i128::from_le_bytes(value.try_into()?)),
+                ValueType::U64 => format!("0x{:x}", u64::from_le_bytes(value.try_into()?)),
+                ValueType::I64 => format!("0x{:x}", i64::from_le_bytes(value.try_into()?)),
+                ValueType::U32 => format!("0x{:x}", u32::from_le_bytes(value.try_into()?)),
+                ValueType::I32 => format!("0x{:x}", i32::from_le_bytes(value.try_into()?)),
+                ValueType::U16 => format!("0x{:x}", u16::from_le_bytes(value.try_into()?)),
+                ValueType::I16 => format!("0x{:x}", i16::from_le_bytes(value.try_into()?)),
+                ValueType::U8 => format!("0x{:x}", u8::from_le_bytes(value.try_into()?)),
+                ValueType::I8 => format!("0x{:x}", i8::from_le_bytes(value.try_into()?)),
+                ValueType::F32
+                | ValueType::F64
+                | ValueType::Pointer
+                | ValueType::Pointer32
+                | ValueType::String
+                | ValueType::WideString
+                | ValueType::Latin1String
+                | ValueType::ShiftJisString
+                | ValueType::Hex
+                | ValueType::Bit
+                | ValueType::Struct(_) => self.get_value_string(value)?,
+            },
+            IntegerFormat::Decimal if self.get_i128(value).is_none() => {
+                self.get_value_string(value)?
+            }
+            IntegerFormat::Decimal if format.thousands_separator => {
+                group_thousands(&self.get_value_string(value)?)
+            }
+            IntegerFormat::Decimal => self.get_value_string(value)?,
         })
     }
 
-    pub fn set_mem_permissions(
-        &mut self,
-        memory_permissions: Vec<MemoryRegionPerms>,
-    ) -> Result<(), ScanError> {
-        self.memory_permissions = memory_permissions;
-        self.update_memory_regions()?;
-        Ok(())
-    }
+    /// Numeric types take a `,` separated list for an array of contiguous values
+    pub fn parse_value(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        if self.get_size() == 0 || matches!(self, ValueType::Struct(_)) {
+            return self.parse_element(value_str);
+        }
 
-    pub fn set_value_type(
-        &mut self,
-        value_type: ValueType,
-        value_str: Option<&str>,
-    ) -> Result<(), ScanError> {
-        self.value_type = value_type;
-        if let Some(value) = value_str {
-            self.set_value_from_str(value)?;
+        let mut value = vec![];
+        for element in value_str.split(',') {
+            value.extend(self.parse_element(element.trim())?);
         }
-        Ok(())
+        Ok(value)
     }
 
-    pub fn set_read_size(&mut self, size: Option<usize>) -> Result<(), ScanError> {
-        const MAX_READ_SIZE: usize = 256;
-        const MIN_READ_SIZE: usize = 1;
+    /// Bits of a parsed value that have to match, empty when all of them do.
+    /// `*` bits of bit patterns and `*` struct fields match anything
+    pub fn parse_mask(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        let mask: Vec<u8> = match self {
+            ValueType::Bit => value_str
+                .split(',')
+                .map(|pattern| Ok(parse_bit_pattern(pattern.trim())?.1))
+                .collect::<Result<_, ScanError>>()?,
+            ValueType::Struct(struct_type) => struct_type.parse_mask(value_str)?,
+            _ => return Ok(vec![]),
+        };
 
-        if let Some(size) = size {
-            if !(MIN_READ_SIZE..=MAX_READ_SIZE).contains(&size) {
-                return Err(ScanError::ReadSizeInvalid(MIN_READ_SIZE, MAX_READ_SIZE));
-            }
-            self.read_size = Some(size);
-        } else {
-            self.read_size = None;
+        if mask.iter().all(|&bits| bits == u8::MAX) {
+            return Ok(vec![]);
         }
-        Ok(())
+        Ok(mask)
     }
 
-    pub fn value_from_str(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
-        Ok(match self.value_type {
-            ValueType::U64 => value_str
-                .parse::<u64>()
+    fn parse_element(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        let (digits, radix) = integer_input(value_str);
+        Ok(match self {
+            ValueType::U128 => u128::from_str_radix(&digits, radix)
                 .map_err(|_| ScanError::InvalidValue)?
                 .to_le_bytes()
                 .to_vec(),
-            ValueType::I64 => value_str
-                .parse::<i64>()
+            ValueType::I128 if radix == 16 => {
+                (u128::from_str_radix(&digits, radix).map_err(|_| ScanError::InvalidValue)? as i128)
+                    .to_le_bytes()
+                    .to_vec()
+            }
+            ValueType::I128 => i128::from_str_radix(&digits, radix)
                 .map_err(|_| ScanError::InvalidValue)?
                 .to_le_bytes()
                 .to_vec(),
-            ValueType::U32 => value_str
-                .parse::<u32>()
+            ValueType::U64 => u64::from_str_radix(&digits, radix)
                 .map_err(|_| ScanError::InvalidValue)?
                 .to_le_bytes()
                 .to_vec(),
-            ValueType::I32 => value_str
-                .parse::<i32>()
+            // hex input of signed types is the two's complement, as it is displayed
+            ValueType::I64 if radix == 16 => {
+                (u64::from_str_radix(&digits, radix).map_err(|_| ScanError::InvalidValue)? as i64)
+                    .to_le_bytes()
+                    .to_vec()
+            }
+            ValueType::I64 => i64::from_str_radix(&digits, radix)
                 .map_err(|_| ScanError::InvalidValue)?
                 .to_le_bytes()
                 .to_vec(),
-            ValueType::String => value_str.as_bytes().to_vec(),
-            ValueType::Hex => {
-                let hex_str = value_str.trim_start_matches("0x");
-                hex::decode(hex_str).map_err(|_| ScanError::InvalidValue)?
+            ValueType::U32 => u32::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::I32 if radix == 16 => {
+                (u32::from_str_radix(&digits, radix).map_err(|_| ScanError::InvalidValue)? as i32)
+                    .to_le_bytes()
+                    .to_vec()
             }
-        })
-    }
-
-    pub fn set_value_from_str(&mut self, value_str: &str) -> Result<(), ScanError> {
-        self.value = self.value_from_str(value_str)?;
-
-        Ok(())
-    }
-
-    fn parse_address_hex(addr_hex: &str) -> Result<Option<u64>, ScanError> {
-        if addr_hex.is_empty() {
-            Ok(None)
-        } else {
-            let parsed_addr = u64::from_str_radix(addr_hex.trim_start_matches("0x"), 16)
-                .map_err(|_| ScanError::InvalidAddress)?;
-            Ok(Some(parsed_addr))
-        }
-    }
-
-    fn update_memory_regions(&mut self) -> Result<(), ScanError> {
-        self.memory_regions = get_memory_regions(
-            self.pid,
-            self.start_address,
-            self.end_address,
-            Some(&self.memory_permissions),
-        )
-        .map_err(ScanError::Memory)?;
-        Ok(())
-    }
-
-    pub fn set_start_address(&mut self, addr_hex: &str) -> Result<(), ScanError> {
-        let parsed_addr = Self::parse_address_hex(addr_hex)?;
-
-        if let (Some(start), Some(end)) = (parsed_addr, self.end_address)
-            && start > end
-        {
-            return Err(ScanError::AddressMismatch);
-        }
-
-        self.start_address = parsed_addr;
-        self.update_memory_regions()?;
-
-        Ok(())
+            ValueType::I32 => i32::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::U16 => u16::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::I16 if radix == 16 => {
+                (u16::from_str_radix(&digits, radix).map_err(|_| ScanError::InvalidValue)? as i16)
+                    .to_le_bytes()
+                    .to_vec()
+            }
+            ValueType::I16 => i16::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::U8 => u8::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::I8 if radix == 16 => {
+                (u8::from_str_radix(&digits, radix).map_err(|_| ScanError::InvalidValue)? as i8)
+                    .to_le_bytes()
+                    .to_vec()
+            }
+            ValueType::I8 => i8::from_str_radix(&digits, radix)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::F32 => value_str
+                .parse::<f32>()
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::F64 => value_str
+                .parse::<f64>()
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            // pointers are always hex, the 0x prefix is optional
+            ValueType::Pointer => u64::from_str_radix(value_str.trim_start_matches("0x"), 16)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::Pointer32 => u32::from_str_radix(value_str.trim_start_matches("0x"), 16)
+                .map_err(|_| ScanError::InvalidValue)?
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::String => unescape_string(value_str)?,
+            ValueType::WideString => String::from_utf8(unescape_string(value_str)?)
+                .map_err(|_| ScanError::InvalidValue)?
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+            ValueType::Latin1String => encode_string(&unescape_string(value_str)?, |chars| {
+                chars.chars().map(|c| u8::try_from(c).ok()).collect()
+            })?,
+            ValueType::ShiftJisString => encode_string(&unescape_string(value_str)?, |chars| {
+                let (bytes, _, had_errors) = SHIFT_JIS.encode(chars);
+                (!had_errors).then(|| bytes.into_owned())
+            })?,
+            ValueType::Hex => {
+                let hex_str = value_str.trim_start_matches("0x");
+                hex::decode(hex_str).map_err(|_| ScanError::InvalidValue)?
+            }
+            ValueType::Bit => vec![parse_bit_pattern(value_str)?.0],
+            ValueType::Struct(struct_type) => struct_type.parse(value_str)?,
+        })
     }
+}
 
-    pub fn set_end_address(&mut self, addr_hex: &str) -> Result<(), ScanError> {
-        let parsed_addr = Self::parse_address_hex(addr_hex)?;
-
-        if let (Some(start), Some(end)) = (self.start_address, parsed_addr)
-            && end < start
-        {
-            return Err(ScanError::AddressMismatch);
-        }
+/// Composite value type defined in the config file, the fields are packed in order
+/// without padding. Values are written as `field: value; ...`, the names are optional
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StructType {
+    pub name: String,
+    pub fields: Vec<StructField>,
+}
 
-        self.end_address = parsed_addr;
-        self.update_memory_regions()?;
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub value_type: ValueType,
+}
 
-        Ok(())
+impl StructType {
+    /// Definitions are kept for the rest of the run so value types can refer to them
+    pub fn into_value_type(self) -> ValueType {
+        ValueType::Struct(Box::leak(Box::new(self)))
     }
 
-    fn scan_region(&self, region: &MemoryRegion) -> Result<Vec<ScanResult>, MemoryError> {
-        let start = region.start as usize;
-        let end = region.end as usize;
-        let size = self.read_size.unwrap_or(self.value.len());
-
-        const BLOCK_SIZE: usize = 0x10000;
-
-        // Validate region with a single byte read to catch ProcessAttach errors early
-        if let Err(e) = read_memory_address(self.pid, start, 1)
-            && let MemoryError::ProcessAttach(_) = e {
-                return Err(e);
-            }
+    /// Fields have to be fixed size values, returns the reason when they are not
+    pub fn check(&self) -> Result<(), String> {
+        if self.fields.is_empty() {
+            return Err(format!("struct `{}` has no fields", self.name));
+        }
+        match self.fields.iter().find(|f| f.value_type.is_variable_size()) {
+            Some(field) => Err(format!(
+                "field `{}` of struct `{}` is not a fixed size type",
+                field.name, self.name
+            )),
+            None => Ok(()),
+        }
+    }
 
-        // Generate all block addresses to scan
-        let block_addresses: Vec<usize> = {
-            let mut addresses = Vec::new();
-            let mut current_address = start;
-            while current_address < end {
-                let to_read = std::cmp::min(BLOCK_SIZE, end - current_address);
-                if to_read < size {
-                    break;
-                }
-                addresses.push(current_address);
-                current_address += to_read - (size - 1);
-            }
-            addresses
-        };
+    pub fn get_size(&self) -> u64 {
+        self.fields.iter().map(|f| f.value_type.get_size()).sum()
+    }
 
-        // Parallel scan of all blocks
-        let results: Vec<Vec<ScanResult>> = block_addresses
-            .par_iter()
-            .filter_map(|&current_address| {
-                let to_read = std::cmp::min(BLOCK_SIZE, end - current_address);
+    /// Every field with its bytes of `value`, fields past the end of it get no bytes
+    fn split<'a>(&'a self, value: &'a [u8]) -> impl Iterator<Item = (&'a StructField, &'a [u8])> {
+        self.fields.iter().scan(0, move |offset, field| {
+            let start = *offset;
+            *offset += field.value_type.get_size() as usize;
+            Some((field, value.get(start..*offset).unwrap_or_default()))
+        })
+    }
 
-                match read_memory_address(self.pid, current_address, to_read) {
-                    Err(_) => None, // Ignore all errors during parallel scan
-                    Ok(val) => {
-                        let block_results: Vec<ScanResult> = memmem::find_iter(&val, &self.value)
-                            .map(|i| {
-                                // Take all available data from position i, up to size bytes
-                                let end_offset = std::cmp::min(i + size, val.len());
-                                ScanResult::new(
-                                    (current_address + i) as u64,
-                                    self.value_type,
-                                    val[i..end_offset].to_vec(),
-                                    region.perms.clone(),
-                                )
-                            })
-                            .collect();
-                        Some(block_results)
-                    }
-                }
+    fn format_value(
+        &self,
+        value: &[u8],
+        format: &DisplayFormat,
+    ) -> Result<String, TryFromSliceError> {
+        let fields = self
+            .split(value)
+            .map(|(field, bytes)| {
+                let value = field.value_type.format_value(bytes, format)?;
+                Ok(format!("{}: {value}", field.name))
             })
-            .collect();
-
-        // Flatten results
-        Ok(results.into_iter().flatten().collect())
+            .collect::<Result<Vec<_>, TryFromSliceError>>()?;
+        Ok(fields.join("; "))
     }
 
-    fn check_value(&self) -> Result<(), ScanError> {
-        if self.value.is_empty() {
-            return Err(ScanError::EmptyValue);
+    /// Input of every field, a `*` field is zero in the value and matches anything
+    fn split_input<'a>(
+        &'a self,
+        value_str: &'a str,
+    ) -> Result<impl Iterator<Item = (&'a StructField, &'a str)>, ScanError> {
+        let inputs: Vec<&str> = value_str.split(';').map(str::trim).collect();
+        if inputs.len() != self.fields.len() {
+            return Err(ScanError::InvalidValue);
         }
 
-        self.value_type
-            .get_value_string(&self.value)
-            .map_err(|_| ScanError::TypeMismatch)?;
-
-        Ok(())
+        let mut fields = vec![];
+        for (field, input) in self.fields.iter().zip(inputs) {
+            let input = match input.split_once(':') {
+                Some((name, input)) if name.trim() == field.name => input.trim(),
+                Some(_) => return Err(ScanError::InvalidValue),
+                None => input,
+            };
+            fields.push((field, input));
+        }
+        Ok(fields.into_iter())
     }
 
-    fn refresh_watchlist(&mut self) -> Result<(), ScanError> {
-        self.check_value()?;
-
-        if self.watchlist.is_empty() {
-            return Ok(());
+    fn parse(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        let mut value = vec![];
+        for (field, input) in self.split_input(value_str)? {
+            let size = field.value_type.get_size() as usize;
+            if input == "*" {
+                value.resize(value.len() + size, 0);
+                continue;
+            }
+            let field_value = field.value_type.parse_value(input)?;
+            if field_value.len() != size {
+                return Err(ScanError::InvalidValue);
+            }
+            value.extend(field_value);
         }
+        Ok(value)
+    }
 
-        // Early validation with single read to catch ProcessAttach errors
-        if let Some(first) = self.watchlist.first() {
-            let read_size = self.read_size.unwrap_or(first.value.len());
-            if let Err(e) = read_memory_address(self.pid, first.address as usize, read_size)
-                && let MemoryError::ProcessAttach(_) = e {
-                    return Err(ScanError::Memory(e));
-                }
+    fn parse_mask(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        let mut mask = vec![];
+        for (field, input) in self.split_input(value_str)? {
+            let size = field.value_type.get_size() as usize;
+            match input {
+                "*" => mask.resize(mask.len() + size, 0),
+                _ => match field.value_type.parse_mask(input)? {
+                    field_mask if field_mask.is_empty() => mask.resize(mask.len() + size, u8::MAX),
+                    field_mask => mask.extend(field_mask),
+                },
+            }
         }
-
-        // Parallel refresh
-        let updated_watchlist: Vec<ScanResult> = self
-            .watchlist
-            .par_iter()
-            .filter_map(|result| {
-                let read_size = self.read_size.unwrap_or(result.value.len());
-                match read_memory_address(self.pid, result.address as usize, read_size) {
-                    Err(_) => None, // Ignore errors during parallel scan
-                    Ok(val) => {
-                        let mut updated = result.clone();
-                        updated.value_type = self.value_type;
-                        updated.value = val;
-                        Some(updated)
-                    }
-                }
-            })
-            .collect();
-
-        self.watchlist = updated_watchlist;
-        Ok(())
+        Ok(mask)
     }
+}
 
-    pub fn init(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
-        self.check_value()?;
+/// Offsets into `len` bytes read at `start` that are at a multiple of `alignment`
+/// and have room for `size` bytes, unaligned offsets are skipped instead of checked
+fn aligned_offsets(
+    start: usize,
+    len: usize,
+    size: usize,
+    alignment: usize,
+) -> impl Iterator<Item = usize> {
+    let first = (alignment - start % alignment) % alignment;
+    (first..(len + 1).saturating_sub(size)).step_by(alignment)
+}
 
-        // Parallel scan across memory regions
-        let results: Result<Vec<Vec<ScanResult>>, MemoryError> = self
-            .memory_regions
-            .par_iter()
-            .map(|region| self.scan_region(region))
-            .collect();
+/// Every mapping of `pid` a scan could select, regions without read or write access
+/// never match any permissions
+fn get_all_regions(memory: &dyn MemoryBackend, pid: u32) -> Result<Vec<MemoryRegion>, ScanError> {
+    let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+    memory
+        .regions(pid, None, None, Some(&perms))
+        .map_err(ScanError::Memory)
+}
 
-        let results = results.map_err(ScanError::Memory)?;
-        self.results = results.into_iter().flatten().collect();
-        self.refresh_watchlist()?;
+fn select_regions(
+    regions: &[MemoryRegion],
+    start: Option<u64>,
+    end: Option<u64>,
+    search_perms: &[MemoryRegionPerms],
+) -> Vec<MemoryRegion> {
+    regions
+        .iter()
+        .filter(|region| region.is_selected(start, end, search_perms))
+        .cloned()
+        .collect()
+}
 
-        Ok(&self.results)
+/// Offsets of a 2, 4 or 8 byte `needle` at the multiples of its size in `data` read
+/// at `start`. Words of 64 bytes are XORed with the repeated needle and only blocks
+/// with an all zero lane are checked value by value, a loop the compiler vectorizes
+fn find_aligned(data: &[u8], start: usize, needle: &[u8]) -> Vec<usize> {
+    let size = needle.len();
+    debug_assert!(matches!(size, 2 | 4 | 8));
+    let first = (size - start % size) % size;
+    let Some(aligned) = data.get(first..) else {
+        return vec![];
+    };
+
+    let mut pattern = [0u8; 8];
+    for lane in pattern.chunks_exact_mut(size) {
+        lane.copy_from_slice(needle);
     }
-
-    pub fn refresh(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
-        self.check_value()?;
-
-        if self.results.is_empty() {
-            self.refresh_watchlist()?;
-            return Ok(&self.results);
-        }
-
-        // Early validation with single read to catch ProcessAttach errors
-        if let Some(first) = self.results.first() {
-            let read_size = self.read_size.unwrap_or(first.value.len());
-            if let Err(e) = read_memory_address(self.pid, first.address as usize, read_size)
-                && let MemoryError::ProcessAttach(_) = e {
-                    return Err(ScanError::Memory(e));
-                }
+    let pattern = u64::from_ne_bytes(pattern);
+    // lowest and highest bit of every lane
+    let lane_bits = size * 8;
+    let (low, high) = (0..64 / lane_bits).fold((0u64, 0u64), |(low, high), lane| {
+        let shift = lane * lane_bits;
+        (low | 1 << shift, high | 1 << (shift + lane_bits - 1))
+    });
+
+    let mut offsets = vec![];
+    let mut find = |values: &[u8], offset: usize| {
+        offsets.extend(
+            values
+                .chunks_exact(size)
+                .enumerate()
+                .filter(|(_, value)| *value == needle)
+                .map(|(i, _)| offset + i * size),
+        );
+    };
+    let mut blocks = aligned.chunks_exact(64);
+    for (i, block) in blocks.by_ref().enumerate() {
+        let zero_lanes = block.chunks_exact(8).fold(0, |zero_lanes, word| {
+            let x = u64::from_ne_bytes(word.try_into().unwrap()) ^ pattern;
+            zero_lanes | (x.wrapping_sub(low) & !x & high)
+        });
+        if zero_lanes != 0 {
+            find(block, first + i * 64);
         }
+    }
+    let tail = blocks.remainder();
+    find(tail, first + aligned.len() - tail.len());
+    offsets
+}
 
-        // Parallel refresh
-        let updated_results: Vec<ScanResult> = self
-            .results
-            .par_iter()
-            .filter_map(|result| {
-                let read_size = self.read_size.unwrap_or(result.value.len());
-                match read_memory_address(self.pid, result.address as usize, read_size) {
-                    Err(_) => None, // Ignore errors during parallel scan
-                    Ok(val) => {
-                        let mut updated = result.clone();
-                        updated.value_type = self.value_type;
-                        updated.value = val;
-                        Some(updated)
-                    }
-                }
-            })
-            .collect();
+/// Value and mask of a bit pattern like `1*0`, missing leading bits are `*`
+fn parse_bit_pattern(pattern: &str) -> Result<(u8, u8), ScanError> {
+    if pattern.is_empty() || pattern.len() > 8 {
+        return Err(ScanError::InvalidValue);
+    }
 
-        self.results = updated_results;
-        self.refresh_watchlist()?;
+    let (mut value, mut mask) = (0u8, 0u8);
+    for bit in pattern.chars() {
+        let (bit_value, bit_mask) = match bit {
+            '0' => (0, 1),
+            '1' => (1, 1),
+            '*' => (0, 0),
+            _ => return Err(ScanError::InvalidValue),
+        };
+        value = value << 1 | bit_value;
+        mask = mask << 1 | bit_mask;
+    }
+    Ok((value, mask))
+}
 
-        Ok(&self.results)
+/// Compares `value` with `expected`, bits cleared in `mask` are ignored unless it is empty
+fn masked_equal(value: &[u8], expected: &[u8], mask: &[u8]) -> bool {
+    if mask.is_empty() {
+        return value == expected;
     }
+    value.len() == expected.len()
+        && value
+            .iter()
+            .zip(expected)
+            .zip(mask)
+            .all(|((v, e), m)| (v ^ e) & m == 0)
+}
 
-    pub fn next_scan(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
-        self.check_value()?;
+/// Most digits after the decimal point of the typed numbers, `1.25` has 2
+fn count_decimals(value_str: &str) -> u32 {
+    value_str
+        .split(',')
+        .filter_map(|number| number.split(['e', 'E']).next()?.split_once('.'))
+        .map(|(_, decimals)| decimals.trim().len() as u32)
+        .max()
+        .unwrap_or(0)
+}
 
-        if self.results.is_empty() {
-            self.refresh_watchlist()?;
-            return Ok(&self.results);
-        }
+fn group_thousands(digits: &str) -> String {
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits),
+    };
 
-        // Early validation with single read to catch ProcessAttach errors
-        if let Some(first) = self.results.first() {
-            let read_size = self.read_size.unwrap_or(first.value.len());
-            if let Err(e) = read_memory_address(self.pid, first.address as usize, read_size)
-                && let MemoryError::ProcessAttach(_) = e {
-                    return Err(ScanError::Memory(e));
-                }
+    let mut grouped = String::from(sign);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push('_');
         }
+        grouped.push(c);
+    }
+    grouped
+}
 
-        // Parallel next scan
-        let new_results: Vec<ScanResult> = self
-            .results
-            .par_iter()
-            .filter_map(|result| {
-                let read_size = self.read_size.unwrap_or(result.value.len());
-                match read_memory_address(self.pid, result.address as usize, read_size) {
-                    Err(_) => None, // Ignore errors during parallel scan
-                    Ok(val) => {
-                        // check only prefix - ensure bounds are valid
-                        if val.len() >= self.value.len() && val[..self.value.len()] == self.value {
-                            let mut new_result = result.clone();
-                            new_result.value_type = self.value_type;
-                            new_result.value = val;
-                            Some(new_result)
-                        } else {
-                            None
-                        }
-                    }
-                }
-            })
-            .collect();
+/// Integers are typed in decimal (`_` separators are ignored) or as `0x` prefixed hex,
+/// so values shown with any display format can be entered back
+fn integer_input(value_str: &str) -> (String, u32) {
+    match value_str.strip_prefix("0x") {
+        Some(hex_str) => (hex_str.to_owned(), 16),
+        None => (value_str.replace('_', ""), 10),
+    }
+}
 
-        self.results = new_results;
-        self.refresh_watchlist()?;
+/// Shows the characters of a string value with the escapes `unescape_string` reads back
+fn escape_string(chars: impl Iterator<Item = char>) -> String {
+    chars
+        .map(|c| match c {
+            '\\' => String::from("\\\\"), // so the shown string can be typed back
+            '\x1b' => String::from("\\x1b"), // ANSI escape
+            c if c.is_control() => format!("\\x{:02x}", c as u32), // other control chars
+            _ => c.to_string(),
+        })
+        .collect()
+}
 
-        Ok(&self.results)
+/// Re-encodes the UTF-8 text of an unescaped string with `encode`, which returns `None` for
+/// characters the encoding doesn't have. Bytes typed as `\xNN` are kept as they are
+fn encode_string(
+    value: &[u8],
+    encode: impl Fn(&str) -> Option<Vec<u8>>,
+) -> Result<Vec<u8>, ScanError> {
+    let mut encoded = vec![];
+    for chunk in value.utf8_chunks() {
+        encoded.extend(encode(chunk.valid()).ok_or(ScanError::InvalidValue)?);
+        encoded.extend(chunk.invalid());
     }
+    Ok(encoded)
+}
 
-    pub fn add_to_watchlist(&mut self, result: ScanResult) {
-        let already_existing = self
-            .watchlist
-            .iter()
-            .position(|w| w.address == result.address);
-        if already_existing.is_some() {
-            return;
+/// Turns the `\n`, `\r`, `\t`, `\0`, `\\` and `\xNN` escapes of a string value into bytes
+fn unescape_string(value_str: &str) -> Result<Vec<u8>, ScanError> {
+    let mut bytes = vec![];
+    let mut chars = value_str.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend(c.encode_utf8(&mut buf).as_bytes());
+            continue;
         }
 
-        self.watchlist.push(result);
+        bytes.push(match chars.next() {
+            Some('n') => b'\n',
+            Some('r') => b'\r',
+            Some('t') => b'\t',
+            Some('0') => 0,
+            Some('\\') => b'\\',
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                if digits.len() != 2 {
+                    return Err(ScanError::InvalidValue);
+                }
+                u8::from_str_radix(&digits, 16).map_err(|_| ScanError::InvalidValue)?
+            }
+            _ => return Err(ScanError::InvalidValue),
+        });
     }
+    Ok(bytes)
+}
 
-    pub fn remove_from_watchlist(&mut self, address: u64) {
-        let already_existing = self.watchlist.iter().position(|w| w.address == address);
-        if already_existing.is_none() {
-            return;
+/// Hex with or without `0x`, decimal needs a `d:` prefix as plain digits are read as hex
+fn parse_address_number(number: &str) -> Result<u64, ScanError> {
+    let number = number.trim();
+    let parsed = match number.strip_prefix("d:") {
+        Some(decimal) => decimal.trim().parse::<u64>(),
+        None => {
+            let hex = number
+                .strip_prefix("0x")
+                .or_else(|| number.strip_prefix("0X"))
+                .unwrap_or(number);
+            u64::from_str_radix(hex, 16)
         }
+    };
+    parsed.map_err(|_| ScanError::InvalidAddress)
+}
 
-        self.watchlist.remove(already_existing.unwrap());
+/// Evaluates a hex address with optional `+`/`-` hex offsets, the first term
+/// can also be the name of a range, e.g. `libgame.so+0x2000` or `heap+0x100`
+pub fn evaluate_address(expression: &str, ranges: &[NamedRange]) -> Result<u64, ScanError> {
+    let expression = expression.trim();
+    let is_operator = |rest: &str| rest.is_empty() || rest.starts_with(['+', '-']);
+
+    // names can contain `+` and `-` themselves, the longest matching one wins
+    let range = ranges
+        .iter()
+        .filter(|r| {
+            expression
+                .strip_prefix(r.name.as_str())
+                .is_some_and(|rest| is_operator(rest.trim_start()))
+        })
+        .max_by_key(|r| r.name.len());
+
+    let (mut address, mut rest) = match range {
+        Some(range) => (range.start, expression[range.name.len()..].trim_start()),
+        None => {
+            let end = expression.find(['+', '-']).unwrap_or(expression.len());
+            (
+                parse_address_number(&expression[..end])?,
+                &expression[end..],
+            )
+        }
+    };
+
+    while let Some(operator) = rest.chars().next() {
+        rest = &rest[1..];
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let offset = parse_address_number(&rest[..end])?;
+        address = match operator {
+            '+' => address.checked_add(offset),
+            _ => address.checked_sub(offset),
+        }
+        .ok_or(ScanError::InvalidAddress)?;
+        rest = &rest[end..];
     }
 
-    pub fn update_value(&mut self, address: u64, value_str: &str) -> Result<(), ScanError> {
-        let value = self.value_from_str(value_str)?;
-        write_memory_address(self.pid, address as usize, &value).map_err(ScanError::Memory)?;
-        Ok(())
-    }
+    Ok(address)
 }
 
-mod test {
-    #[allow(unused_imports)]
-    use crate::core::mem::write_memory_address;
+/// Addresses of a list with one per line, only the first column is read, so recorded
+/// edit scripts and copied watchlist tables can be imported as they are
+pub fn parse_address_list(content: &str, ranges: &[NamedRange]) -> Result<Vec<u64>, ScanError> {
+    let mut addresses = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let first_column = line
+            .trim()
+            .trim_start_matches('|')
+            .split(['\t', '|'])
+            .next()
+            .unwrap_or_default()
+            .trim();
+
+        // comments and the header and separator rows of tables
+        if first_column.is_empty()
+            || first_column.starts_with('#')
+            || first_column.starts_with("---")
+            || first_column.eq_ignore_ascii_case("address")
+        {
+            continue;
+        }
 
-    #[test]
-    #[ignore = "requires root"]
-    pub fn test_scan_creation_success() {
-        use super::*;
-        use std::process::{Command, Stdio};
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+        let address = evaluate_address(first_column, ranges)
+            .map_err(|_| ScanError::InvalidImportLine(i + 1))?;
+        addresses.push(address);
+    }
+    Ok(addresses)
+}
 
-        match proc {
-            Err(e) => assert!(false, "Error running simple program: {e}"),
-            Ok(child) => {
-                let proc = crate::core::utils::ChildGuard(child);
+/// Sorted addresses that are in every result set, e.g. values in memory shared by
+/// a parent process and its workers
+pub fn common_addresses(result_sets: &[&[ScanResult]]) -> Vec<u64> {
+    let Some((first, rest)) = result_sets.split_first() else {
+        return vec![];
+    };
+
+    let others: Vec<HashSet<u64>> = rest
+        .iter()
+        .map(|results| results.iter().map(|r| r.address).collect())
+        .collect();
+    let mut common: Vec<u64> = first
+        .iter()
+        .map(|r| r.address)
+        .filter(|address| others.iter().all(|set| set.contains(address)))
+        .collect();
+    common.sort_unstable();
+    common.dedup();
+    common
+}
 
-                let scan = Scan::new(
-                    proc.0.id(),
-                    31337_u32.to_le_bytes().to_vec(),
-                    ValueType::U32,
-                    None,
-                    None,
-                    None,
-                );
-                assert!(scan.is_ok());
-                let scan = scan.unwrap();
-                assert_eq!(scan.results.len(), 0);
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    pub address: u64,
+    pub value_type: ValueType,
+    pub perms: Vec<MemoryRegionPerms>,
+    pub value: Vec<u8>,
+    /// Value read before the last refresh/next scan, used to spot changed rows
+    pub previous_value: Vec<u8>,
+    /// Value captured by the first scan, kept while the results are narrowed down
+    pub first_value: Vec<u8>,
+    /// Bytes read by the last first or next scan, next scans compare with them
+    /// so refreshes in between don't move the baseline of changed/increased
+    pub scanned_value: Vec<u8>,
+    pub path: Option<String>,
+    /// Offset of the address in the file of its mapping, `None` unless it is file-backed
+    pub file_offset: Option<u64>,
+    /// Byte order override, set on watchlist entries that hold big-endian data
+    pub endianness: Endianness,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanError {
+    InvalidValue,
+    EmptyValue,
+    InvalidAddress,
+    AddressMismatch,
+    ReadSizeInvalid(usize, usize),
+    BlockSizeInvalid(usize, usize),
+    InvalidAlignment(usize),
+    Memory(MemoryError),
+    TypeMismatch,
+    NothingPinned,
+    UnmappedPointer(u64),
+    ProcessChanged(u32),
+    InvalidImportLine(usize),
+    NeedsPreviousScan,
+    SpillFile(String),
+    PauseFailed(i32),
+    AddressOutOfRange(u64),
+    UnknownModule(String),
+}
+impl std::fmt::Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidValue => write!(f, "Invalid scan value provided"),
+            Self::EmptyValue => write!(f, "Value is reqeuired to be set before scan"),
+            Self::InvalidAddress => {
+                write!(f, "Invalid address, use hex, d:decimal or module+offset")
+            }
+            Self::AddressMismatch => write!(f, "Start address should be smaller than end address"),
+            Self::TypeMismatch => write!(f, "Invalid type for value"),
+            Self::ReadSizeInvalid(min, max) => {
+                write!(f, "Read size should be in range {min}-{max}")
+            }
+            Self::BlockSizeInvalid(min, max) => {
+                write!(f, "Block size should be in range {min}-{max} bytes")
+            }
+            Self::InvalidAlignment(alignment) => {
+                write!(f, "Invalid alignment {alignment}, use 1, 2, 4 or 8 bytes")
+            }
+            Self::Memory(e) => write!(f, "{e}"),
+            Self::NothingPinned => write!(f, "Pin a result set before comparing scans"),
+            Self::UnmappedPointer(address) => {
+                write!(f, "0x{address:x} is not a mapped address in the target")
+            }
+            Self::InvalidImportLine(line) => {
+                write!(f, "Line {line} of the address list is not an address")
+            }
+            Self::NeedsPreviousScan => {
+                write!(
+                    f,
+                    "This comparison needs the values of a previous scan, use it for next scans"
+                )
+            }
+            Self::SpillFile(e) => write!(f, "Could not keep the snapshot on disk: {e}"),
+            Self::PauseFailed(e) => write!(f, "Could not pause the process (os error {e})"),
+            Self::UnknownModule(name) => write!(f, "No module named {name} is mapped"),
+            Self::AddressOutOfRange(address) => {
+                write!(
+                    f,
+                    "0x{address:x} is past the 32-bit address space of the target"
+                )
+            }
+            Self::ProcessChanged(pid) => write!(
+                f,
+                "Process {pid} exited or its pid was reused, nothing was written. Reattach from the process list"
+            ),
+        }
+    }
+}
+
+impl ScanResult {
+    pub fn new(
+        address: u64,
+        value_type: ValueType,
+        value: Vec<u8>,
+        perms: Vec<MemoryRegionPerms>,
+        path: Option<String>,
+    ) -> Self {
+        ScanResult {
+            address,
+            value_type,
+            perms,
+            previous_value: value.clone(),
+            first_value: value.clone(),
+            scanned_value: value.clone(),
+            value,
+            path,
+            file_offset: None,
+            endianness: Endianness::default(),
+        }
+    }
+
+    fn decode(&self, value: &[u8]) -> Vec<u8> {
+        self.endianness.convert(self.value_type, value)
+    }
+
+    pub fn get_string(&self) -> Result<String, ScanError> {
+        self.value_type
+            .get_value_string(&self.decode(&self.value))
+            .map_err(|_| ScanError::TypeMismatch)
+    }
+
+    pub fn get_display_string(&self, format: &DisplayFormat) -> Result<String, ScanError> {
+        self.value_type
+            .format_value(&self.decode(&self.value), format)
+            .map_err(|_| ScanError::TypeMismatch)
+    }
+
+    pub fn get_first_string(&self, format: &DisplayFormat) -> Result<String, ScanError> {
+        self.value_type
+            .format_value(&self.decode(&self.first_value), format)
+            .map_err(|_| ScanError::TypeMismatch)
+    }
+
+    /// Difference between the current and the first scan value of numeric results
+    pub fn get_drift(&self) -> Option<i128> {
+        Some(
+            self.value_type.get_i128(&self.decode(&self.value))?
+                - self.value_type.get_i128(&self.decode(&self.first_value))?,
+        )
+    }
+
+    pub fn has_changed(&self) -> bool {
+        self.value != self.previous_value
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        !self.perms.contains(&MemoryRegionPerms::Write)
+    }
+
+    /// Static addresses live in a file-backed mapping (main binary or a loaded module),
+    /// so they keep their module-relative offset across restarts of the target. Memfds,
+    /// device mappings and files deleted from disk have a path but can not be found again
+    pub fn is_static(&self) -> bool {
+        self.path.as_ref().is_some_and(|p| {
+            !p.starts_with('[')
+                && !p.starts_with("/memfd:")
+                && !p.starts_with("/dev/")
+                && !p.ends_with(" (deleted)")
+        })
+    }
+
+    /// File name of the mapping the result lives in, e.g. `libc.so.6` or `[heap]`
+    pub fn get_module_name(&self) -> Option<&str> {
+        self.path.as_deref().and_then(|p| p.rsplit('/').next())
+    }
+
+    /// Mapping the result lives in, file-backed ones with the offset in the file
+    /// (`libgame.so@0x1c010`), otherwise `[heap]`, `[stack]` or `anon`
+    pub fn get_location(&self) -> String {
+        let name = self.get_module_name().unwrap_or("anon");
+        match self.file_offset {
+            Some(offset) => format!("{name}@0x{offset:x}"),
+            None => name.to_owned(),
+        }
+    }
+}
+
+/// Which combination of the pinned (A) and current (B) result sets is shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultSetView {
+    Current,
+    Intersection,
+    OnlyPinned,
+    OnlyCurrent,
+}
+
+impl ResultSetView {
+    pub fn get_string(&self) -> &str {
+        match self {
+            ResultSetView::Current => "B",
+            ResultSetView::Intersection => "A∩B",
+            ResultSetView::OnlyPinned => "A-B",
+            ResultSetView::OnlyCurrent => "B-A",
+        }
+    }
+
+    pub fn next(&self) -> ResultSetView {
+        match self {
+            ResultSetView::Current => ResultSetView::Intersection,
+            ResultSetView::Intersection => ResultSetView::OnlyPinned,
+            ResultSetView::OnlyPinned => ResultSetView::OnlyCurrent,
+            ResultSetView::OnlyCurrent => ResultSetView::Current,
+        }
+    }
+}
+
+/// Coverage and throughput of the last first or next scan
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScanStats {
+    /// Size of all regions (or results on next scans) selected for the scan
+    pub total_bytes: u64,
+    /// Bytes that were actually read from the target
+    pub bytes_read: u64,
+    /// Bytes in pages that were swapped out when the scan started
+    pub swapped_bytes: u64,
+    pub regions_scanned: usize,
+    pub matches: usize,
+    pub elapsed: Duration,
+}
+
+impl ScanStats {
+    fn merge(self, other: ScanStats) -> ScanStats {
+        ScanStats {
+            total_bytes: self.total_bytes + other.total_bytes,
+            bytes_read: self.bytes_read + other.bytes_read,
+            swapped_bytes: self.swapped_bytes + other.swapped_bytes,
+            regions_scanned: self.regions_scanned + other.regions_scanned,
+            matches: self.matches + other.matches,
+            // regions are scanned in parallel
+            elapsed: self.elapsed.max(other.elapsed),
+        }
+    }
+
+    /// Bytes read per second
+    pub fn throughput(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.bytes_read as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Work done so far by a running first or next scan
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScanProgress {
+    /// Regions of a first scan, next scans only count bytes
+    pub regions_done: usize,
+    pub regions_total: usize,
+    pub bytes_scanned: u64,
+    pub bytes_total: u64,
+    /// Matches found in the work done so far
+    pub matches: usize,
+}
+
+impl ScanProgress {
+    /// Share of the bytes that were scanned, between 0 and 1
+    pub fn ratio(&self) -> f64 {
+        if self.bytes_total == 0 {
+            return 0.0;
+        }
+        (self.bytes_scanned as f64 / self.bytes_total as f64).min(1.0)
+    }
+}
+
+/// Progress is sent after every region and every time this many more bytes were scanned
+const PROGRESS_STEP: u64 = 1 << 20;
+
+/// Called from the scan threads with the progress of first and next scans
+pub struct ProgressCallback(Box<dyn Fn(ScanProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(ScanProgress) + Send + Sync + 'static) -> Self {
+        ProgressCallback(Box::new(callback))
+    }
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Custom comparison replacing the one of the compare mode, `bytes` are the scan value's size
+/// of memory (in target byte order) and `previous` the baseline of next scans, `None` on
+/// first scans
+pub trait ScanPredicate: Send + Sync {
+    fn matches(&self, bytes: &[u8], previous: Option<&[u8]>) -> bool;
+}
+
+impl std::fmt::Debug for dyn ScanPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ScanPredicate")
+    }
+}
+
+/// Adds up the work of the scan threads and reports it to the progress callback of the scan
+struct ProgressReporter<'a> {
+    callback: Option<&'a ProgressCallback>,
+    regions_total: usize,
+    bytes_total: u64,
+    regions_done: AtomicUsize,
+    bytes_scanned: AtomicU64,
+    matches: AtomicUsize,
+}
+
+impl<'a> ProgressReporter<'a> {
+    fn new(scan: &'a Scan, regions_total: usize, bytes_total: u64) -> Self {
+        ProgressReporter {
+            callback: scan.progress_callback.as_ref(),
+            regions_total,
+            bytes_total,
+            regions_done: AtomicUsize::new(0),
+            bytes_scanned: AtomicU64::new(0),
+            matches: AtomicUsize::new(0),
+        }
+    }
+
+    fn add(&self, regions: usize, bytes: u64, matches: usize) {
+        let Some(callback) = self.callback else {
+            return;
+        };
+        let regions_done = self
+            .regions_done
+            .fetch_add(regions, atomic::Ordering::Relaxed)
+            + regions;
+        let matches = self.matches.fetch_add(matches, atomic::Ordering::Relaxed) + matches;
+        let before = self
+            .bytes_scanned
+            .fetch_add(bytes, atomic::Ordering::Relaxed);
+        let bytes_scanned = before + bytes;
+        if regions == 0 && before / PROGRESS_STEP == bytes_scanned / PROGRESS_STEP {
+            return;
+        }
+
+        (callback.0)(ScanProgress {
+            regions_done,
+            regions_total: self.regions_total,
+            bytes_scanned,
+            bytes_total: self.bytes_total,
+            matches,
+        });
+    }
+}
+
+pub const DEFAULT_COMPARE_PERCENT: f64 = 10.0;
+
+/// Matches past this many are kept without their details, see `Scan::set_max_results`
+pub const DEFAULT_MAX_RESULTS: usize = 1_000_000;
+
+/// Truncated matches a next scan thread narrows down at a time
+const NARROW_CHUNK: usize = 4096;
+
+/// Pages whose state is asked for at once when splitting a region into the ranges to read
+const PAGE_STATE_CHUNK: u64 = 0x10000;
+
+/// Matches kept as parallel arrays instead of a `ScanResult` each, next scans only need
+/// their address and value and the region is looked up once a match becomes a result
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MatchStore {
+    /// Bytes per value, values cut short by the end of a region are zero padded
+    value_size: usize,
+    addresses: Vec<u64>,
+    values: Vec<u8>,
+    /// Values of the first scan, empty while they are the ones in `values`
+    first_values: Vec<u8>,
+    /// Index of the region of each match in the memory regions of the scan
+    regions: Vec<u32>,
+}
+
+impl MatchStore {
+    fn new(value_size: usize) -> Self {
+        MatchStore {
+            value_size,
+            ..Default::default()
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    fn value(&self, i: usize) -> &[u8] {
+        &self.values[i * self.value_size..(i + 1) * self.value_size]
+    }
+
+    fn first_value(&self, i: usize) -> &[u8] {
+        if self.first_values.is_empty() {
+            return self.value(i);
+        }
+        &self.first_values[i * self.value_size..(i + 1) * self.value_size]
+    }
+
+    /// The first value has to be passed for every match or for none
+    fn push(&mut self, address: u64, value: &[u8], first_value: Option<&[u8]>, region: u32) {
+        let pad = |values: &mut Vec<u8>, value: &[u8]| {
+            let end = values.len() + self.value_size;
+            values.extend_from_slice(&value[..value.len().min(self.value_size)]);
+            values.resize(end, 0);
+        };
+        self.addresses.push(address);
+        pad(&mut self.values, value);
+        if let Some(first_value) = first_value {
+            pad(&mut self.first_values, first_value);
+        }
+        self.regions.push(region);
+    }
+
+    fn append(&mut self, other: &mut MatchStore) {
+        self.addresses.append(&mut other.addresses);
+        self.values.append(&mut other.values);
+        self.first_values.append(&mut other.first_values);
+        self.regions.append(&mut other.regions);
+    }
+
+    /// Drops the matches that start inside the `len` bytes of the match kept before them,
+    /// the matches have to be in address order
+    fn without_overlaps(self, len: usize) -> MatchStore {
+        let mut kept = MatchStore::new(self.value_size);
+        let mut next_free = 0;
+        for i in 0..self.len() {
+            let address = self.addresses[i];
+            if address < next_free {
+                continue;
             }
+            let first_value = (!self.first_values.is_empty()).then(|| self.first_value(i));
+            kept.push(address, self.value(i), first_value, self.regions[i]);
+            next_free = address + len as u64;
+        }
+        kept
+    }
+
+    /// Keeps the first `at` matches and returns the rest
+    fn split_off(&mut self, at: usize) -> MatchStore {
+        let first_values = if self.first_values.is_empty() {
+            vec![]
+        } else {
+            self.first_values.split_off(at * self.value_size)
+        };
+        MatchStore {
+            value_size: self.value_size,
+            addresses: self.addresses.split_off(at),
+            values: self.values.split_off(at * self.value_size),
+            first_values,
+            regions: self.regions.split_off(at),
+        }
+    }
+}
+
+/// Unknown initial value scans larger than this write their snapshot to a temporary file
+pub const DEFAULT_MEMORY_BUDGET: u64 = 1 << 30;
+
+/// Memory read by an unknown initial value scan, the baseline of the next scan
+#[derive(Debug)]
+struct SnapshotBlock {
+    start: u64,
+    data: BlockData,
+}
+
+#[derive(Debug)]
+enum BlockData {
+    Memory(Vec<u8>),
+    /// Offset and length in the spill file of the snapshot
+    Spilled(usize, usize),
+}
+
+impl SnapshotBlock {
+    fn len(&self) -> usize {
+        match &self.data {
+            BlockData::Memory(data) => data.len(),
+            BlockData::Spilled(_, len) => *len,
+        }
+    }
+}
+
+/// Blocks of an unknown initial value scan, with the mapped spill file they were
+/// written to when the scan was larger than the memory budget
+#[derive(Debug, Default)]
+struct Snapshot {
+    blocks: Vec<SnapshotBlock>,
+    spill: Option<Mmap>,
+}
+
+impl Snapshot {
+    fn data<'a>(&'a self, block: &'a SnapshotBlock) -> &'a [u8] {
+        match &block.data {
+            BlockData::Memory(data) => data,
+            BlockData::Spilled(offset, len) => self
+                .spill
+                .as_deref()
+                .map_or(&[][..], |spill| &spill[*offset..*offset + *len]),
+        }
+    }
+}
+
+/// Temporary file snapshot blocks are appended to by the scan threads,
+/// it is removed right away and lives on until the mapping of it is dropped
+struct SpillFile {
+    file: Mutex<File>,
+}
+
+impl SpillFile {
+    fn create() -> io::Result<Self> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let path = std::env::temp_dir().join(format!(
+            "cheat-engine-rs-snapshot-{}-{time}",
+            std::process::id()
+        ));
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        // open files stay usable after removal on unix, elsewhere this fails and
+        // the file is left behind in the temp directory
+        let _ = fs::remove_file(&path);
+        Ok(SpillFile {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Appends `data`, a failed write keeps it in memory instead
+    fn write(&self, data: Vec<u8>) -> BlockData {
+        let Ok(mut file) = self.file.lock() else {
+            return BlockData::Memory(data);
+        };
+        let written = file
+            .stream_position()
+            .and_then(|offset| file.write_all(&data).map(|()| offset));
+        match written {
+            Ok(offset) => BlockData::Spilled(offset as usize, data.len()),
+            Err(_) => BlockData::Memory(data),
+        }
+    }
+
+    fn map(self) -> io::Result<Mmap> {
+        let file = self.file.into_inner().map_err(|_| io::ErrorKind::Other)?;
+        file.sync_data()?;
+        // SAFETY: the file was removed and nothing else writes to it once it is mapped
+        unsafe { Mmap::map(&file) }
+    }
+}
+
+#[derive(Debug)]
+pub struct Scan {
+    pub pid: u32,
+    pub value: Vec<u8>,
+    pub value_type: ValueType,
+    pub compare_mode: CompareMode,
+    pub results: Vec<ScanResult>,
+    pub watchlist: Vec<ScanResult>,
+    pub stats: ScanStats,
+    pub pinned: Option<Vec<ScanResult>>,
+    pub result_view: ResultSetView,
+    /// Results of the current scan while a combined view replaces them
+    unfiltered_results: Option<Vec<ScanResult>>,
+    /// Set by an unknown initial value scan until the next scan turns it into results
+    snapshot: Option<Snapshot>,
+    /// Snapshots larger than this are written to disk
+    memory_budget: u64,
+    /// Matches past `max_results`, next scans narrow them down with the results
+    truncated: MatchStore,
+    max_results: usize,
+    read_size: Option<usize>,
+    /// Bytes read at once by first scans
+    block_size: usize,
+    compare_percent: f64,
+    /// Tolerance of the rounded comparison, by default half a unit of the last typed decimal
+    compare_epsilon: Option<f64>,
+    /// Decimals the scan value was typed with
+    value_decimals: u32,
+    /// Bits of the scan value that have to match, empty unless it has `*` bits or fields
+    value_mask: Vec<u8>,
+    /// First scans only report addresses that are a multiple of this
+    alignment: usize,
+    string_terminator: StringTerminator,
+    /// Byte order numbers are searched and shown in
+    endianness: Endianness,
+    skip_swapped: bool,
+    skip_non_resident: bool,
+    /// First scans count the swapped bytes of the regions they read
+    count_swapped: bool,
+    exclude_shared_libs: bool,
+    exclude_shared_mappings: bool,
+    exclude_executable: bool,
+    /// First scans only read the heap, stacks and anonymous memory
+    heap_stack_only: bool,
+    skip_file_backed: bool,
+    /// First scans leave out values that are zero
+    skip_zeros: bool,
+    /// First scans only keep the first of overlapping matches, on by default for strings
+    skip_overlapping: bool,
+    /// The process is stopped while first and next scans read its memory
+    pause_target: bool,
+    start_address: Option<u64>,
+    end_address: Option<u64>,
+    /// Only the mappings of this module are scanned, the address range is set to its bounds
+    module: Option<String>,
+    /// Size of a pointer in the target, 32-bit targets refuse addresses above 4 GiB
+    pointer_width: usize,
+    memory_permissions: Vec<MemoryRegionPerms>,
+    memory_regions: Vec<MemoryRegion>,
+    /// Every readable or writable mapping of the process, `memory_regions` is selected
+    /// from it so option changes don't read the maps again until `reload_regions`
+    region_cache: Vec<MemoryRegion>,
+    regions_loaded: Instant,
+    /// Process the scan was attached to, checked before writing
+    identity: Option<ProcessIdentity>,
+    /// Gets the progress of first and next scans
+    progress_callback: Option<ProgressCallback>,
+    predicate: Option<Box<dyn ScanPredicate>>,
+    /// How the memory of the process is listed, read and written
+    memory: Box<dyn MemoryBackend>,
+}
+
+impl Scan {
+    /// Scans the process with the system calls of this machine, the front ends attach
+    /// through `with_backend` so the `backend` config option applies
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn new(
+        pid: u32,
+        value: Vec<u8>,
+        value_type: ValueType,
+        start_address: Option<u64>,
+        end_address: Option<u64>,
+        memory_permissions: Option<Vec<MemoryRegionPerms>>,
+    ) -> Result<Self, ScanError> {
+        Self::with_backend(
+            Box::new(NativeMemory),
+            pid,
+            value,
+            value_type,
+            start_address,
+            end_address,
+            memory_permissions,
+        )
+    }
+
+    /// Scans the process through `memory` instead of the system calls of this machine
+    pub fn with_backend(
+        memory: Box<dyn MemoryBackend>,
+        pid: u32,
+        value: Vec<u8>,
+        value_type: ValueType,
+        start_address: Option<u64>,
+        end_address: Option<u64>,
+        memory_permissions: Option<Vec<MemoryRegionPerms>>,
+    ) -> Result<Self, ScanError> {
+        let memory_permissions = memory_permissions.unwrap_or(DEFAULT_SEARCH_PERMS.to_vec());
+        let region_cache = get_all_regions(memory.as_ref(), pid)?;
+        let memory_regions = select_regions(
+            &region_cache,
+            start_address,
+            end_address,
+            &memory_permissions,
+        );
+
+        Ok(Scan {
+            pid,
+            read_size: None,
+            block_size: READ_BLOCK_SIZE,
+            value,
+            start_address,
+            end_address,
+            module: None,
+            pointer_width: memory.pointer_width(pid).unwrap_or(8),
+            memory_regions,
+            value_type,
+            compare_mode: CompareMode::Exact,
+            compare_percent: DEFAULT_COMPARE_PERCENT,
+            compare_epsilon: None,
+            value_decimals: 0,
+            value_mask: vec![],
+            alignment: value_type.get_default_alignment(),
+            string_terminator: StringTerminator::None,
+            endianness: Endianness::Little,
+            memory_permissions,
+            region_cache,
+            regions_loaded: Instant::now(),
+            results: vec![],
+            watchlist: vec![],
+            stats: ScanStats::default(),
+            pinned: None,
+            result_view: ResultSetView::Current,
+            unfiltered_results: None,
+            snapshot: None,
+            memory_budget: DEFAULT_MEMORY_BUDGET,
+            truncated: MatchStore::default(),
+            max_results: DEFAULT_MAX_RESULTS,
+            skip_swapped: false,
+            skip_non_resident: false,
+            count_swapped: false,
+            exclude_shared_libs: false,
+            exclude_shared_mappings: false,
+            exclude_executable: false,
+            heap_stack_only: false,
+            skip_file_backed: false,
+            skip_zeros: false,
+            skip_overlapping: value_type.is_variable_size(),
+            pause_target: false,
+            identity: get_process_identity(pid),
+            progress_callback: None,
+            predicate: None,
+            memory,
+        })
+    }
+
+    pub fn set_mem_permissions(
+        &mut self,
+        memory_permissions: Vec<MemoryRegionPerms>,
+    ) -> Result<(), ScanError> {
+        self.memory_permissions = memory_permissions;
+        self.update_memory_regions()?;
+        Ok(())
+    }
+
+    pub fn set_value_type(
+        &mut self,
+        value_type: ValueType,
+        value_str: Option<&str>,
+    ) -> Result<(), ScanError> {
+        self.value_type = value_type;
+        self.alignment = value_type.get_default_alignment();
+        self.skip_overlapping = value_type.is_variable_size();
+        self.value_mask.clear();
+        if let Some(value) = value_str {
+            self.set_value_from_str(value)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_alignment(&mut self, alignment: usize) -> Result<(), ScanError> {
+        if ![1, 2, 4, 8].contains(&alignment) {
+            return Err(ScanError::InvalidAlignment(alignment));
+        }
+        self.alignment = alignment;
+        Ok(())
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Parsed start and end address of the scan range
+    pub fn address_range(&self) -> (Option<u64>, Option<u64>) {
+        (self.start_address, self.end_address)
+    }
+
+    pub fn set_compare_mode(&mut self, mode: CompareMode) {
+        self.compare_mode = mode;
+    }
+
+    /// Following scans keep the values `predicate` matches instead of comparing them the
+    /// way the compare mode does, `None` goes back to the compare mode
+    #[allow(dead_code)]
+    pub fn set_predicate(&mut self, predicate: Option<Box<dyn ScanPredicate>>) {
+        self.predicate = predicate;
+    }
+
+    pub fn set_compare_percent(&mut self, percent: f64) -> Result<(), ScanError> {
+        if !percent.is_finite() || percent < 0.0 {
+            return Err(ScanError::InvalidValue);
+        }
+        self.compare_percent = percent;
+        Ok(())
+    }
+
+    /// Big-endian is for memory shared with or dumped from big-endian targets,
+    /// e.g. emulators and network buffers
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    pub fn set_string_terminator(&mut self, terminator: StringTerminator) {
+        self.string_terminator = terminator;
+    }
+
+    /// Fixed tolerance for the rounded comparison, `None` rounds to the typed decimals
+    pub fn set_compare_epsilon(&mut self, epsilon: Option<f64>) -> Result<(), ScanError> {
+        if let Some(epsilon) = epsilon
+            && (!epsilon.is_finite() || epsilon < 0.0)
+        {
+            return Err(ScanError::InvalidValue);
+        }
+        self.compare_epsilon = epsilon;
+        Ok(())
+    }
+
+    /// `callback` is called from the scan threads with the progress of the following
+    /// scans, `None` removes it. It has to be quick, the scan waits for it
+    pub fn set_progress_callback(&mut self, callback: Option<ProgressCallback>) {
+        self.progress_callback = callback;
+    }
+
+    /// Results kept with their details after a scan, the rest of the matches only keep
+    /// their address and value so huge first scans fit in memory
+    pub fn set_max_results(&mut self, max_results: usize) -> Result<(), ScanError> {
+        if max_results == 0 {
+            return Err(ScanError::InvalidValue);
+        }
+        self.max_results = max_results;
+        Ok(())
+    }
+
+    /// Unknown initial value scans of more memory than `bytes` keep their snapshot
+    /// in a temporary file instead of memory
+    pub fn set_memory_budget(&mut self, bytes: u64) {
+        self.memory_budget = bytes;
+    }
+
+    /// Matches of the last scan past the result cap, they are not in `results`
+    pub fn truncated_count(&self) -> usize {
+        self.truncated.len()
+    }
+
+    /// All matches of the last scan, shown results and truncated ones
+    pub fn match_count(&self) -> usize {
+        self.results.len() + self.truncated.len()
+    }
+
+    /// Regions a first scan reads and a reporter for their progress
+    fn get_regions_to_scan(
+        &self,
+        resident_regions: &Option<HashSet<u64>>,
+    ) -> (Vec<(u32, &MemoryRegion)>, ProgressReporter<'_>) {
+        let regions: Vec<(u32, &MemoryRegion)> = self
+            .memory_regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| self.should_scan_region(region, resident_regions))
+            .map(|(i, region)| (i as u32, region))
+            .collect();
+        let bytes_total = regions.iter().map(|(_, r)| r.end - r.start).sum();
+        let progress = ProgressReporter::new(self, regions.len(), bytes_total);
+        (regions, progress)
+    }
+
+    /// Swapped out pages are read (and so swapped back in) by default,
+    /// skipping them keeps the scan from touching swap at the cost of missing their data
+    pub fn set_skip_swapped(&mut self, skip: bool) {
+        self.skip_swapped = skip;
+    }
+
+    pub fn skip_swapped(&self) -> bool {
+        self.skip_swapped
+    }
+
+    /// Regions without a single resident page are left out of first scans and, where
+    /// pagemap is readable, so are the pages of the other regions that are not in memory
+    pub fn set_skip_non_resident(&mut self, skip: bool) {
+        self.skip_non_resident = skip;
+    }
+
+    /// Fills `swapped_bytes` of the stats even when swapped pages are read, which takes
+    /// the page table of every region a first scan reads
+    pub fn set_count_swapped(&mut self, count: bool) {
+        self.count_swapped = count;
+    }
+
+    /// Leaves mappings of shared libraries out of first scans,
+    /// the main binary, heap, stacks and anonymous memory are still scanned
+    pub fn set_exclude_shared_libs(&mut self, exclude: bool) {
+        self.exclude_shared_libs = exclude;
+    }
+
+    /// Leaves mappings shared with other processes out of first scans, mostly shared
+    /// memory and framebuffers that are large and rarely hold the state of the target
+    pub fn set_exclude_shared_mappings(&mut self, exclude: bool) {
+        self.exclude_shared_mappings = exclude;
+    }
+
+    /// Restricts first scans to the heap, stacks and anonymous mappings, game state
+    /// almost never lives in the mappings of files
+    pub fn set_heap_stack_only(&mut self, only: bool) {
+        self.heap_stack_only = only;
+    }
+
+    /// Leaves every mapping of a file out of first scans, the main binary and libraries
+    /// included, so only the heap, stacks and anonymous or pseudo mappings are read
+    pub fn set_skip_file_backed(&mut self, skip: bool) {
+        self.skip_file_backed = skip;
+    }
+
+    /// Leaves executable mappings out of first scans, code rarely holds the values
+    /// searched for while scanning data
+    pub fn set_exclude_executable(&mut self, exclude: bool) {
+        self.exclude_executable = exclude;
+    }
+
+    /// Zeroed memory dominates small integer and unknown value scans, skipping values
+    /// that are zero as a number keeps it out of first scan results
+    pub fn set_skip_zeros(&mut self, skip: bool) {
+        self.skip_zeros = skip;
+    }
+
+    /// Searching "aaa" in "aaaaa" matches three times, skipping overlapping matches only
+    /// keeps the first. Changing the value type goes back to its default
+    pub fn set_skip_overlapping(&mut self, skip: bool) {
+        self.skip_overlapping = skip;
+    }
+
+    pub fn skip_overlapping(&self) -> bool {
+        self.skip_overlapping
+    }
+
+    /// Stops the process for the duration of first and next scans, so values that change
+    /// quickly can't move between the start and the end of a scan
+    pub fn set_pause_target(&mut self, pause: bool) {
+        self.pause_target = pause;
+    }
+
+    /// Guard keeping the process stopped during a scan when pausing is enabled
+    fn pause_target(&self) -> Result<Option<PausedProcess>, ScanError> {
+        if !self.pause_target {
+            return Ok(None);
+        }
+        pause_process(self.pid)
+            .map(Some)
+            .map_err(ScanError::PauseFailed)
+    }
+
+    fn should_scan_region(&self, region: &MemoryRegion, resident: &Option<HashSet<u64>>) -> bool {
+        if self.exclude_shared_libs && region.is_shared_library() {
+            return false;
+        }
+        if self.exclude_shared_mappings && region.shared {
+            return false;
+        }
+        if self.heap_stack_only && !region.is_heap_or_stack() {
+            return false;
+        }
+        if self.skip_file_backed && region.is_file_backed() {
+            return false;
+        }
+        if self.exclude_executable && region.perms.contains(&MemoryRegionPerms::Execute) {
+            return false;
+        }
+
+        resident
+            .as_ref()
+            .is_none_or(|resident| resident.contains(&region.start))
+    }
+
+    /// Start addresses of the regions that have resident pages,
+    /// `None` when every region should be scanned
+    fn get_resident_regions(&self) -> Option<HashSet<u64>> {
+        if !self.skip_non_resident {
+            return None;
+        }
+
+        let regions = self.memory.region_stats(self.pid).ok()?;
+        Some(
+            regions
+                .into_iter()
+                .filter(|(_, stats)| stats.rss > 0)
+                .map(|(region, _)| region.start)
+                .collect(),
+        )
+    }
+
+    pub fn set_read_size(&mut self, size: Option<usize>) -> Result<(), ScanError> {
+        const MAX_READ_SIZE: usize = 256;
+        const MIN_READ_SIZE: usize = 1;
+
+        if let Some(size) = size {
+            if !(MIN_READ_SIZE..=MAX_READ_SIZE).contains(&size) {
+                return Err(ScanError::ReadSizeInvalid(MIN_READ_SIZE, MAX_READ_SIZE));
+            }
+            self.read_size = Some(size);
+        } else {
+            self.read_size = None;
+        }
+        Ok(())
+    }
+
+    /// Larger blocks need fewer reads, smaller ones lose less when part of a block
+    /// can't be read, which happens more often with the fragmented maps of macOS
+    pub fn set_block_size(&mut self, size: usize) -> Result<(), ScanError> {
+        const MIN_BLOCK_SIZE: usize = 0x1000;
+        const MAX_BLOCK_SIZE: usize = 0x4000000;
+
+        if !(MIN_BLOCK_SIZE..=MAX_BLOCK_SIZE).contains(&size) {
+            return Err(ScanError::BlockSizeInvalid(MIN_BLOCK_SIZE, MAX_BLOCK_SIZE));
+        }
+        self.block_size = size;
+        Ok(())
+    }
+
+    /// Numeric types take a `,` separated list to scan for an array of contiguous values
+    pub fn value_from_str(&self, value_str: &str) -> Result<Vec<u8>, ScanError> {
+        self.value_type.parse_value(value_str)
+    }
+
+    /// Pointers written to the target have to be null or point into one of its mappings
+    fn check_pointer(&self, value_type: ValueType, value: &[u8]) -> Result<(), ScanError> {
+        if !matches!(value_type, ValueType::Pointer | ValueType::Pointer32) {
+            return Ok(());
+        }
+
+        let address = value_type
+            .get_pointer(value)
+            .ok_or(ScanError::TypeMismatch)?;
+        if address == 0 {
+            return Ok(());
+        }
+
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let regions = self
+            .memory
+            .regions(self.pid, Some(address), Some(address), Some(&perms))
+            .map_err(ScanError::Memory)?;
+        if regions
+            .iter()
+            .any(|r| r.start <= address && address < r.end)
+        {
+            Ok(())
+        } else {
+            Err(ScanError::UnmappedPointer(address))
+        }
+    }
+
+    pub fn set_value_from_str(&mut self, value_str: &str) -> Result<(), ScanError> {
+        self.value = self.value_from_str(value_str)?;
+        self.value_decimals = count_decimals(value_str);
+        self.value_mask = self.value_type.parse_mask(value_str)?;
+
+        Ok(())
+    }
+
+    /// Number or `module+offset` expression, see `evaluate_address`
+    pub fn resolve_address(&self, address: &str) -> Result<u64, ScanError> {
+        self.parse_address(address.trim())?
+            .ok_or(ScanError::InvalidAddress)
+    }
+
+    /// Reads memory of the process through the backend of the scan
+    pub fn read_memory(&self, address: u64, size: usize) -> Result<Vec<u8>, ScanError> {
+        self.memory
+            .read(self.pid, address as usize, size)
+            .map_err(ScanError::Memory)
+    }
+
+    fn parse_address(&self, address: &str) -> Result<Option<u64>, ScanError> {
+        if address.is_empty() {
+            return Ok(None);
+        }
+
+        // the module list is only needed for expressions
+        let parsed_addr = match parse_address_number(address) {
+            Ok(parsed_addr) => parsed_addr,
+            Err(_) => {
+                let ranges = self
+                    .memory
+                    .named_ranges(self.pid)
+                    .map_err(ScanError::Memory)?;
+                evaluate_address(address, &ranges)?
+            }
+        };
+        self.check_address(parsed_addr).map(Some)
+    }
+
+    /// Size of a pointer in the target in bytes
+    pub fn pointer_width(&self) -> usize {
+        self.pointer_width
+    }
+
+    /// The heap, the main stack and the range of every loaded module of the target
+    pub fn named_ranges(&self) -> Result<Vec<NamedRange>, ScanError> {
+        self.memory
+            .named_ranges(self.pid)
+            .map_err(ScanError::Memory)
+    }
+
+    /// Refuses addresses a 32-bit target can not have
+    fn check_address(&self, address: u64) -> Result<u64, ScanError> {
+        // the end bound is exclusive, so the top of the address space itself is fine
+        if self.pointer_width == 4 && address > 1 << 32 {
+            return Err(ScanError::AddressOutOfRange(address));
+        }
+        Ok(address)
+    }
+
+    /// Sets both bounds at once, so a new range can lie entirely outside the old one.
+    /// Like the start and end address setters it ends a `set_module` restriction
+    pub fn set_address_range(
+        &mut self,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<(), ScanError> {
+        if let (Some(start), Some(end)) = (start, end)
+            && start > end
+        {
+            return Err(ScanError::AddressMismatch);
+        }
+        for address in [start, end].into_iter().flatten() {
+            self.check_address(address)?;
+        }
+
+        self.module = None;
+        self.start_address = start;
+        self.end_address = end;
+        self.update_memory_regions()
+    }
+
+    fn update_memory_regions(&mut self) -> Result<(), ScanError> {
+        self.memory_regions = select_regions(
+            &self.region_cache,
+            self.start_address,
+            self.end_address,
+            &self.memory_permissions,
+        );
+        if let Some(module) = &self.module {
+            self.memory_regions
+                .retain(|region| region.module_name() == Some(module.as_str()));
+        }
+        Ok(())
+    }
+
+    /// Limits the scan to the mappings of the module `name` (`libgame.so`), leaving out
+    /// whatever else is mapped between them. Sets and returns the bounds of the module as
+    /// the address range, setting another range ends the restriction
+    pub fn set_module(&mut self, name: &str) -> Result<(u64, u64), ScanError> {
+        let (start, end) = self
+            .region_cache
+            .iter()
+            .filter(|region| region.module_name() == Some(name))
+            .fold(None, |bounds: Option<(u64, u64)>, region| match bounds {
+                Some((start, end)) => Some((start.min(region.start), end.max(region.end))),
+                None => Some((region.start, region.end)),
+            })
+            .ok_or_else(|| ScanError::UnknownModule(name.to_owned()))?;
+
+        self.module = Some(name.to_owned());
+        self.start_address = Some(start);
+        self.end_address = Some(end);
+        self.update_memory_regions()?;
+        Ok((start, end))
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Backing path of the mapping `address` is in, as of the last region reload
+    pub fn region_path(&self, address: u64) -> Option<String> {
+        self.region_cache
+            .iter()
+            .find(|region| region.start <= address && address < region.end)
+            .and_then(|region| region.path.clone())
+    }
+
+    /// Reads the memory maps of the process again, they are cached since the scan was
+    /// created or last reloaded. Returns how many regions the scan now covers
+    pub fn reload_regions(&mut self) -> Result<usize, ScanError> {
+        self.region_cache = get_all_regions(self.memory.as_ref(), self.pid)?;
+        self.regions_loaded = Instant::now();
+        self.update_memory_regions()?;
+        Ok(self.memory_regions.len())
+    }
+
+    /// Time since the memory maps were read
+    pub fn regions_age(&self) -> Duration {
+        self.regions_loaded.elapsed()
+    }
+
+    pub fn set_start_address(&mut self, address: &str) -> Result<(), ScanError> {
+        let parsed_addr = self.parse_address(address)?;
+
+        if let (Some(start), Some(end)) = (parsed_addr, self.end_address)
+            && start > end
+        {
+            return Err(ScanError::AddressMismatch);
+        }
+
+        self.module = None;
+        self.start_address = parsed_addr;
+        self.update_memory_regions()?;
+
+        Ok(())
+    }
+
+    pub fn set_end_address(&mut self, address: &str) -> Result<(), ScanError> {
+        let parsed_addr = self.parse_address(address)?;
+
+        if let (Some(start), Some(end)) = (self.start_address, parsed_addr)
+            && end < start
+        {
+            return Err(ScanError::AddressMismatch);
+        }
+
+        self.module = None;
+        self.end_address = parsed_addr;
+        self.update_memory_regions()?;
+
+        Ok(())
+    }
+
+    /// Splits the region into the address ranges that should be read,
+    /// leaving out swapped pages when `skip_swapped` is set and pages that are not
+    /// present when `skip_non_resident` is
+    fn get_scan_ranges(&self, region: &MemoryRegion, stats: &mut ScanStats) -> Vec<(usize, usize)> {
+        let whole_region = vec![(region.start as usize, region.end as usize)];
+        if !self.skip_swapped && !self.skip_non_resident && !self.count_swapped {
+            return whole_region;
+        }
+
+        let page_size = get_page_size();
+        let first_page = region.start / page_size * page_size;
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut push_range = |start: u64, end: u64| match ranges.last_mut() {
+            Some(last) if last.1 == start as usize => last.1 = end as usize,
+            _ => ranges.push((start as usize, end as usize)),
+        };
+
+        // a chunk of the page table at a time, huge mappings would need megabytes of it
+        let mut chunk_start = first_page;
+        while chunk_start < region.end {
+            let chunk_end = (chunk_start + PAGE_STATE_CHUNK * page_size).min(region.end);
+            let states =
+                match self
+                    .memory
+                    .page_states(self.pid, chunk_start.max(region.start), chunk_end)
+                {
+                    Ok(states) => states,
+                    // without pagemap access nothing is known about swapped pages
+                    Err(_) if chunk_start == first_page => return whole_region,
+                    Err(_) => {
+                        push_range(chunk_start, chunk_end);
+                        chunk_start = chunk_end;
+                        continue;
+                    }
+                };
+
+            for (i, state) in states.iter().enumerate() {
+                let page_start = (chunk_start + i as u64 * page_size).max(region.start);
+                let page_end = (chunk_start + (i as u64 + 1) * page_size).min(region.end);
+
+                if *state == PageState::Swapped {
+                    stats.swapped_bytes += page_end - page_start;
+                    if self.skip_swapped {
+                        continue;
+                    }
+                }
+                // never touched anonymous pages read as zeros, evicted file pages would be
+                // faulted back in just to be scanned
+                if *state == PageState::NotPresent && self.skip_non_resident {
+                    continue;
+                }
+                push_range(page_start, page_end);
+            }
+            chunk_start = chunk_end;
+        }
+
+        ranges
+    }
+
+    /// Blocks to read from the region as (address, bytes to read, bytes not shared with
+    /// the next block), blocks overlap so values of `size` bytes are never split
+    fn get_read_blocks(
+        &self,
+        region: &MemoryRegion,
+        size: usize,
+        stats: &mut ScanStats,
+    ) -> Vec<(usize, usize, usize)> {
+        let mut blocks = Vec::new();
+        for (range_start, range_end) in self.get_scan_ranges(region, stats) {
+            let mut current_address = range_start;
+            while current_address < range_end {
+                let to_read = std::cmp::min(self.block_size, range_end - current_address);
+                if to_read < size {
+                    break;
+                }
+                let advance = if current_address + to_read < range_end {
+                    to_read - (size - 1)
+                } else {
+                    to_read
+                };
+                blocks.push((current_address, to_read, advance));
+                current_address += advance;
+            }
+        }
+        blocks
+    }
+
+    fn scan_region(
+        &self,
+        region_index: u32,
+        region: &MemoryRegion,
+    ) -> Result<(MatchStore, ScanStats), MemoryError> {
+        let start = region.start as usize;
+        let size = self.read_size.unwrap_or(self.match_size());
+
+        // Validate region with a single byte read to catch ProcessAttach errors early
+        if let Err(e) = self.memory.read(self.pid, start, 1)
+            && let MemoryError::ProcessAttach(_) = e
+        {
+            return Err(e);
+        }
+
+        let mut stats = ScanStats {
+            total_bytes: region.end - region.start,
+            regions_scanned: 1,
+            ..Default::default()
+        };
+
+        let blocks = self.get_read_blocks(region, size, &mut stats);
+
+        // the scan value is kept little-endian
+        let needle = self.endianness.convert(self.value_type, &self.value);
+
+        // Parallel scan of all blocks
+        let results: Vec<(MatchStore, usize)> = blocks
+            .par_iter()
+            .map(|&(current_address, to_read, advance)| {
+                match self.memory.read(self.pid, current_address, to_read) {
+                    Ok(val) => (
+                        self.scan_block(region_index, current_address, &val, &needle, size),
+                        advance,
+                    ),
+                    // one unreadable page fails the whole block, its other pages are still
+                    // worth scanning
+                    Err(_) => self.scan_block_pages(
+                        region_index,
+                        (current_address, to_read, advance),
+                        &needle,
+                        size,
+                    ),
+                }
+            })
+            .collect();
+
+        // Flatten results
+        let mut region_results = MatchStore::new(size);
+        for (mut block_results, bytes_read) in results {
+            stats.bytes_read += bytes_read as u64;
+            region_results.append(&mut block_results);
+        }
+        if self.skip_overlapping {
+            region_results = region_results.without_overlaps(self.match_size());
+        }
+        stats.matches = region_results.len();
+
+        Ok((region_results, stats))
+    }
+
+    /// Matches in `data` read from `address`, every one keeps `size` bytes of it
+    fn scan_block(
+        &self,
+        region_index: u32,
+        address: usize,
+        data: &[u8],
+        needle: &[u8],
+        size: usize,
+    ) -> MatchStore {
+        // unaligned exact scans use the fast substring search, numbers aligned
+        // to their size a word wise search and everything else only looks
+        // at the aligned offsets
+        let aligned_offsets =
+            || aligned_offsets(address, data.len(), self.match_size(), self.alignment);
+        let exact = self.predicate.is_none() && self.value_mask.is_empty();
+        let offsets: Vec<usize> = match self.compare_mode {
+            CompareMode::Exact if exact && self.alignment == 1 => {
+                memmem::find_iter(data, needle).collect()
+            }
+            CompareMode::Exact
+                if exact && self.alignment == needle.len() && matches!(needle.len(), 2 | 4 | 8) =>
+            {
+                find_aligned(data, address, needle)
+            }
+            CompareMode::Exact if exact => aligned_offsets()
+                .filter(|&i| data[i..i + needle.len()] == *needle)
+                .collect(),
+            _ => aligned_offsets()
+                .filter(|&i| self.value_matches(&data[i..]))
+                .collect(),
+        };
+        let mut matches = MatchStore::new(size);
+        for i in offsets {
+            if self.is_skipped_zero(&data[i..]) {
+                continue;
+            }
+            // Take all available data from position i, up to size bytes
+            let end_offset = std::cmp::min(i + size, data.len());
+            matches.push(
+                (address + i) as u64,
+                &data[i..end_offset],
+                None,
+                region_index,
+            );
+        }
+        matches
+    }
+
+    /// Scans a block that could not be read at once page by page, runs of readable pages
+    /// are scanned together so only values touching an unreadable page are lost.
+    /// Returns the matches and how many bytes of the block's advance were read
+    fn scan_block_pages(
+        &self,
+        region_index: u32,
+        (address, to_read, advance): (usize, usize, usize),
+        needle: &[u8],
+        size: usize,
+    ) -> (MatchStore, usize) {
+        let page_size = get_page_size() as usize;
+        let end = address + to_read;
+        let mut matches = MatchStore::new(size);
+        let mut bytes_read = 0;
+        let mut run_start = address;
+        let mut run = Vec::new();
+        let mut page = address;
+        while page < end {
+            let page_end = ((page / page_size + 1) * page_size).min(end);
+            if let Ok(data) = self.memory.read(self.pid, page, page_end - page) {
+                bytes_read += page_end.min(address + advance).saturating_sub(page);
+                run.extend(data);
+            } else {
+                let mut run_matches = self.scan_block(region_index, run_start, &run, needle, size);
+                matches.append(&mut run_matches);
+                run.clear();
+                run_start = page_end;
+            }
+            page = page_end;
+        }
+        let mut run_matches = self.scan_block(region_index, run_start, &run, needle, size);
+        matches.append(&mut run_matches);
+        (matches, bytes_read)
+    }
+
+    fn snapshot_region(
+        &self,
+        region: &MemoryRegion,
+        spill: Option<&SpillFile>,
+    ) -> Result<(Vec<SnapshotBlock>, ScanStats), MemoryError> {
+        let size = self.value_type.get_size() as usize;
+
+        if let Err(e) = self.memory.read(self.pid, region.start as usize, 1)
+            && let MemoryError::ProcessAttach(_) = e
+        {
+            return Err(e);
+        }
+
+        let mut stats = ScanStats {
+            total_bytes: region.end - region.start,
+            regions_scanned: 1,
+            ..Default::default()
+        };
+        // candidates are counted before the data is written to disk
+        let blocks: Vec<(SnapshotBlock, usize)> = self
+            .get_read_blocks(region, size, &mut stats)
+            .into_par_iter()
+            .filter_map(|(address, to_read, _)| {
+                let data = self.memory.read(self.pid, address, to_read).ok()?;
+                let candidates = self.get_candidate_offsets(address as u64, &data).count();
+                let block = SnapshotBlock {
+                    start: address as u64,
+                    data: match spill {
+                        Some(spill) => spill.write(data),
+                        None => BlockData::Memory(data),
+                    },
+                };
+                Some((block, candidates))
+            })
+            .collect();
+
+        stats.bytes_read = blocks.iter().map(|(b, _)| b.len() as u64).sum();
+        stats.matches = blocks.iter().map(|(_, candidates)| candidates).sum();
+        Ok((blocks.into_iter().map(|(block, _)| block).collect(), stats))
+    }
+
+    /// Aligned offsets of snapshot data read from `start` that a whole value fits behind
+    fn get_candidate_offsets<'a>(
+        &'a self,
+        start: u64,
+        data: &'a [u8],
+    ) -> impl Iterator<Item = usize> + 'a {
+        aligned_offsets(
+            start as usize,
+            data.len(),
+            self.value_type.get_size() as usize,
+            self.alignment,
+        )
+        .filter(|&i| !self.is_skipped_zero(&data[i..]))
+    }
+
+    /// True for values that are zero as a number while zeros are skipped,
+    /// only the first value of a larger read counts
+    fn is_skipped_zero(&self, value: &[u8]) -> bool {
+        let size = self.value_type.get_size() as usize;
+        self.skip_zeros
+            && value
+                .get(..size)
+                .and_then(|value| self.value_type.get_f64(value))
+                == Some(0.0)
+    }
+
+    fn check_value(&self) -> Result<(), ScanError> {
+        if !self.compare_mode.needs_value() {
+            if self.compare_mode.is_numeric_only() && self.value_type.is_variable_size() {
+                return Err(ScanError::TypeMismatch);
+            }
+            return Ok(());
+        }
+        if self.value.is_empty() {
+            return Err(ScanError::EmptyValue);
+        }
+
+        self.value_type
+            .get_value_string(&self.value)
+            .map_err(|_| ScanError::TypeMismatch)?;
+
+        if self.compare_mode == CompareMode::AnyOf && self.value_type.is_variable_size() {
+            return Err(ScanError::TypeMismatch);
+        }
+        if self.compare_mode.is_numeric_only() && self.value_type.get_f64(&self.value).is_none() {
+            return Err(ScanError::TypeMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Bytes of memory compared with the scan value, one element for `AnyOf`
+    fn match_size(&self) -> usize {
+        match self.compare_mode {
+            CompareMode::AnyOf => self.value_type.get_size() as usize,
+            _ => self.value.len(),
+        }
+    }
+
+    /// Checks a value read from memory against the scan value,
+    /// only the first `value.len()` bytes count when a larger read size is used
+    fn value_matches(&self, value: &[u8]) -> bool {
+        let Some(value) = value.get(..self.match_size()) else {
+            return false;
+        };
+        if let Some(predicate) = &self.predicate {
+            return predicate.matches(value, None);
+        }
+        let decoded;
+        let value = match self.endianness {
+            Endianness::Little => value,
+            Endianness::Big => {
+                decoded = self.endianness.convert(self.value_type, value);
+                &decoded
+            }
+        };
+
+        match self.compare_mode {
+            CompareMode::Exact => masked_equal(value, &self.value, &self.value_mask),
+            CompareMode::NotEqual => !masked_equal(value, &self.value, &self.value_mask),
+            CompareMode::AnyOf => {
+                let len = value.len();
+                self.value.chunks(len).enumerate().any(|(i, v)| {
+                    let mask = self.value_mask.get(i * len..(i + 1) * len).unwrap_or(&[]);
+                    masked_equal(value, v, mask)
+                })
+            }
+            // first scans have nothing to compare with
+            CompareMode::Changed
+            | CompareMode::Unchanged
+            | CompareMode::SameAsFirst
+            | CompareMode::Increased
+            | CompareMode::Decreased
+            | CompareMode::IncreasedBy
+            | CompareMode::DecreasedBy => false,
+            CompareMode::WithinPercent => {
+                let (Some(target), Some(current)) = (
+                    self.value_type.get_f64(&self.value),
+                    self.value_type.get_f64(value),
+                ) else {
+                    return false;
+                };
+                (current - target).abs() <= target.abs() * self.compare_percent / 100.0
+            }
+            CompareMode::Rounded => {
+                let (Some(target), Some(current)) = (
+                    self.value_type.get_f64(&self.value),
+                    self.value_type.get_f64(value),
+                ) else {
+                    return false;
+                };
+                let epsilon = self
+                    .compare_epsilon
+                    .unwrap_or(0.5 / 10_f64.powi(self.value_decimals as i32));
+                (current - target).abs() <= epsilon
+            }
+        }
+    }
+
+    /// Value a next scan compares with, the one read by the previous scan or,
+    /// for "same as first", by the first scan
+    fn baseline<'a>(&self, scanned: &'a [u8], first: &'a [u8]) -> &'a [u8] {
+        match self.compare_mode {
+            CompareMode::SameAsFirst => first,
+            _ => scanned,
+        }
+    }
+
+    /// Checks a value read by a next scan, `previous` is its baseline
+    fn value_matches_previous(&self, value: &[u8], previous: &[u8]) -> bool {
+        if let Some(predicate) = &self.predicate {
+            return predicate.matches(value, Some(previous));
+        }
+        match self.compare_mode {
+            CompareMode::Changed => value != previous,
+            // the previous value is the first one, see `baseline`
+            CompareMode::Unchanged | CompareMode::SameAsFirst => value == previous,
+            CompareMode::Increased => {
+                self.compare_numbers(value, previous) == Some(Ordering::Greater)
+            }
+            CompareMode::Decreased => self.compare_numbers(value, previous) == Some(Ordering::Less),
+            CompareMode::IncreasedBy => self.changed_by(value, previous, 1),
+            CompareMode::DecreasedBy => self.changed_by(value, previous, -1),
+            CompareMode::Exact
+            | CompareMode::NotEqual
+            | CompareMode::AnyOf
+            | CompareMode::WithinPercent
+            | CompareMode::Rounded => self.value_matches(value),
+        }
+    }
+
+    /// True when the value moved by exactly the scan value in the direction of `sign`,
+    /// floats redo the addition of the target in its precision as differences are inexact
+    fn changed_by(&self, value: &[u8], previous: &[u8], sign: i8) -> bool {
+        let value_type = self.value_type;
+        let value = self.endianness.convert(value_type, value);
+        let previous = self.endianness.convert(value_type, previous);
+
+        if let (Some(value), Some(previous), Some(delta)) = (
+            value_type.get_i128(&value),
+            value_type.get_i128(&previous),
+            value_type.get_i128(&self.value),
+        ) {
+            return value.checked_sub(previous) == delta.checked_mul(sign as i128);
+        }
+
+        let (Some(value), Some(previous), Some(delta)) = (
+            value_type.get_f64(&value),
+            value_type.get_f64(&previous),
+            value_type.get_f64(&self.value),
+        ) else {
+            return false;
+        };
+        match value_type {
+            ValueType::F32 => previous as f32 + sign as f32 * delta as f32 == value as f32,
+            _ => previous + sign as f64 * delta == value,
+        }
+    }
+
+    /// Orders two values read from memory as numbers of the scan type,
+    /// integers are compared exactly and floats as f64
+    fn compare_numbers(&self, value: &[u8], previous: &[u8]) -> Option<Ordering> {
+        let value = self.endianness.convert(self.value_type, value);
+        let previous = self.endianness.convert(self.value_type, previous);
+        match (
+            self.value_type.get_i128(&value),
+            self.value_type.get_i128(&previous),
+        ) {
+            (Some(value), Some(previous)) => Some(value.cmp(&previous)),
+            _ => self
+                .value_type
+                .get_f64(&value)?
+                .partial_cmp(&self.value_type.get_f64(&previous)?),
+        }
+    }
+
+    fn refresh_watchlist(&mut self) -> Result<(), ScanError> {
+        self.check_value()?;
+
+        if self.watchlist.is_empty() {
+            return Ok(());
+        }
+
+        // Early validation with single read to catch ProcessAttach errors
+        if let Some(first) = self.watchlist.first() {
+            let read_size = self.read_size.unwrap_or(first.value.len());
+            if let Err(e) = self
+                .memory
+                .read(self.pid, first.address as usize, read_size)
+                && let MemoryError::ProcessAttach(_) = e
+            {
+                return Err(ScanError::Memory(e));
+            }
+        }
+
+        // Parallel refresh
+        let updated_watchlist: Vec<ScanResult> = self
+            .watchlist
+            .par_iter()
+            .filter_map(|result| {
+                // entries of another type than the scan keep the size they were added with
+                let read_size = match self.read_size {
+                    Some(read_size) if result.value_type == self.value_type => read_size,
+                    _ => result.value.len(),
+                };
+                match self
+                    .memory
+                    .read(self.pid, result.address as usize, read_size)
+                {
+                    Err(_) => None, // Ignore errors during parallel scan
+                    Ok(val) => {
+                        let mut updated = result.clone();
+                        updated.previous_value = std::mem::replace(&mut updated.value, val);
+                        Some(updated)
+                    }
+                }
+            })
+            .collect();
+
+        self.watchlist = updated_watchlist;
+        Ok(())
+    }
+
+    pub fn init(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
+        self.check_value()?;
+        if self.predicate.is_none() && self.compare_mode.needs_previous() {
+            return Err(ScanError::NeedsPreviousScan);
+        }
+
+        let _paused = self.pause_target()?;
+        self.reset_result_view();
+        self.snapshot = None;
+        let started = Instant::now();
+        let resident_regions = self.get_resident_regions();
+
+        // Parallel scan across memory regions
+        let (regions, progress) = self.get_regions_to_scan(&resident_regions);
+        let results: Result<Vec<(MatchStore, ScanStats)>, MemoryError> = regions
+            .par_iter()
+            .map(|&(i, region)| {
+                let scanned = self.scan_region(i, region);
+                let matches = scanned.as_ref().map_or(0, |(matches, _)| matches.len());
+                progress.add(1, region.end - region.start, matches);
+                scanned
+            })
+            .collect();
+
+        let results = results.map_err(ScanError::Memory)?;
+        // skipped regions still count towards the total
+        let mut stats = ScanStats {
+            total_bytes: self
+                .memory_regions
+                .iter()
+                .filter(|region| !self.should_scan_region(region, &resident_regions))
+                .map(|region| region.end - region.start)
+                .sum(),
+            ..Default::default()
+        };
+        // the regions are in address order, so are the matches
+        let mut matches = MatchStore::new(self.read_size.unwrap_or(self.match_size()));
+        for (mut region_matches, region_stats) in results {
+            stats = stats.merge(region_stats);
+            matches.append(&mut region_matches);
+        }
+        stats.elapsed = started.elapsed();
+        let mut results = Vec::new();
+        let truncated = self.keep_matches(&mut results, matches, |m, i| m.value(i).to_vec());
+        self.stats = stats;
+        self.results = results;
+        self.truncated = truncated;
+        self.refresh_watchlist()?;
+
+        Ok(&self.results)
+    }
+
+    /// First scan without a value, every aligned address of the scanned regions is a
+    /// candidate and the next scan compares against the memory read now.
+    /// Returns the number of candidates
+    pub fn init_unknown(&mut self) -> Result<usize, ScanError> {
+        if self.value_type.is_variable_size() {
+            return Err(ScanError::TypeMismatch);
+        }
+
+        let _paused = self.pause_target()?;
+        self.reset_result_view();
+        let started = Instant::now();
+        let resident_regions = self.get_resident_regions();
+
+        let (regions, progress) = self.get_regions_to_scan(&resident_regions);
+        let size: u64 = regions.iter().map(|(_, r)| r.end - r.start).sum();
+        let spill = if size > self.memory_budget {
+            Some(SpillFile::create().map_err(|e| ScanError::SpillFile(e.to_string()))?)
+        } else {
+            None
+        };
+        let snapshots: Result<Vec<(Vec<SnapshotBlock>, ScanStats)>, MemoryError> = regions
+            .par_iter()
+            .map(|&(_, region)| {
+                let snapshot = self.snapshot_region(region, spill.as_ref());
+                progress.add(1, region.end - region.start, 0);
+                snapshot
+            })
+            .collect();
+
+        let snapshots = snapshots.map_err(ScanError::Memory)?;
+        self.stats = ScanStats {
+            total_bytes: self
+                .memory_regions
+                .iter()
+                .filter(|region| !self.should_scan_region(region, &resident_regions))
+                .map(|region| region.end - region.start)
+                .sum(),
+            ..Default::default()
+        };
+        let mut snapshot = Snapshot::default();
+        for (blocks, region_stats) in snapshots {
+            self.stats = self.stats.merge(region_stats);
+            snapshot.blocks.extend(blocks);
+        }
+        if let Some(spill) = spill {
+            snapshot.spill = Some(
+                spill
+                    .map()
+                    .map_err(|e| ScanError::SpillFile(e.to_string()))?,
+            );
+        }
+        self.stats.elapsed = started.elapsed();
+        self.results = Vec::new();
+        self.truncated = MatchStore::default();
+        self.snapshot = Some(snapshot);
+
+        Ok(self.stats.matches)
+    }
+
+    /// True between an unknown initial value scan and the next scan
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot.is_some()
+    }
+
+    /// Next scan after an unknown initial value scan, the snapshot becomes the
+    /// previous and first value of the results
+    fn next_scan_snapshot(&mut self, snapshot: Snapshot) -> Vec<ScanResult> {
+        let size = self.value_type.get_size() as usize;
+        let started = Instant::now();
+        let progress = ProgressReporter::new(
+            self,
+            0,
+            snapshot.blocks.iter().map(|block| block.len() as u64).sum(),
+        );
+
+        let scanned: Vec<(MatchStore, u64, u64)> = snapshot
+            .blocks
+            .par_iter()
+            .map(|block| {
+                let total = block.len() as u64;
+                let mut matches = MatchStore::new(size);
+                let Ok(current) = self
+                    .memory
+                    .read(self.pid, block.start as usize, block.len())
+                else {
+                    progress.add(0, total, 0);
+                    return (matches, total, 0);
+                };
+
+                let data = snapshot.data(block);
+                let region = self.region_index(block.start);
+                for i in self.get_candidate_offsets(block.start, data) {
+                    let (value, previous) = (&current[i..i + size], &data[i..i + size]);
+                    if self.value_matches_previous(value, previous) {
+                        matches.push(block.start + i as u64, value, Some(previous), region);
+                    }
+                }
+                progress.add(0, total, matches.len());
+                (matches, total, total)
+            })
+            .collect();
+
+        let mut stats = ScanStats::default();
+        let mut matches = MatchStore::new(size);
+        for (mut block_matches, total_bytes, bytes_read) in scanned {
+            stats.total_bytes += total_bytes;
+            stats.bytes_read += bytes_read;
+            matches.append(&mut block_matches);
+        }
+        stats.matches = matches.len();
+        stats.elapsed = started.elapsed();
+        self.stats = stats;
+        let mut results = Vec::new();
+        self.truncated = self.keep_matches(&mut results, matches, |m, i| m.first_value(i).to_vec());
+        results
+    }
+
+    pub fn refresh(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
+        self.check_value()?;
+
+        if self.results.is_empty() {
+            self.refresh_watchlist()?;
+            return Ok(&self.results);
+        }
+
+        // Parallel refresh in chunks read with batched reads, unreadable results are dropped
+        let updated_results: Result<Vec<Vec<ScanResult>>, MemoryError> = self
+            .results
+            .par_chunks(NARROW_CHUNK)
+            .map(|chunk| {
+                let values = self.memory.read_many(self.pid, &self.result_reads(chunk))?;
+                Ok(chunk
+                    .iter()
+                    .zip(values)
+                    .filter_map(|(result, val)| {
+                        let mut updated = result.clone();
+                        updated.value_type = self.value_type;
+                        updated.endianness = self.endianness;
+                        updated.previous_value = std::mem::replace(&mut updated.value, val?);
+                        Some(updated)
+                    })
+                    .collect())
+            })
+            .collect();
+        let updated_results = updated_results
+            .map_err(ScanError::Memory)?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        self.results = updated_results;
+        self.refresh_watchlist()?;
+
+        Ok(&self.results)
+    }
+
+    pub fn next_scan(&mut self) -> Result<&Vec<ScanResult>, ScanError> {
+        self.check_value()?;
+        let _paused = self.pause_target()?;
+        self.reset_result_view();
+
+        if let Some(snapshot) = self.snapshot.take() {
+            self.results = self.next_scan_snapshot(snapshot);
+            self.refresh_watchlist()?;
+            return Ok(&self.results);
+        }
+
+        let started = Instant::now();
+
+        if self.results.is_empty() && self.truncated.is_empty() {
+            self.refresh_watchlist()?;
+            return Ok(&self.results);
+        }
+
+        let progress = ProgressReporter::new(
+            self,
+            0,
+            self.results
+                .iter()
+                .map(|result| self.read_size.unwrap_or(result.value.len()) as u64)
+                .sum::<u64>()
+                + (self.truncated.len() * self.truncated_read_size()) as u64,
+        );
+
+        // Parallel next scan in chunks read with batched reads, attach errors end the scan
+        // and unreadable results are dropped
+        let scanned: Result<Vec<(Vec<ScanResult>, u64, u64)>, MemoryError> = self
+            .results
+            .par_chunks(NARROW_CHUNK)
+            .map(|chunk| {
+                let requests = self.result_reads(chunk);
+                let values = self.memory.read_many(self.pid, &requests)?;
+                let total: u64 = requests.iter().map(|&(_, size)| size as u64).sum();
+                let mut bytes_read = 0;
+                let mut narrowed = Vec::new();
+                for (result, val) in chunk.iter().zip(values) {
+                    let Some(val) = val else {
+                        continue;
+                    };
+                    bytes_read += val.len() as u64;
+                    let previous = self.baseline(&result.scanned_value, &result.first_value);
+                    if self.value_matches_previous(&val, previous) {
+                        let mut new_result = result.clone();
+                        new_result.value_type = self.value_type;
+                        new_result.endianness = self.endianness;
+                        new_result.scanned_value = val.clone();
+                        new_result.previous_value = std::mem::replace(&mut new_result.value, val);
+                        narrowed.push(new_result);
+                    }
+                }
+                progress.add(0, total, narrowed.len());
+                Ok((narrowed, total, bytes_read))
+            })
+            .collect();
+        let scanned = scanned.map_err(ScanError::Memory)?;
+
+        // truncated matches are narrowed down the same way, each chunk keeps the index
+        // of its matches in the old store to find the previous values
+        let read_size = self.truncated_read_size();
+        let count = self.truncated.len();
+        let chunks: Result<Vec<(MatchStore, Vec<usize>, u64)>, MemoryError> = (0..count)
+            .into_par_iter()
+            .step_by(NARROW_CHUNK)
+            .map(|start| {
+                let end = (start + NARROW_CHUNK).min(count);
+                let requests: Vec<(usize, usize)> = self.truncated.addresses[start..end]
+                    .iter()
+                    .map(|&address| (address as usize, read_size))
+                    .collect();
+                let values = self.memory.read_many(self.pid, &requests)?;
+                let mut narrowed = MatchStore::new(read_size);
+                let mut previous = Vec::new();
+                let mut bytes_read = 0;
+                for (i, val) in (start..end).zip(values) {
+                    let Some(val) = val else {
+                        continue;
+                    };
+                    bytes_read += read_size as u64;
+                    let first_value = self.truncated.first_value(i);
+                    let baseline = self.baseline(self.truncated.value(i), first_value);
+                    if self.value_matches_previous(&val, baseline) {
+                        let address = self.truncated.addresses[i];
+                        narrowed.push(address, &val, Some(first_value), self.truncated.regions[i]);
+                        previous.push(i);
+                    }
+                }
+                progress.add(0, ((end - start) * read_size) as u64, narrowed.len());
+                Ok((narrowed, previous, bytes_read))
+            })
+            .collect();
+        let chunks = chunks.map_err(ScanError::Memory)?;
+
+        let mut stats = ScanStats::default();
+        let mut new_results = Vec::new();
+        for (result, total_bytes, bytes_read) in scanned {
+            stats.total_bytes += total_bytes;
+            stats.bytes_read += bytes_read;
+            new_results.extend(result);
+        }
+
+        // truncated matches fill the results back up to the cap
+        stats.total_bytes += (self.truncated.len() * read_size) as u64;
+        let mut narrowed = MatchStore::new(read_size);
+        let mut previous = Vec::new();
+        for (mut chunk, chunk_previous, bytes_read) in chunks {
+            stats.bytes_read += bytes_read;
+            narrowed.append(&mut chunk);
+            previous.extend(chunk_previous);
+        }
+        let truncated = self.keep_matches(&mut new_results, narrowed, |_, i| {
+            self.truncated.value(previous[i]).to_vec()
+        });
+        stats.matches = new_results.len() + truncated.len();
+        stats.elapsed = started.elapsed();
+
+        self.stats = stats;
+        self.results = new_results;
+        self.truncated = truncated;
+        self.refresh_watchlist()?;
+
+        Ok(&self.results)
+    }
+
+    /// Addresses and sizes refreshes and next scans read for `results`
+    fn result_reads(&self, results: &[ScanResult]) -> Vec<(usize, usize)> {
+        results
+            .iter()
+            .map(|result| {
+                let read_size = self.read_size.unwrap_or(result.value.len());
+                (result.address as usize, read_size)
+            })
+            .collect()
+    }
+
+    /// Read size of next scans over the truncated matches
+    fn truncated_read_size(&self) -> usize {
+        self.read_size.unwrap_or(self.truncated.value_size)
+    }
+
+    /// Index of the memory region holding `address`, `u32::MAX` when there is none
+    fn region_index(&self, address: u64) -> u32 {
+        let i = self.memory_regions.partition_point(|r| r.start <= address);
+        match self.memory_regions[..i].last() {
+            Some(region) if address < region.end => (i - 1) as u32,
+            _ => u32::MAX,
+        }
+    }
+
+    /// Turns the matches into results until there are `max_results`, returns the rest.
+    /// `previous` gives the value of a match before this scan
+    fn keep_matches(
+        &self,
+        results: &mut Vec<ScanResult>,
+        mut matches: MatchStore,
+        previous: impl Fn(&MatchStore, usize) -> Vec<u8>,
+    ) -> MatchStore {
+        let kept = self
+            .max_results
+            .saturating_sub(results.len())
+            .min(matches.len());
+        results.extend((0..kept).map(|i| {
+            let address = matches.addresses[i];
+            // the regions change when the permissions do, the index is only a hint then
+            let region = self
+                .memory_regions
+                .get(matches.regions[i] as usize)
+                .filter(|r| r.start <= address && address < r.end)
+                .or_else(|| self.memory_regions.get(self.region_index(address) as usize));
+            let mut result = ScanResult::new(
+                address,
+                self.value_type,
+                matches.value(i).to_vec(),
+                region.map(|r| r.perms.clone()).unwrap_or_default(),
+                region.and_then(|r| r.path.clone()),
+            );
+            result.file_offset = region.and_then(|r| r.file_offset(address));
+            result.endianness = self.endianness;
+            result.previous_value = previous(&matches, i);
+            result.first_value = matches.first_value(i).to_vec();
+            result
+        }));
+        matches.split_off(kept)
+    }
+
+    /// Replaces the results with the addresses of a list, so refreshes and next scans only
+    /// look at them. Addresses that can not be read are left out, returns how many were imported
+    pub fn import_addresses(&mut self, content: &str) -> Result<usize, ScanError> {
+        let ranges = self
+            .memory
+            .named_ranges(self.pid)
+            .map_err(ScanError::Memory)?;
+        let addresses = parse_address_list(content, &ranges)?;
+
+        let size = match self.value_type.get_size() as usize {
+            0 => self.read_size.unwrap_or(self.value.len()),
+            size => self.read_size.unwrap_or(size),
+        };
+        if size == 0 {
+            return Err(ScanError::EmptyValue);
+        }
+
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let mut regions = self
+            .memory
+            .regions(self.pid, None, None, Some(&perms))
+            .map_err(ScanError::Memory)?;
+        regions.sort_unstable_by_key(|r| r.start);
+
+        let imported: Vec<ScanResult> = addresses
+            .into_iter()
+            .filter_map(|address| {
+                let i = regions.partition_point(|r| r.start <= address);
+                let region = regions[..i].last().filter(|r| address < r.end)?;
+                let value = self.memory.read(self.pid, address as usize, size).ok()?;
+                let mut result = ScanResult::new(
+                    address,
+                    self.value_type,
+                    value,
+                    region.perms.clone(),
+                    region.path.clone(),
+                );
+                result.file_offset = region.file_offset(address);
+                result.endianness = self.endianness;
+                Some(result)
+            })
+            .collect();
+
+        self.reset_result_view();
+        self.snapshot = None;
+        self.stats = ScanStats {
+            matches: imported.len(),
+            ..ScanStats::default()
+        };
+        self.results = imported;
+        self.truncated = MatchStore::default();
+        Ok(self.results.len())
+    }
+
+    /// Keeps the current results as set A for comparing with later scans
+    pub fn pin_results(&mut self) {
+        self.reset_result_view();
+        self.pinned = Some(self.results.clone());
+    }
+
+    /// Replaces the results with a combination of the pinned set and the current scan,
+    /// values of pinned-only results are the ones captured when they were pinned
+    pub fn set_result_view(&mut self, view: ResultSetView) -> Result<&Vec<ScanResult>, ScanError> {
+        let Some(pinned) = &self.pinned else {
+            return Err(ScanError::NothingPinned);
+        };
+
+        let current = self
+            .unfiltered_results
+            .take()
+            .unwrap_or_else(|| std::mem::take(&mut self.results));
+        let pinned_addresses: HashSet<u64> = pinned.iter().map(|r| r.address).collect();
+        let current_addresses: HashSet<u64> = current.iter().map(|r| r.address).collect();
+
+        self.results = match view {
+            ResultSetView::Current => current.clone(),
+            ResultSetView::Intersection => current
+                .iter()
+                .filter(|r| pinned_addresses.contains(&r.address))
+                .cloned()
+                .collect(),
+            ResultSetView::OnlyPinned => pinned
+                .iter()
+                .filter(|r| !current_addresses.contains(&r.address))
+                .cloned()
+                .collect(),
+            ResultSetView::OnlyCurrent => current
+                .iter()
+                .filter(|r| !pinned_addresses.contains(&r.address))
+                .cloned()
+                .collect(),
+        };
+        self.result_view = view;
+        if view != ResultSetView::Current {
+            self.unfiltered_results = Some(current);
+        }
+
+        Ok(&self.results)
+    }
+
+    /// Number of results in each scanned region that has any, the densest region first
+    pub fn get_match_density(&self) -> Vec<(NamedRange, usize)> {
+        let mut counts = vec![0; self.memory_regions.len()];
+        for result in &self.results {
+            let i = self
+                .memory_regions
+                .partition_point(|r| r.start <= result.address);
+            if i > 0 && result.address < self.memory_regions[i - 1].end {
+                counts[i - 1] += 1;
+            }
+        }
+
+        let mut density: Vec<(NamedRange, usize)> = self
+            .memory_regions
+            .iter()
+            .zip(counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(region, count)| {
+                let name = region
+                    .path
+                    .as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .unwrap_or("anon");
+                let range = NamedRange {
+                    name: name.to_owned(),
+                    start: region.start,
+                    end: region.end,
+                };
+                (range, count)
+            })
+            .collect();
+        // stable, regions with the same count stay in address order
+        density.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        density
+    }
+
+    /// Keeps the results whose value, read as a pointer of the target, points into one of its
+    /// mappings, returns how many were dropped
+    pub fn filter_valid_pointers(&mut self, pointer_width: usize) -> Result<usize, ScanError> {
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let regions = self
+            .memory
+            .regions(self.pid, None, None, Some(&perms))
+            .map_err(ScanError::Memory)?;
+        Ok(self.retain_pointers_into(&regions, pointer_width))
+    }
+
+    fn retain_pointers_into(&mut self, regions: &[MemoryRegion], pointer_width: usize) -> usize {
+        self.reset_result_view();
+
+        let mut ranges: Vec<(u64, u64)> = regions.iter().map(|r| (r.start, r.end)).collect();
+        ranges.sort_unstable();
+
+        // the leading bytes of every value are the pointer, so arrays and strings work too
+        let is_mapped = |value: &[u8]| {
+            let address = match value.get(..pointer_width) {
+                Some(bytes) if pointer_width == 4 => {
+                    u32::from_le_bytes(bytes.try_into().unwrap()) as u64
+                }
+                Some(bytes) if pointer_width == 8 => u64::from_le_bytes(bytes.try_into().unwrap()),
+                _ => return false,
+            };
+            let i = ranges.partition_point(|&(start, _)| start <= address);
+            i > 0 && address < ranges[i - 1].1
+        };
+
+        let before = self.results.len();
+        self.results.retain(|result| is_mapped(&result.value));
+        before - self.results.len()
+    }
+
+    /// The visible results become the current scan, scans continue from there
+    fn reset_result_view(&mut self) {
+        self.unfiltered_results = None;
+        self.result_view = ResultSetView::Current;
+    }
+
+    pub fn add_to_watchlist(&mut self, result: ScanResult) {
+        let already_existing = self
+            .watchlist
+            .iter()
+            .position(|w| w.address == result.address);
+        if already_existing.is_some() {
+            return;
+        }
+
+        self.watchlist.push(result);
+    }
+
+    pub fn remove_from_watchlist(&mut self, address: u64) {
+        let already_existing = self.watchlist.iter().position(|w| w.address == address);
+        if already_existing.is_none() {
+            return;
+        }
+
+        self.watchlist.remove(already_existing.unwrap());
+    }
+
+    /// Removes every watchlist entry at one of the addresses, returns how many were removed
+    pub fn remove_addresses_from_watchlist(&mut self, addresses: &HashSet<u64>) -> usize {
+        let len = self.watchlist.len();
+        self.watchlist.retain(|w| !addresses.contains(&w.address));
+        len - self.watchlist.len()
+    }
+
+    pub fn clear_watchlist(&mut self) {
+        self.watchlist.clear();
+    }
+
+    /// Byte order of the watchlist entry at `address`, the one of the scan for everything else
+    pub fn get_endianness(&self, address: u64) -> Endianness {
+        self.watchlist
+            .iter()
+            .find(|w| w.address == address)
+            .map_or(self.endianness, |w| w.endianness)
+    }
+
+    /// Type values at `address` are read and written as, watchlist entries keep the type
+    /// they were added with
+    pub fn get_value_type(&self, address: u64) -> ValueType {
+        self.watchlist
+            .iter()
+            .find(|w| w.address == address)
+            .map_or(self.value_type, |w| w.value_type)
+    }
+
+    /// Flips the byte order of a watchlist entry, returns the new one
+    pub fn toggle_watchlist_endianness(&mut self, address: u64) -> Option<Endianness> {
+        let entry = self.watchlist.iter_mut().find(|w| w.address == address)?;
+        entry.endianness = entry.endianness.next();
+        Some(entry.endianness)
+    }
+
+    /// Makes sure the pid still belongs to the process the scan was attached to
+    fn check_identity(&self) -> Result<(), ScanError> {
+        match &self.identity {
+            Some(identity) if get_process_identity(self.pid).as_ref() != Some(identity) => {
+                Err(ScanError::ProcessChanged(self.pid))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn update_value(&mut self, address: u64, value_str: &str) -> Result<(), ScanError> {
+        self.write_value(address, value_str, false)
+    }
+
+    /// Like `update_value`, for values on pages that are not writable. The protection is
+    /// only lifted for the write
+    pub fn update_protected_value(
+        &mut self,
+        address: u64,
+        value_str: &str,
+    ) -> Result<(), ScanError> {
+        self.write_value(address, value_str, true)
+    }
+
+    fn write_value(
+        &mut self,
+        address: u64,
+        value_str: &str,
+        protected: bool,
+    ) -> Result<(), ScanError> {
+        let value_type = self.get_value_type(address);
+        let mut value = value_type.parse_value(value_str)?;
+        self.check_pointer(value_type, &value)?;
+        self.check_identity()?;
+        if matches!(
+            value_type,
+            ValueType::String
+                | ValueType::WideString
+                | ValueType::Latin1String
+                | ValueType::ShiftJisString
+        ) && let Some(previous) = self
+            .watchlist
+            .iter()
+            .chain(self.results.iter())
+            .find(|r| r.address == address)
+        {
+            let unit_size = if value_type == ValueType::WideString {
+                2
+            } else {
+                1
+            };
+            value = self
+                .string_terminator
+                .terminate(&value, previous.value.len(), unit_size);
+        }
+        let endianness = self.get_endianness(address);
+        let mut value = endianness.convert(value_type, &value);
+        // `*` bits and fields keep what is in memory
+        let mask = value_type.parse_mask(value_str)?;
+        if !mask.is_empty() {
+            let mask = endianness.convert(value_type, &mask);
+            let current = self
+                .memory
+                .read(self.pid, address as usize, value.len())
+                .map_err(ScanError::Memory)?;
+            value = value
+                .iter()
+                .zip(mask)
+                .zip(current)
+                .map(|((v, m), c)| v & m | c & !m)
+                .collect();
+        }
+        if protected {
+            self.memory
+                .write_protected(self.pid, address as usize, &value)
+        } else {
+            self.memory.write(self.pid, address as usize, &value)
+        }
+        .map_err(ScanError::Memory)
+    }
+
+    fn apply_step(value_type: ValueType, value: &[u8], step: i64) -> Result<Vec<u8>, ScanError> {
+        fn bytes<const N: usize>(value: &[u8]) -> Result<[u8; N], ScanError> {
+            value.try_into().map_err(|_| ScanError::TypeMismatch)
+        }
+
+        // integer values wrap around like they would in the target
+        Ok(match value_type {
+            ValueType::U128 => u128::from_le_bytes(bytes(value)?)
+                .wrapping_add_signed(step as i128)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::I128 => i128::from_le_bytes(bytes(value)?)
+                .wrapping_add(step as i128)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::U64 => u64::from_le_bytes(bytes(value)?)
+                .wrapping_add_signed(step)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::I64 => i64::from_le_bytes(bytes(value)?)
+                .wrapping_add(step)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::U32 => (u32::from_le_bytes(bytes(value)?) as i64)
+                .wrapping_add(step)
+                .to_le_bytes()[..4]
+                .to_vec(),
+            ValueType::I32 => (i32::from_le_bytes(bytes(value)?) as i64)
+                .wrapping_add(step)
+                .to_le_bytes()[..4]
+                .to_vec(),
+            ValueType::U16 | ValueType::I16 => (u16::from_le_bytes(bytes(value)?) as i64)
+                .wrapping_add(step)
+                .to_le_bytes()[..2]
+                .to_vec(),
+            ValueType::U8 | ValueType::I8 | ValueType::Bit => (u8::from_le_bytes(bytes(value)?)
+                as i64)
+                .wrapping_add(step)
+                .to_le_bytes()[..1]
+                .to_vec(),
+            ValueType::F32 => (f32::from_le_bytes(bytes(value)?) + step as f32)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::F64 => (f64::from_le_bytes(bytes(value)?) + step as f64)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::Pointer => u64::from_le_bytes(bytes(value)?)
+                .wrapping_add_signed(step)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::Pointer32 => u32::from_le_bytes(bytes(value)?)
+                .wrapping_add_signed(step as i32)
+                .to_le_bytes()
+                .to_vec(),
+            ValueType::String
+            | ValueType::WideString
+            | ValueType::Latin1String
+            | ValueType::ShiftJisString
+            | ValueType::Hex
+            | ValueType::Struct(_) => {
+                return Err(ScanError::TypeMismatch);
+            }
+        })
+    }
+
+    /// Reads the current value of type `value_type` at `address`, adds `step` to it and
+    /// writes it back. Watchlist entries keep their own type when the scan type changes
+    pub fn step_value(
+        &mut self,
+        address: u64,
+        value_type: ValueType,
+        step: i64,
+    ) -> Result<Vec<u8>, ScanError> {
+        let size = value_type.get_size() as usize;
+        if size == 0 {
+            return Err(ScanError::TypeMismatch);
+        }
+
+        let endianness = self.get_endianness(address);
+        let current = self
+            .memory
+            .read(self.pid, address as usize, size)
+            .map_err(ScanError::Memory)?;
+        let current = endianness.convert(value_type, &current);
+        let value = Self::apply_step(value_type, &current, step)?;
+        self.check_pointer(value_type, &value)?;
+        self.check_identity()?;
+        self.memory
+            .write(
+                self.pid,
+                address as usize,
+                &endianness.convert(value_type, &value),
+            )
+            .map_err(ScanError::Memory)?;
+
+        Ok(value)
+    }
+}
+
+pub(crate) mod test {
+    #[allow(unused_imports)]
+    use crate::core::mem::write_memory_address;
+
+    /// Memory of a pretend process, a single writable mapping at `MOCK_BASE`
+    #[allow(dead_code)]
+    struct MockMemory(std::sync::Mutex<Vec<u8>>);
+
+    #[allow(dead_code)]
+    const MOCK_BASE: usize = 0x10000;
+
+    impl crate::core::mem::MemoryBackend for MockMemory {
+        fn regions(
+            &self,
+            _pid: u32,
+            start: Option<u64>,
+            end: Option<u64>,
+            search_perms: Option<&[super::MemoryRegionPerms]>,
+        ) -> Result<Vec<super::MemoryRegion>, super::MemoryError> {
+            let region = test_region(
+                MOCK_BASE as u64,
+                (MOCK_BASE + self.0.lock().unwrap().len()) as u64,
+                &[
+                    super::MemoryRegionPerms::Read,
+                    super::MemoryRegionPerms::Write,
+                ],
+                Some("[heap]"),
+            );
+            let search_perms = search_perms.unwrap_or(&super::DEFAULT_SEARCH_PERMS);
+            Ok(Some(region)
+                .filter(|r| r.is_selected(start, end, search_perms))
+                .into_iter()
+                .collect())
+        }
+
+        fn read(&self, _pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, super::MemoryError> {
+            let data = self.0.lock().unwrap();
+            addr.checked_sub(MOCK_BASE)
+                .and_then(|offset| data.get(offset..offset + size))
+                .map(|bytes| bytes.to_vec())
+                .ok_or(super::MemoryError::MemRead(libc::EFAULT))
+        }
+
+        fn write(&self, _pid: u32, addr: usize, value: &[u8]) -> Result<(), super::MemoryError> {
+            let mut data = self.0.lock().unwrap();
+            addr.checked_sub(MOCK_BASE)
+                .and_then(|offset| data.get_mut(offset..offset + value.len()))
+                .ok_or(super::MemoryError::MemWrite(libc::EFAULT))?
+                .copy_from_slice(value);
+            Ok(())
+        }
+    }
+
+    #[allow(dead_code)]
+    fn test_scan(value_type: super::ValueType) -> super::Scan {
+        super::Scan {
+            pid: 0,
+            value: vec![],
+            value_type,
+            compare_mode: super::CompareMode::Exact,
+            compare_percent: super::DEFAULT_COMPARE_PERCENT,
+            compare_epsilon: None,
+            value_decimals: 0,
+            value_mask: vec![],
+            alignment: 1,
+            string_terminator: super::StringTerminator::None,
+            endianness: super::Endianness::Little,
+            identity: None,
+            progress_callback: None,
+            predicate: None,
+            memory: Box::new(crate::core::mem::NativeMemory),
+            results: vec![],
+            watchlist: vec![],
+            start_address: None,
+            end_address: None,
+            module: None,
+            pointer_width: 8,
+            stats: super::ScanStats::default(),
+            pinned: None,
+            result_view: super::ResultSetView::Current,
+            unfiltered_results: None,
+            snapshot: None,
+            memory_budget: super::DEFAULT_MEMORY_BUDGET,
+            truncated: super::MatchStore::default(),
+            max_results: super::DEFAULT_MAX_RESULTS,
+            read_size: None,
+            block_size: super::READ_BLOCK_SIZE,
+            skip_swapped: false,
+            skip_non_resident: false,
+            count_swapped: false,
+            exclude_shared_libs: false,
+            exclude_shared_mappings: false,
+            exclude_executable: false,
+            heap_stack_only: false,
+            skip_file_backed: false,
+            skip_zeros: false,
+            skip_overlapping: false,
+            pause_target: false,
+            memory_regions: vec![],
+            memory_permissions: vec![],
+            region_cache: vec![],
+            regions_loaded: std::time::Instant::now(),
+        }
+    }
+
+    /// Private mapping at `start..end`, anonymous unless it has a `path`
+    #[allow(dead_code)]
+    pub(crate) fn test_region(
+        start: u64,
+        end: u64,
+        perms: &[super::MemoryRegionPerms],
+        path: Option<&str>,
+    ) -> super::MemoryRegion {
+        super::MemoryRegion {
+            start,
+            end,
+            perms: perms.to_vec(),
+            path: path.map(|p| p.to_owned()),
+            offset: 0,
+            inode: 0,
+            shared: false,
+        }
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_scan_creation_success() {
+        use super::*;
+        use std::process::{Command, Stdio};
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match proc {
+            Err(e) => assert!(false, "Error running simple program: {e}"),
+            Ok(child) => {
+                let proc = crate::core::utils::ChildGuard(child);
+
+                let scan = Scan::new(
+                    proc.0.id(),
+                    31337_u32.to_le_bytes().to_vec(),
+                    ValueType::U32,
+                    None,
+                    None,
+                    None,
+                );
+                assert!(scan.is_ok());
+                let scan = scan.unwrap();
+                assert_eq!(scan.results.len(), 0);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_scan_init_success() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+
+        reader.read_line(&mut line).unwrap();
+
+        let hex_str = line.trim();
+        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let scan = Scan::new(
+            proc.0.id(),
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        );
+        assert!(scan.is_ok());
+        let mut scan = scan.unwrap();
+        let results = scan.init().unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.address, address as u64);
+        assert_eq!(
+            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
+            31337_u32
+        );
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_scan_init_stats() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        // wait until the value is in memory, the reader keeps the pipe open as the
+        // program exits when it can not print its second line
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        reader.read_line(&mut String::new()).unwrap();
+        let mut scan = Scan::new(
+            proc.0.id(),
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        scan.set_skip_swapped(true);
+        scan.set_skip_non_resident(true);
+        scan.init().unwrap();
+        assert!(scan.stats.total_bytes > 0);
+        assert!(scan.stats.bytes_read > 0);
+        assert!(scan.stats.bytes_read <= scan.stats.total_bytes - scan.stats.swapped_bytes);
+        assert!(scan.stats.regions_scanned > 0);
+        assert_eq!(scan.stats.matches, scan.results.len());
+
+        let matches = scan.results.len();
+        scan.next_scan().unwrap();
+        assert_eq!(scan.stats.total_bytes, matches as u64 * 4);
+        assert_eq!(scan.stats.matches, scan.results.len());
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_scan_refresh_success() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+
+        reader.read_line(&mut line).unwrap();
+
+        let hex_str = line.trim();
+        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let scan = Scan::new(
+            proc.0.id(),
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        );
+        assert!(scan.is_ok());
+        let mut scan = scan.unwrap();
+        let results = scan.init().unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.address, address as u64);
+        assert_eq!(
+            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
+            31337_u32
+        );
+
+        scan.update_value(address as u64, "333333").unwrap();
+
+        let results = scan.refresh().unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.address, address as u64);
+        assert_eq!(
+            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
+            333333_u32
+        );
+        assert!(result.has_changed());
+        assert_eq!(result.previous_value, 31337_u32.to_le_bytes().to_vec());
+
+        let results = scan.refresh().unwrap();
+        assert!(!results[0].has_changed());
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_next_scan_success() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+
+        reader.read_line(&mut line).unwrap();
+
+        let hex_str = line.trim();
+        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let scan = Scan::new(
+            proc.0.id(),
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        );
+        assert!(scan.is_ok());
+        let mut scan = scan.unwrap();
+        let results = scan.init().unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.address, address as u64);
+        assert_eq!(
+            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
+            31337_u32
+        );
+
+        let results = scan.next_scan().unwrap();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.address, address as u64);
+        assert_eq!(
+            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
+            31337_u32
+        );
+
+        write_memory_address(proc.0.id(), address, &333333_u32.to_le_bytes().to_vec()).unwrap();
+
+        let results = scan.next_scan().unwrap();
+        assert_eq!(results.len(), 0);
+    }
+
+    #[test]
+    pub fn test_regions_selected_from_cache() {
+        use super::*;
+        let region =
+            |start, perms: &[MemoryRegionPerms]| test_region(start, start + 0x1000, perms, None);
+        // no process to read the maps of, they have to come from the cache
+        let mut scan = test_scan(ValueType::U32);
+        scan.pid = u32::MAX;
+        scan.region_cache = vec![
+            region(0x1000, &[MemoryRegionPerms::Read]),
+            region(0x2000, &[MemoryRegionPerms::Read, MemoryRegionPerms::Write]),
+            region(0x3000, &[MemoryRegionPerms::Read, MemoryRegionPerms::Write]),
+        ];
+
+        scan.set_mem_permissions(vec![MemoryRegionPerms::Write])
+            .unwrap();
+        let starts = |scan: &Scan| {
+            scan.memory_regions
+                .iter()
+                .map(|r| r.start)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(starts(&scan), [0x2000, 0x3000]);
+        scan.set_address_range(None, Some(0x2800)).unwrap();
+        assert_eq!(starts(&scan), [0x2000]);
+        scan.set_mem_permissions(vec![MemoryRegionPerms::Read])
+            .unwrap();
+        assert_eq!(starts(&scan), [0x1000, 0x2000]);
+
+        assert!(matches!(scan.reload_regions(), Err(ScanError::Memory(_))));
+    }
+
+    #[test]
+    pub fn test_set_module() {
+        use super::*;
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let region = |start, path| test_region(start, start + 0x1000, &perms, path);
+        let mut scan = test_scan(ValueType::U32);
+        scan.memory_permissions = vec![MemoryRegionPerms::Write];
+        // the bss of another library sits between the two mappings of libgame.so
+        scan.region_cache = vec![
+            region(0x1000, Some("/usr/lib/libgame.so")),
+            region(0x2000, Some("/usr/lib/libother.so")),
+            region(0x3000, None),
+            region(0x4000, Some("/usr/lib/libgame.so (deleted)")),
+            region(0x5000, Some("[heap]")),
+        ];
+        let starts = |scan: &Scan| {
+            scan.memory_regions
+                .iter()
+                .map(|r| r.start)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scan.set_module("libgame.so"), Ok((0x1000, 0x5000)));
+        assert_eq!(scan.module(), Some("libgame.so"));
+        assert_eq!(
+            (scan.start_address, scan.end_address),
+            (Some(0x1000), Some(0x5000))
+        );
+        assert_eq!(starts(&scan), [0x1000, 0x4000]);
+
+        assert_eq!(
+            scan.set_module("heap"),
+            Err(ScanError::UnknownModule("heap".to_owned()))
+        );
+        assert_eq!(scan.module(), Some("libgame.so"));
+
+        // changing the range ends the restriction
+        scan.set_address_range(None, None).unwrap();
+        assert_eq!(scan.module(), None);
+        assert_eq!(starts(&scan), [0x1000, 0x2000, 0x3000, 0x4000, 0x5000]);
+    }
+
+    #[test]
+    pub fn test_exclude_executable() {
+        use super::*;
+        let region = |perms: &[MemoryRegionPerms]| test_region(0x1000, 0x2000, perms, None);
+        let code = region(&[MemoryRegionPerms::Read, MemoryRegionPerms::Execute]);
+        let data = region(&[MemoryRegionPerms::Read, MemoryRegionPerms::Write]);
+
+        let mut scan = test_scan(ValueType::U32);
+        assert!(scan.should_scan_region(&code, &None));
+        scan.set_exclude_executable(true);
+        assert!(!scan.should_scan_region(&code, &None));
+        assert!(scan.should_scan_region(&data, &None));
+
+        // code alone is selected by searching for executable regions
+        let regions = select_regions(&[code, data], None, None, &[MemoryRegionPerms::Execute]);
+        assert_eq!(regions.len(), 1);
+        assert!(regions[0].perms.contains(&MemoryRegionPerms::Execute));
+    }
+
+    #[test]
+    pub fn test_exclude_shared_mappings() {
+        use super::*;
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let region = |shared| MemoryRegion {
+            shared,
+            ..test_region(0x1000, 0x2000, &perms, None)
+        };
+
+        let mut scan = test_scan(ValueType::U32);
+        assert!(scan.should_scan_region(&region(true), &None));
+        scan.set_exclude_shared_mappings(true);
+        assert!(!scan.should_scan_region(&region(true), &None));
+        assert!(scan.should_scan_region(&region(false), &None));
+    }
+
+    #[test]
+    pub fn test_heap_stack_only() {
+        use super::*;
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let region = |path| test_region(0x1000, 0x2000, &perms, path);
+
+        let mut scan = test_scan(ValueType::U32);
+        assert!(scan.should_scan_region(&region(Some("/usr/bin/game")), &None));
+        scan.set_heap_stack_only(true);
+        assert!(!scan.should_scan_region(&region(Some("/usr/bin/game")), &None));
+        assert!(!scan.should_scan_region(&region(Some("[vvar]")), &None));
+        for path in [
+            None,
+            Some("[heap]"),
+            Some("[stack]"),
+            Some("[anon:libc_malloc]"),
+        ] {
+            assert!(scan.should_scan_region(&region(path), &None));
+        }
+    }
+
+    #[test]
+    pub fn test_skip_file_backed() {
+        use super::*;
+        let perms = [MemoryRegionPerms::Read, MemoryRegionPerms::Write];
+        let region = |path| test_region(0x1000, 0x2000, &perms, path);
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_skip_file_backed(true);
+        assert!(!scan.should_scan_region(&region(Some("/usr/bin/game")), &None));
+        for path in [
+            None,
+            Some("[heap]"),
+            Some("[stack]"),
+            Some("[vvar]"),
+            Some("/memfd:state (deleted)"),
+        ] {
+            assert!(scan.should_scan_region(&region(path), &None));
+        }
+    }
+
+    #[test]
+    pub fn test_block_size() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        assert_eq!(
+            scan.set_block_size(0x100),
+            Err(ScanError::BlockSizeInvalid(0x1000, 0x4000000))
+        );
+        scan.set_block_size(0x4000).unwrap();
+
+        let region = test_region(0x10000, 0x20000, &[MemoryRegionPerms::Read], None);
+        let blocks = scan.get_read_blocks(&region, 4, &mut ScanStats::default());
+        // every block but the last overlaps the next by 3 bytes
+        assert_eq!(blocks[0], (0x10000, 0x4000, 0x3ffd));
+        assert_eq!(blocks[1], (0x13ffd, 0x4000, 0x3ffd));
+        assert_eq!(blocks.len(), 5);
+        let (start, len, advance) = *blocks.last().unwrap();
+        assert_eq!((start + len, len, advance), (0x20000, len, len));
+    }
+
+    #[test]
+    pub fn test_match_store() {
+        use super::*;
+        let mut store = MatchStore::new(4);
+        store.push(0x1000, &[1, 2, 3, 4], None, 0);
+        // cut short by the end of a region
+        store.push(0x2000, &[5, 6], None, 1);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.value(1), [5, 6, 0, 0]);
+        assert_eq!(store.first_value(0), [1, 2, 3, 4]);
+
+        let rest = store.split_off(1);
+        assert_eq!(store.addresses, [0x1000]);
+        assert_eq!(rest.addresses, [0x2000]);
+        assert_eq!(rest.regions, [1]);
+        assert_eq!(rest.value(0), [5, 6, 0, 0]);
+
+        let mut narrowed = MatchStore::new(4);
+        narrowed.push(0x1000, &[9, 9, 9, 9], Some(store.first_value(0)), 0);
+        assert_eq!(narrowed.value(0), [9, 9, 9, 9]);
+        assert_eq!(narrowed.first_value(0), [1, 2, 3, 4]);
+        let mut other = MatchStore::new(4);
+        other.push(0x3000, &[7, 7, 7, 7], Some(&[8, 8, 8, 8]), 2);
+        narrowed.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(narrowed.first_value(1), [8, 8, 8, 8]);
+        assert_eq!(narrowed.split_off(1).first_value(0), [8, 8, 8, 8]);
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_max_results() {
+        use super::*;
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let proc = crate::core::utils::ChildGuard(proc);
+
+        let mut scan = Scan::new(proc.0.id(), vec![0], ValueType::U8, None, None, None).unwrap();
+        assert_eq!(scan.set_max_results(0), Err(ScanError::InvalidValue));
+        scan.set_max_results(100).unwrap();
+
+        scan.init().unwrap();
+        assert_eq!(scan.results.len(), 100);
+        assert!(scan.truncated_count() > 0);
+        assert_eq!(scan.match_count(), scan.stats.matches);
+        // the kept results are the lowest addresses
+        let last_kept = scan.results.last().unwrap().address;
+        assert!(scan.truncated.addresses.iter().all(|&a| a > last_kept));
+
+        // next scans keep narrowing the truncated matches and refill the results
+        let matches = scan.match_count();
+        scan.next_scan().unwrap();
+        assert_eq!(scan.results.len(), 100);
+        assert!(scan.match_count() <= matches);
+        assert_eq!(scan.match_count(), scan.stats.matches);
+        assert!(scan.results.iter().all(|r| r.value == [0]));
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_progress_callback() {
+        use super::*;
+        use std::process::{Command, Stdio};
+        use std::sync::Arc;
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let proc = crate::core::utils::ChildGuard(proc);
+
+        let mut scan = Scan::new(proc.0.id(), vec![0], ValueType::U8, None, None, None).unwrap();
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        scan.set_progress_callback(Some(ProgressCallback::new(move |progress| {
+            sink.lock().unwrap().push(progress)
+        })));
+        scan.init().unwrap();
+
+        // every region is reported once it is done, threads may report out of order
+        let reported = reported.lock().unwrap();
+        let last = reported.iter().max_by_key(|p| p.regions_done).unwrap();
+        assert_eq!(last.regions_done, last.regions_total);
+        assert_eq!(last.bytes_scanned, last.bytes_total);
+        assert_eq!(last.matches, scan.match_count());
+
+        scan.set_progress_callback(None);
+        scan.next_scan().unwrap();
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_unknown_initial_value_scan() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let address = usize::from_str_radix(line.trim().trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let mut scan = Scan::new(proc.0.id(), vec![], ValueType::U32, None, None, None).unwrap();
+        let candidates = scan.init_unknown().unwrap();
+        assert!(candidates > 0);
+        assert!(scan.has_snapshot());
+        assert!(scan.results.is_empty());
+
+        write_memory_address(proc.0.id(), address, &424242_u32.to_le_bytes()).unwrap();
+        scan.set_value_from_str("424242").unwrap();
+        let results = scan.next_scan().unwrap();
+        let result = results
+            .iter()
+            .find(|r| r.address == address as u64)
+            .expect("changed address not found");
+        assert_eq!(result.previous_value, 31337_u32.to_le_bytes());
+        assert_eq!(result.value, 424242_u32.to_le_bytes());
+        assert!(!scan.has_snapshot());
+
+        scan.set_compare_mode(CompareMode::Unchanged);
+        let results = scan.next_scan().unwrap();
+        assert!(results.iter().any(|r| r.address == address as u64));
+
+        // a refresh in between doesn't replace the bytes the next scan compares with
+        write_memory_address(proc.0.id(), address, &424243_u32.to_le_bytes()).unwrap();
+        scan.refresh().unwrap();
+        scan.set_compare_mode(CompareMode::Increased);
+        let results = scan.next_scan().unwrap();
+        let result = results
+            .iter()
+            .find(|r| r.address == address as u64)
+            .expect("increased address not found");
+        assert_eq!(result.scanned_value, 424243_u32.to_le_bytes());
+
+        write_memory_address(proc.0.id(), address, &5_u32.to_le_bytes()).unwrap();
+        scan.set_compare_mode(CompareMode::Changed);
+        let results = scan.next_scan().unwrap();
+        assert!(results.iter().any(|r| r.address == address as u64));
+        assert!(results.iter().all(|r| r.value != r.previous_value));
+
+        let mut scan = test_scan(ValueType::String);
+        assert_eq!(scan.init_unknown(), Err(ScanError::TypeMismatch));
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_unknown_initial_value_scan_spilled() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let address = usize::from_str_radix(line.trim().trim_start_matches("0x"), 16)
+            .expect("failed to parse hex");
+
+        let mut scan = Scan::new(proc.0.id(), vec![], ValueType::U32, None, None, None).unwrap();
+        scan.set_memory_budget(0);
+        assert!(scan.init_unknown().unwrap() > 0);
+        assert!(scan.snapshot.as_ref().unwrap().spill.is_some());
+
+        write_memory_address(proc.0.id(), address, &424242_u32.to_le_bytes()).unwrap();
+        scan.set_compare_mode(CompareMode::Changed);
+        let results = scan.next_scan().unwrap();
+        let result = results
+            .iter()
+            .find(|r| r.address == address as u64)
+            .expect("changed address not found");
+        assert_eq!(result.previous_value, 31337_u32.to_le_bytes());
+        assert_eq!(result.value, 424242_u32.to_le_bytes());
+    }
+
+    #[test]
+    pub fn test_spill_file() {
+        use super::*;
+        let spill = SpillFile::create().unwrap();
+        let blocks: Vec<SnapshotBlock> = [vec![1, 2, 3], vec![], vec![4, 5]]
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| SnapshotBlock {
+                start: 0x1000 * i as u64,
+                data: spill.write(data),
+            })
+            .collect();
+        assert_eq!(blocks[2].len(), 2);
+
+        let snapshot = Snapshot {
+            blocks,
+            spill: Some(spill.map().unwrap()),
+        };
+        let data: Vec<&[u8]> = snapshot.blocks.iter().map(|b| snapshot.data(b)).collect();
+        assert_eq!(data, [&[1, 2, 3][..], &[], &[4, 5]]);
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_u64_success() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U64);
+
+        let result = scan.set_value_from_str("12345");
+        assert!(result.is_ok());
+        assert_eq!(scan.value, 12345_u64.to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_i64_success() {
+        use super::*;
+        let mut scan = test_scan(ValueType::I64);
+
+        let result = scan.set_value_from_str("-54321");
+        assert!(result.is_ok());
+        assert_eq!(scan.value, (-54321_i64).to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_u32_success() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+
+        let result = scan.set_value_from_str("31337");
+        assert!(result.is_ok());
+        assert_eq!(scan.value, 31337_u32.to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_i32_success() {
+        use super::*;
+        let mut scan = test_scan(ValueType::I32);
+
+        let result = scan.set_value_from_str("-999");
+        assert!(result.is_ok());
+        assert_eq!(scan.value, (-999_i32).to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_128_bit() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U128);
+        scan.set_value_from_str("340282366920938463463374607431768211455")
+            .unwrap();
+        assert_eq!(scan.value, vec![0xff; 16]);
+        assert_eq!(ValueType::U128.get_i128(&scan.value), None);
+        assert_eq!(
+            ValueType::U128.get_value_string(&scan.value).unwrap(),
+            u128::MAX.to_string()
+        );
+
+        let mut scan = test_scan(ValueType::I128);
+        scan.set_value_from_str("-1").unwrap();
+        assert_eq!(scan.value, vec![0xff; 16]);
+        assert_eq!(ValueType::I128.get_i128(&scan.value), Some(-1));
+        let hex = DisplayFormat {
+            integers: IntegerFormat::Hex,
+            thousands_separator: false,
+        };
+        assert_eq!(
+            ValueType::I128.format_value(&scan.value, &hex).unwrap(),
+            format!("0x{}", "f".repeat(32))
+        );
+
+        assert_eq!(
+            Scan::apply_step(ValueType::U128, &[0xff; 16], 1).unwrap(),
+            vec![0; 16]
+        );
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_small_integers() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U8);
+        scan.set_value_from_str("200").unwrap();
+        assert_eq!(scan.value, vec![200]);
+        assert!(scan.set_value_from_str("256").is_err());
+
+        let mut scan = test_scan(ValueType::I8);
+        scan.set_value_from_str("0xff").unwrap();
+        assert_eq!(ValueType::I8.get_value_string(&scan.value).unwrap(), "-1");
+
+        let mut scan = test_scan(ValueType::I16);
+        scan.set_value_from_str("-300").unwrap();
+        assert_eq!(scan.value, (-300_i16).to_le_bytes().to_vec());
+        assert_eq!(ValueType::I16.get_i128(&scan.value), Some(-300));
+
+        let mut scan = test_scan(ValueType::U16);
+        scan.set_value_from_str("1, 2").unwrap();
+        assert_eq!(scan.value, vec![1, 0, 2, 0]);
+        assert_eq!(
+            ValueType::U16.get_value_string(&scan.value).unwrap(),
+            "1, 2"
+        );
+
+        // steps wrap around like in the target
+        assert_eq!(Scan::apply_step(ValueType::U8, &[255], 1).unwrap(), vec![0]);
+        assert_eq!(
+            Scan::apply_step(ValueType::I8, &[0], -1).unwrap(),
+            vec![0xff]
+        );
+        assert_eq!(
+            Scan::apply_step(ValueType::U16, &[0xff, 0xff], 2).unwrap(),
+            vec![1, 0]
+        );
+        assert!(Scan::apply_step(ValueType::U8, &[], 1).is_err());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_float_success() {
+        use super::*;
+        let mut scan = test_scan(ValueType::F32);
+        scan.set_value_from_str("98.5").unwrap();
+        assert_eq!(scan.value, 98.5_f32.to_le_bytes().to_vec());
+        assert_eq!(
+            ValueType::F32.get_value_string(&scan.value).unwrap(),
+            "98.5"
+        );
+        assert!(scan.set_value_from_str("0x10").is_err());
+
+        let mut scan = test_scan(ValueType::F64);
+        scan.set_value_from_str("-0.25").unwrap();
+        assert_eq!(scan.value, (-0.25_f64).to_le_bytes().to_vec());
+        assert_eq!(ValueType::F64.get_f64(&scan.value), Some(-0.25));
+        assert_eq!(ValueType::F64.get_i128(&scan.value), None);
+
+        let stepped = Scan::apply_step(ValueType::F32, &1.5_f32.to_le_bytes(), -2).unwrap();
+        assert_eq!(stepped, (-0.5_f32).to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_string_escapes() {
+        use super::*;
+        let mut scan = test_scan(ValueType::String);
+
+        scan.set_value_from_str(r"a\nb\0\x7f\xff\\").unwrap();
+        assert_eq!(scan.value, b"a\nb\0\x7f\xff\\".to_vec());
+
+        for invalid in [r"\x4", r"\xzz", r"\q", "abc\\"] {
+            assert!(matches!(
+                scan.set_value_from_str(invalid),
+                Err(ScanError::InvalidValue)
+            ));
+        }
+
+        // the displayed string can be entered back
+        let shown = ValueType::String
+            .get_value_string(b"C:\\dir\n\x1b")
+            .unwrap();
+        assert_eq!(shown, r"C:\\dir\x0a\x1b");
+        scan.set_value_from_str(&shown).unwrap();
+        assert_eq!(scan.value, b"C:\\dir\n\x1b".to_vec());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_wide_string() {
+        use super::*;
+        let mut scan = test_scan(ValueType::WideString);
+        scan.set_value_from_str("Hé\\n").unwrap();
+        assert_eq!(scan.value, b"H\0\xe9\0\n\0");
+        assert_eq!(
+            ValueType::WideString.get_value_string(&scan.value).unwrap(),
+            "Hé\\x0a"
+        );
+
+        // surrogate pairs and a stray trailing byte read from memory
+        let value: Vec<u8> = "a😀".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        assert_eq!(value.len(), 6);
+        let mut read = value.clone();
+        read.push(b'x');
+        assert_eq!(
+            ValueType::WideString.get_value_string(&read).unwrap(),
+            "a😀"
+        );
+        // decoding stops at an unpaired surrogate
+        assert_eq!(
+            ValueType::WideString
+                .get_value_string(&[b'a', 0, 0x00, 0xd8, b'b', 0])
+                .unwrap(),
+            "a"
+        );
+        assert!(scan.set_value_from_str("\\xff").is_err());
+    }
+
+    #[test]
+    pub fn test_bit_value() {
+        use super::*;
+        let mut scan = test_scan(ValueType::Bit);
+        scan.set_value_from_str("00101001").unwrap();
+        assert_eq!(scan.value, vec![0b0010_1001]);
+        assert!(scan.value_mask.is_empty());
+        assert_eq!(
+            ValueType::Bit
+                .format_value(&scan.value, &DisplayFormat::default())
+                .unwrap(),
+            "00101001"
+        );
+
+        // wildcards and missing leading bits match both 0 and 1
+        scan.set_value_from_str("1**0").unwrap();
+        assert_eq!(scan.value, vec![0b1000]);
+        assert_eq!(scan.value_mask, vec![0b1001]);
+        assert!(scan.value_matches(&[0b1000]));
+        assert!(scan.value_matches(&[0b1111_1110]));
+        assert!(!scan.value_matches(&[0b1001]));
+        assert!(!scan.value_matches(&[0b0000]));
+        scan.set_compare_mode(CompareMode::NotEqual);
+        assert!(scan.value_matches(&[0b0000]));
+
+        scan.set_compare_mode(CompareMode::AnyOf);
+        scan.set_value_from_str("1*, 1*******").unwrap();
+        assert!(scan.value_matches(&[0b10]));
+        assert!(scan.value_matches(&[0b1000_0000]));
+        assert!(!scan.value_matches(&[0b0100_0001]));
+
+        assert!(scan.set_value_from_str("2").is_err());
+        assert!(scan.set_value_from_str("101010101").is_err());
+        assert_eq!(
+            Scan::apply_step(ValueType::Bit, &[0b1111_1111], 1).unwrap(),
+            vec![0]
+        );
+    }
+
+    #[test]
+    pub fn test_struct_value() {
+        use super::*;
+        let player = StructType {
+            name: "player".to_owned(),
+            fields: vec![
+                StructField {
+                    name: "hp".to_owned(),
+                    value_type: ValueType::U16,
+                },
+                StructField {
+                    name: "x".to_owned(),
+                    value_type: ValueType::F32,
+                },
+                StructField {
+                    name: "flags".to_owned(),
+                    value_type: ValueType::Bit,
+                },
+            ],
+        };
+        assert!(player.check().is_ok());
+        let value_type = player.into_value_type();
+        assert_eq!(value_type.get_size(), 7);
+        assert_eq!(value_type.get_default_alignment(), 4);
+        assert_eq!(value_type.get_string(), "player (7B)");
+
+        let mut scan = test_scan(value_type);
+        scan.set_value_from_str("100; 1.5; 1*").unwrap();
+        assert_eq!(
+            scan.value,
+            [&[100, 0][..], &1.5f32.to_le_bytes(), &[0b10]].concat()
+        );
+        assert_eq!(scan.value_mask, [&[0xff; 6][..], &[0b10]].concat());
+        let shown = value_type.get_value_string(&scan.value).unwrap();
+        assert_eq!(shown, "hp: 100; x: 1.5; flags: 00000010");
+        let display = DisplayFormat {
+            integers: IntegerFormat::Hex,
+            thousands_separator: false,
+        };
+        assert_eq!(
+            value_type.format_value(&scan.value, &display).unwrap(),
+            "hp: 0x64; x: 1.5; flags: 00000010"
+        );
+
+        // shown values parse back, `*` fields match anything
+        scan.set_value_from_str(&shown).unwrap();
+        assert!(scan.value_mask.is_empty());
+        scan.set_value_from_str("hp: *; x: 1.5; flags: *").unwrap();
+        assert!(scan.value_matches(&[7, 7, 0, 0, 0xc0, 0x3f, 0xff]));
+        assert!(!scan.value_matches(&[7, 7, 0, 0, 0x80, 0x3f, 0xff]));
+        assert!(scan.set_value_from_str("100; 1.5").is_err());
+        assert!(
+            scan.set_value_from_str("hp: 100; y: 1.5; flags: 0")
+                .is_err()
+        );
+        assert!(scan.set_value_from_str("1, 2; 1.5; 0").is_err());
+
+        // big-endian structs swap every field on its own
+        assert_eq!(
+            Endianness::Big.convert(value_type, &[1, 2, 3, 4, 5, 6, 7]),
+            vec![2, 1, 6, 5, 4, 3, 7]
+        );
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_encodings() {
+        use super::*;
+        let mut scan = test_scan(ValueType::Latin1String);
+        scan.set_value_from_str("Café\\x00").unwrap();
+        assert_eq!(scan.value, b"Caf\xe9\x00");
+        assert_eq!(
+            ValueType::Latin1String
+                .get_value_string(&scan.value)
+                .unwrap(),
+            "Café\\x00"
+        );
+        assert!(scan.set_value_from_str("日本").is_err());
+
+        let mut scan = test_scan(ValueType::ShiftJisString);
+        scan.set_value_from_str("HP 日本").unwrap();
+        assert_eq!(scan.value, b"HP \x93\xfa\x96\x7b");
+        assert_eq!(
+            ValueType::ShiftJisString
+                .get_value_string(&scan.value)
+                .unwrap(),
+            "HP 日本"
+        );
+        // a character cut off by the read size is left out
+        assert_eq!(
+            ValueType::ShiftJisString
+                .get_value_string(b"HP \x93\xfa\x96")
+                .unwrap(),
+            "HP 日"
+        );
+        assert!(scan.set_value_from_str("Café").is_err());
+    }
+
+    #[test]
+    pub fn test_set_value_from_str_display_formats() {
+        use super::*;
+        let mut scan = test_scan(ValueType::I32);
+
+        scan.set_value_from_str("-1_234_567").unwrap();
+        assert_eq!(scan.value, (-1234567_i32).to_le_bytes().to_vec());
+
+        scan.set_value_from_str("0xffffff85").unwrap();
+        assert_eq!(scan.value, (-123_i32).to_le_bytes().to_vec());
+
+        let mut scan = test_scan(ValueType::U64);
+        scan.set_value_from_str("0x7a69").unwrap();
+        assert_eq!(scan.value, 31337_u64.to_le_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_array_value() {
+        use super::*;
+        let scan = test_scan(ValueType::I32);
+
+        let value = scan.value_from_str("10, 20,-30").unwrap();
+        let expected: Vec<u8> = [10_i32, 20, -30]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        assert_eq!(value, expected);
+        assert_eq!(ValueType::I32.get_array_len(&value), 3);
+        assert_eq!(
+            ValueType::I32.get_value_string(&value).unwrap(),
+            "10, 20, -30"
+        );
+
+        let hex = DisplayFormat {
+            integers: IntegerFormat::Hex,
+            thousands_separator: false,
+        };
+        assert_eq!(
+            ValueType::I32.format_value(&value[..8], &hex).unwrap(),
+            "0xa, 0x14"
+        );
+
+        assert!(scan.value_from_str("10,,20").is_err());
+        // strings keep their commas
+        let scan = test_scan(ValueType::String);
+        assert_eq!(scan.value_from_str("a,b").unwrap(), b"a,b".to_vec());
+    }
+
+    #[test]
+    pub fn test_string_terminator() {
+        use super::*;
+        assert_eq!(StringTerminator::None.terminate(b"ab", 5, 1), b"ab");
+        assert_eq!(StringTerminator::Nul.terminate(b"ab", 5, 1), b"ab\0");
+        assert_eq!(StringTerminator::Pad.terminate(b"ab", 5, 1), b"ab\0\0\0");
+        // nothing to clean up after strings that are at least as long
+        assert_eq!(StringTerminator::Nul.terminate(b"abcde", 5, 1), b"abcde");
+        assert_eq!(StringTerminator::Pad.terminate(b"abcdef", 5, 1), b"abcdef");
+        assert_eq!(StringTerminator::Nul.terminate(b"a\0", 6, 2), b"a\0\0\0");
+    }
+
+    #[test]
+    pub fn test_alignment() {
+        use super::*;
+        assert_eq!(ValueType::U64.get_default_alignment(), 8);
+        assert_eq!(ValueType::I32.get_default_alignment(), 4);
+        assert_eq!(ValueType::Pointer32.get_default_alignment(), 4);
+        assert_eq!(ValueType::String.get_default_alignment(), 1);
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_alignment(2).unwrap();
+        assert_eq!(scan.alignment(), 2);
+        assert!(matches!(
+            scan.set_alignment(3),
+            Err(ScanError::InvalidAlignment(3))
+        ));
+        assert_eq!(scan.alignment(), 2);
+
+        // a new type starts from its own alignment
+        scan.set_value_type(ValueType::I64, None).unwrap();
+        assert_eq!(scan.alignment(), 8);
+    }
+
+    #[test]
+    pub fn test_aligned_offsets() {
+        use super::*;
+        let offsets = |start, len, size, alignment| {
+            aligned_offsets(start, len, size, alignment).collect::<Vec<_>>()
+        };
+        assert_eq!(offsets(0x1000, 16, 4, 4), vec![0, 4, 8, 12]);
+        assert_eq!(offsets(0x1001, 16, 4, 4), vec![3, 7, 11]);
+        assert_eq!(offsets(0x1000, 6, 2, 1), vec![0, 1, 2, 3, 4]);
+        assert_eq!(offsets(0x1006, 16, 8, 8), vec![2]);
+        assert!(offsets(0x1000, 3, 4, 4).is_empty());
+    }
+
+    #[test]
+    pub fn test_scan_block_pages() {
+        use super::*;
+
+        // the page of a file mapping past the end of the file can not be read
+        let page_size = get_page_size() as usize;
+        let needle = 0xdeadbeef_u32.to_le_bytes();
+        let mut data = vec![0; page_size];
+        data[64..68].copy_from_slice(&needle);
+        data[page_size - 4..].copy_from_slice(&needle);
+        let path = std::env::temp_dir().join(format!("scan-pages-test-{}", std::process::id()));
+        fs::write(&path, &data).unwrap();
+        let file = File::open(&path).unwrap();
+        let map = unsafe { memmap2::MmapOptions::new().len(page_size * 2).map(&file) }.unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.pid = std::process::id();
+        scan.set_value_from_str("3735928559").unwrap();
+        let start = map.as_ptr() as usize;
+        assert!(scan.memory.read(scan.pid, start, page_size * 2).is_err());
+
+        let block = (start, page_size * 2, page_size * 2);
+        let (matches, bytes_read) = scan.scan_block_pages(3, block, &needle, 4);
+        assert_eq!(
+            matches.addresses,
+            vec![(start + 64) as u64, (start + page_size - 4) as u64]
+        );
+        assert_eq!(matches.regions, vec![3, 3]);
+        assert_eq!(bytes_read, page_size);
+    }
+
+    #[test]
+    pub fn test_skip_zeros() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U16);
+        scan.alignment = 2;
+        let data = [0, 0, 1, 0, 0, 0, 0, 2];
+        assert_eq!(scan.get_candidate_offsets(0, &data).count(), 4);
+
+        scan.set_skip_zeros(true);
+        let candidates: Vec<usize> = scan.get_candidate_offsets(0, &data).collect();
+        assert_eq!(candidates, vec![2, 6]);
+        // first scans drop them too, but only numeric zeros
+        scan.set_compare_mode(CompareMode::NotEqual);
+        scan.set_value_from_str("7").unwrap();
+        let matches = scan.scan_block(0, 0x1000, &data, &[7, 0], 2);
+        assert_eq!(matches.addresses, vec![0x1002, 0x1006]);
+        assert!(!scan.is_skipped_zero(&[1, 0, 0, 0]));
+
+        scan.value_type = ValueType::F32;
+        assert!(scan.is_skipped_zero(&(-0.0_f32).to_le_bytes()));
+        scan.value_type = ValueType::String;
+        assert!(!scan.is_skipped_zero(&[0, 0]));
+    }
+
+    #[test]
+    pub fn test_skip_overlapping() {
+        use super::*;
+
+        // "aaa" in "aaaaaa_aaa"
+        let mut matches = MatchStore::new(3);
+        for address in [0x10, 0x11, 0x12, 0x13, 0x17] {
+            matches.push(address, b"aaa", None, 0);
+        }
+        let kept = matches.clone().without_overlaps(3);
+        assert_eq!(kept.addresses, vec![0x10, 0x13, 0x17]);
+        assert_eq!(kept.values, b"aaaaaaaaa");
+        assert_eq!(matches.without_overlaps(1).len(), 5);
+
+        // on by default for strings only
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_value_type(ValueType::Hex, None).unwrap();
+        assert!(scan.skip_overlapping());
+        scan.set_value_type(ValueType::U8, None).unwrap();
+        assert!(!scan.skip_overlapping());
+        scan.set_skip_overlapping(true);
+        assert!(scan.skip_overlapping());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    pub fn test_skip_non_resident_pages() {
+        use super::*;
+
+        // only the second page of the mapping was ever touched
+        let page_size = get_page_size() as usize;
+        let mut map = memmap2::MmapMut::map_anon(page_size * 4).unwrap();
+        map[page_size] = 1;
+        let start = map.as_ptr() as u64;
+        let region = test_region(
+            start,
+            start + page_size as u64 * 4,
+            &[MemoryRegionPerms::Read, MemoryRegionPerms::Write],
+            None,
+        );
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.pid = std::process::id();
+        let mut stats = ScanStats::default();
+        assert_eq!(scan.get_scan_ranges(&region, &mut stats).len(), 1);
+
+        scan.set_skip_non_resident(true);
+        let page = start as usize + page_size;
+        assert_eq!(
+            scan.get_scan_ranges(&region, &mut stats),
+            vec![(page, page + page_size)]
+        );
+    }
+
+    /// Mock memory whose odd pages are swapped out, counts the page state requests
+    #[allow(dead_code)]
+    struct SwappedMemory(MockMemory, std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl crate::core::mem::MemoryBackend for SwappedMemory {
+        fn regions(
+            &self,
+            pid: u32,
+            start: Option<u64>,
+            end: Option<u64>,
+            search_perms: Option<&[super::MemoryRegionPerms]>,
+        ) -> Result<Vec<super::MemoryRegion>, super::MemoryError> {
+            self.0.regions(pid, start, end, search_perms)
+        }
+
+        fn read(&self, pid: u32, addr: usize, size: usize) -> Result<Vec<u8>, super::MemoryError> {
+            self.0.read(pid, addr, size)
+        }
+
+        fn write(&self, pid: u32, addr: usize, value: &[u8]) -> Result<(), super::MemoryError> {
+            self.0.write(pid, addr, value)
+        }
+
+        fn page_states(
+            &self,
+            _pid: u32,
+            start: u64,
+            end: u64,
+        ) -> Result<Vec<super::PageState>, super::MemoryError> {
+            self.1.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let page_size = super::get_page_size();
+            Ok((start / page_size..end.div_ceil(page_size))
+                .map(|page| match page % 2 {
+                    0 => super::PageState::Resident,
+                    _ => super::PageState::Swapped,
+                })
+                .collect())
         }
     }
 
     #[test]
-    #[ignore = "requires root"]
-    pub fn test_scan_init_success() {
+    pub fn test_scan_ranges_page_states() {
         use super::*;
-        use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
-
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap();
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
 
-        let mut proc = crate::core::utils::ChildGuard(proc);
-        let stdout = proc.0.stdout.take().expect("child had no stdout");
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
+        let page_size = get_page_size();
+        let pages = PAGE_STATE_CHUNK * 2 + 2;
+        let requests = Arc::new(AtomicUsize::new(0));
+        let mut scan = test_scan(ValueType::U32);
+        scan.memory = Box::new(SwappedMemory(
+            MockMemory(std::sync::Mutex::new(vec![])),
+            requests.clone(),
+        ));
+        let start = page_size * 16;
+        let region = test_region(
+            start,
+            start + pages * page_size,
+            &[MemoryRegionPerms::Read, MemoryRegionPerms::Write],
+            None,
+        );
 
-        reader.read_line(&mut line).unwrap();
+        // nothing to skip or count, the page table is left alone
+        let mut stats = ScanStats::default();
+        assert_eq!(
+            scan.get_scan_ranges(&region, &mut stats),
+            vec![(region.start as usize, region.end as usize)]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 0);
 
-        let hex_str = line.trim();
-        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
-            .expect("failed to parse hex");
+        scan.set_count_swapped(true);
+        assert_eq!(scan.get_scan_ranges(&region, &mut stats).len(), 1);
+        assert_eq!(stats.swapped_bytes, pages / 2 * page_size);
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
 
-        let scan = Scan::new(
-            proc.0.id(),
-            31337_u32.to_le_bytes().to_vec(),
-            ValueType::U32,
-            None,
-            None,
-            None,
-        );
-        assert!(scan.is_ok());
-        let mut scan = scan.unwrap();
-        let results = scan.init().unwrap();
-        assert_eq!(results.len(), 1);
-        let result = &results[0];
-        assert_eq!(result.address, address as u64);
+        scan.set_skip_swapped(true);
+        let ranges = scan.get_scan_ranges(&region, &mut stats);
+        assert_eq!(ranges.len() as u64, pages / 2);
         assert_eq!(
-            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
-            31337_u32
+            ranges[1],
+            (
+                (start + 2 * page_size) as usize,
+                (start + 3 * page_size) as usize
+            )
         );
     }
 
     #[test]
-    #[ignore = "requires root"]
-    pub fn test_scan_refresh_success() {
+    pub fn test_find_aligned() {
         use super::*;
-        use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
-
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap();
+        let mut data: Vec<u8> = (0..1000_u32).map(|i| (i * 7 % 251) as u8).collect();
+        for needle in [&[0xab, 0xcd][..], &[1, 2, 3, 4], &[9, 8, 7, 6, 5, 4, 3, 2]] {
+            // every offset modulo 8, across the first block boundary and in the tail
+            for at in (0..72).step_by(9).chain((900..992).step_by(9)) {
+                data[at..at + needle.len()].copy_from_slice(needle);
+            }
+            for start in [0x1000, 0x1001, 0x1006] {
+                let expected: Vec<usize> =
+                    aligned_offsets(start, data.len(), needle.len(), needle.len())
+                        .filter(|&i| data[i..i + needle.len()] == *needle)
+                        .collect();
+                assert!(!expected.is_empty());
+                assert_eq!(find_aligned(&data, start, needle), expected);
+            }
+        }
+        assert!(find_aligned(&[1, 2, 3], 0x1001, &[1, 2, 3, 4]).is_empty());
+    }
 
-        let mut proc = crate::core::utils::ChildGuard(proc);
-        let stdout = proc.0.stdout.take().expect("child had no stdout");
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
+    #[test]
+    pub fn test_write_refused_for_other_process() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        scan.pid = u32::MAX;
+        scan.identity = Some(ProcessIdentity {
+            start_time: 1,
+            exe_inode: None,
+        });
 
-        reader.read_line(&mut line).unwrap();
+        assert_eq!(
+            scan.update_value(0x1000, "1"),
+            Err(ScanError::ProcessChanged(u32::MAX))
+        );
+    }
 
-        let hex_str = line.trim();
-        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
-            .expect("failed to parse hex");
+    #[test]
+    pub fn test_common_addresses() {
+        use super::*;
+        let results = |addresses: &[u64]| -> Vec<ScanResult> {
+            addresses
+                .iter()
+                .map(|&address| ScanResult::new(address, ValueType::U32, vec![], vec![], None))
+                .collect()
+        };
+        let parent = results(&[0x3000, 0x1000, 0x2000]);
+        let worker = results(&[0x2000, 0x3000, 0x4000]);
+        let other_worker = results(&[0x3000, 0x2000]);
 
-        let scan = Scan::new(
-            proc.0.id(),
-            31337_u32.to_le_bytes().to_vec(),
-            ValueType::U32,
-            None,
-            None,
-            None,
+        assert_eq!(
+            common_addresses(&[&parent, &worker, &other_worker]),
+            vec![0x2000, 0x3000]
         );
-        assert!(scan.is_ok());
-        let mut scan = scan.unwrap();
-        let results = scan.init().unwrap();
-        assert_eq!(results.len(), 1);
-        let result = &results[0];
-        assert_eq!(result.address, address as u64);
+        assert_eq!(common_addresses(&[&parent]), vec![0x1000, 0x2000, 0x3000]);
         assert_eq!(
-            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
-            31337_u32
+            common_addresses(&[&parent, &results(&[])]),
+            Vec::<u64>::new()
         );
+        assert_eq!(common_addresses(&[]), Vec::<u64>::new());
+    }
 
-        scan.update_value(address as u64, "333333").unwrap();
+    #[test]
+    pub fn test_parse_address_list() {
+        use super::*;
+        let ranges = vec![NamedRange {
+            name: "game".to_owned(),
+            start: 0x40_0000,
+            end: 0x41_0000,
+        }];
 
-        let results = scan.refresh().unwrap();
-        assert_eq!(results.len(), 1);
-        let result = &results[0];
-        assert_eq!(result.address, address as u64);
+        let list = "# candidates\n0x1000\n\n2000 health?\n";
         assert_eq!(
-            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
-            333333_u32
+            parse_address_list(list, &ranges).map_err(|e| e.to_string()),
+            Err("Line 4 of the address list is not an address".to_owned())
+        );
+        let list = "# candidates\n0x1000\n\n2000\ngame+0x10\n";
+        assert_eq!(
+            parse_address_list(list, &ranges),
+            Ok(vec![0x1000, 0x2000, 0x40_0010])
+        );
+
+        // recorded edits and copied watchlist tables
+        let script = "game+0x20\tu32\t100\n0x3000\tstring\tabc\tbe\n";
+        assert_eq!(
+            parse_address_list(script, &ranges),
+            Ok(vec![0x40_0020, 0x3000])
         );
+        let table = "| Address | Location | Type | Value |\n|---|---|---|---|\n| 0x4000 | 0x4000 | u32 | 1 |\n";
+        assert_eq!(parse_address_list(table, &ranges), Ok(vec![0x4000]));
     }
 
     #[test]
-    #[ignore = "requires root"]
-    pub fn test_next_scan_success() {
+    pub fn test_evaluate_address() {
         use super::*;
-        use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
+        let range = |name: &str, start: u64| NamedRange {
+            name: name.to_owned(),
+            start,
+            end: start + 0x10000,
+        };
+        let ranges = vec![
+            range("heap", 0x5000_0000),
+            range("libgame.so", 0x7f00_0000_0000),
+            range("libstdc++.so.6", 0x7f10_0000_0000),
+            range("ld-linux-x86-64.so.2", 0x7f20_0000_0000),
+        ];
+
+        assert_eq!(evaluate_address("0x1000", &ranges), Ok(0x1000));
+        assert_eq!(evaluate_address("dead", &ranges), Ok(0xdead));
+        assert_eq!(evaluate_address("0XDEAD", &ranges), Ok(0xdead));
+        assert_eq!(evaluate_address("d:4096", &ranges), Ok(0x1000));
+        assert_eq!(evaluate_address("heap+d:16", &ranges), Ok(0x5000_0010));
+        assert_eq!(
+            evaluate_address("d:ff", &ranges),
+            Err(ScanError::InvalidAddress)
+        );
+        assert_eq!(
+            evaluate_address("libgame.so+0x2000", &ranges),
+            Ok(0x7f00_0000_2000)
+        );
+        assert_eq!(
+            evaluate_address("0x7f00aa000000+0x10000", &ranges),
+            Ok(0x7f00_aa01_0000)
+        );
+        assert_eq!(evaluate_address("heap + 10 - 8", &ranges), Ok(0x5000_0008));
+        assert_eq!(
+            evaluate_address("libstdc++.so.6+0x10", &ranges),
+            Ok(0x7f10_0000_0010)
+        );
+        assert_eq!(
+            evaluate_address("ld-linux-x86-64.so.2", &ranges),
+            Ok(0x7f20_0000_0000)
+        );
 
-        let proc = Command::new("./target/debug/examples/simple_program")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .unwrap();
+        assert_eq!(
+            evaluate_address("libmissing.so+0x10", &ranges),
+            Err(ScanError::InvalidAddress)
+        );
+        assert_eq!(
+            evaluate_address("libgame.so+", &ranges),
+            Err(ScanError::InvalidAddress)
+        );
+        assert_eq!(
+            evaluate_address("0x10-0x20", &ranges),
+            Err(ScanError::InvalidAddress)
+        );
+    }
 
-        let mut proc = crate::core::utils::ChildGuard(proc);
-        let stdout = proc.0.stdout.take().expect("child had no stdout");
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
+    #[test]
+    pub fn test_match_density() {
+        use super::*;
+        let region = |start, end, path| test_region(start, end, &[MemoryRegionPerms::Write], path);
+        let result = |address: u64| ScanResult::new(address, ValueType::U32, vec![], vec![], None);
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.memory_regions = vec![
+            region(0x1000, 0x2000, Some("/usr/bin/game")),
+            region(0x2000, 0x3000, None),
+            region(0x4000, 0x5000, Some("[heap]")),
+            region(0x6000, 0x7000, None),
+        ];
+        scan.results = [0x1000, 0x4000, 0x4ffc, 0x2ffc, 0x3000, 0x4100]
+            .into_iter()
+            .map(result)
+            .collect();
 
-        reader.read_line(&mut line).unwrap();
+        let density = scan.get_match_density();
+        let density: Vec<(&str, u64, usize)> = density
+            .iter()
+            .map(|(range, count)| (range.name.as_str(), range.start, *count))
+            .collect();
+        assert_eq!(
+            density,
+            vec![
+                ("[heap]", 0x4000, 3),
+                ("game", 0x1000, 1),
+                ("anon", 0x2000, 1)
+            ]
+        );
+    }
 
-        let hex_str = line.trim();
-        let address = usize::from_str_radix(hex_str.trim_start_matches("0x"), 16)
-            .expect("failed to parse hex");
+    #[test]
+    pub fn test_retain_pointers_into() {
+        use super::*;
+        let region = |start, end| test_region(start, end, &[MemoryRegionPerms::Read], None);
+        let result = |address: u64, value: Vec<u8>| {
+            ScanResult::new(address, ValueType::U64, value, vec![], None)
+        };
 
-        let scan = Scan::new(
-            proc.0.id(),
-            31337_u32.to_le_bytes().to_vec(),
+        let mut scan = test_scan(ValueType::U64);
+        scan.results = vec![
+            result(1, 0x1000_u64.to_le_bytes().to_vec()),
+            result(2, 0x1fff_u64.to_le_bytes().to_vec()),
+            result(3, 0x2000_u64.to_le_bytes().to_vec()),
+            result(4, 0_u64.to_le_bytes().to_vec()),
+            result(5, 0x5010_u64.to_le_bytes().to_vec()),
+            result(6, vec![0x10]),
+        ];
+
+        let removed =
+            scan.retain_pointers_into(&[region(0x5000, 0x6000), region(0x1000, 0x2000)], 8);
+        assert_eq!(removed, 3);
+        let addresses: Vec<u64> = scan.results.iter().map(|r| r.address).collect();
+        assert_eq!(addresses, vec![1, 2, 5]);
+
+        // 32-bit targets only look at the first 4 bytes
+        scan.results = vec![result(1, 0x1000_0000_5010_u64.to_le_bytes().to_vec())];
+        assert_eq!(scan.retain_pointers_into(&[region(0x5000, 0x6000)], 4), 0);
+    }
+
+    #[test]
+    pub fn test_watchlist_endianness() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        scan.add_to_watchlist(ScanResult::new(
+            0x1000,
             ValueType::U32,
+            0x01020304_u32.to_be_bytes().to_vec(),
+            vec![],
             None,
-            None,
-            None,
-        );
-        assert!(scan.is_ok());
-        let mut scan = scan.unwrap();
-        let results = scan.init().unwrap();
-        assert_eq!(results.len(), 1);
-        let result = &results[0];
-        assert_eq!(result.address, address as u64);
+        ));
+
+        assert_eq!(scan.watchlist[0].get_string().unwrap(), "67305985");
         assert_eq!(
-            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
-            31337_u32
+            scan.toggle_watchlist_endianness(0x1000),
+            Some(Endianness::Big)
         );
+        assert_eq!(scan.watchlist[0].get_string().unwrap(), "16909060");
+        assert_eq!(scan.get_endianness(0x1000), Endianness::Big);
+        assert_eq!(scan.get_endianness(0x2000), Endianness::Little);
+        assert_eq!(scan.toggle_watchlist_endianness(0x2000), None);
 
-        let results = scan.next_scan().unwrap();
-        assert_eq!(results.len(), 1);
-        let result = &results[0];
-        assert_eq!(result.address, address as u64);
+        // arrays are swapped element by element
+        let value = [1_u32.to_be_bytes(), 2_u32.to_be_bytes()].concat();
         assert_eq!(
-            u32::from_le_bytes(result.value.as_slice().try_into().unwrap()),
-            31337_u32
+            Endianness::Big.convert(ValueType::U32, &value),
+            [1_u32.to_le_bytes(), 2_u32.to_le_bytes()].concat()
         );
+        assert_eq!(Endianness::Big.convert(ValueType::String, b"ab"), b"ab");
+    }
 
-        write_memory_address(proc.0.id(), address, &333333_u32.to_le_bytes().to_vec()).unwrap();
+    #[test]
+    pub fn test_any_of_compare_mode() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_value_from_str("100, 250,500").unwrap();
+        scan.set_compare_mode(CompareMode::AnyOf);
+        assert!(scan.check_value().is_ok());
+        assert_eq!(scan.match_size(), 4);
+        assert!(scan.value_matches(&250_u32.to_le_bytes()));
+        assert!(scan.value_matches(&[500_u32.to_le_bytes(), [9; 4]].concat()));
+        assert!(!scan.value_matches(&251_u32.to_le_bytes()));
+        assert!(!scan.value_matches(&[0xfa, 0]));
+
+        // exact keeps reading the list as an array
+        scan.set_compare_mode(CompareMode::Exact);
+        assert_eq!(scan.match_size(), 12);
+        assert!(!scan.value_matches(&250_u32.to_le_bytes()));
+
+        let mut scan = test_scan(ValueType::U16);
+        scan.set_value_from_str("1,2").unwrap();
+        scan.set_compare_mode(CompareMode::AnyOf);
+        scan.set_endianness(Endianness::Big);
+        assert!(scan.value_matches(&[0, 2]));
+
+        let mut scan = test_scan(ValueType::String);
+        scan.set_value_from_str("a,b").unwrap();
+        scan.set_compare_mode(CompareMode::AnyOf);
+        assert_eq!(scan.check_value(), Err(ScanError::TypeMismatch));
+    }
 
-        let results = scan.next_scan().unwrap();
-        assert_eq!(results.len(), 0);
+    #[test]
+    pub fn test_rounded_compare_mode() {
+        use super::*;
+        assert_eq!(count_decimals("12"), 0);
+        assert_eq!(count_decimals("12.30"), 2);
+        assert_eq!(count_decimals("1.5, 2.25"), 2);
+        assert_eq!(count_decimals("1.5e3"), 1);
+
+        let mut scan = test_scan(ValueType::F32);
+        scan.set_compare_mode(CompareMode::Rounded);
+        scan.set_value_from_str("12.3").unwrap();
+        assert!(scan.value_matches(&12.3_f32.to_le_bytes()));
+        assert!(scan.value_matches(&12.34_f32.to_le_bytes()));
+        assert!(scan.value_matches(&12.26_f32.to_le_bytes()));
+        assert!(!scan.value_matches(&12.36_f32.to_le_bytes()));
+
+        scan.set_value_from_str("12").unwrap();
+        assert!(scan.value_matches(&12.4_f32.to_le_bytes()));
+        assert!(!scan.value_matches(&12.6_f32.to_le_bytes()));
+
+        scan.set_compare_epsilon(Some(0.01)).unwrap();
+        assert!(!scan.value_matches(&12.4_f32.to_le_bytes()));
+        assert!(scan.value_matches(&12.005_f32.to_le_bytes()));
+        assert!(scan.set_compare_epsilon(Some(-1.0)).is_err());
+        assert!(scan.set_compare_epsilon(Some(f64::NAN)).is_err());
+
+        // first and next scans both compare with the scan value
+        assert!(!scan.compare_mode.needs_previous());
+        assert!(scan.value_matches_previous(&12.0_f32.to_le_bytes(), &[0; 4]));
     }
 
     #[test]
-    pub fn test_set_value_from_str_u64_success() {
+    pub fn test_not_equal_compare_mode() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U64,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_value_from_str("100").unwrap();
+        scan.set_compare_mode(CompareMode::NotEqual);
+        assert!(scan.value_matches(&101_u32.to_le_bytes()));
+        assert!(!scan.value_matches(&100_u32.to_le_bytes()));
+        assert_eq!(scan.init().err(), Some(ScanError::NeedsPreviousScan));
+
+        // strings only compare the typed prefix when a larger read size is used
+        let mut scan = test_scan(ValueType::String);
+        scan.set_value_from_str("abc").unwrap();
+        scan.set_compare_mode(CompareMode::NotEqual);
+        assert!(scan.check_value().is_ok());
+        assert!(scan.value_matches(b"abd"));
+        assert!(!scan.value_matches(b"abcdef"));
+
+        let mut scan = test_scan(ValueType::Hex);
+        scan.set_value_from_str("beef").unwrap();
+        scan.set_compare_mode(CompareMode::NotEqual);
+        assert!(scan.value_matches(&[0xbe, 0xee]));
+        assert!(!scan.value_matches(&[0xbe, 0xef]));
+    }
 
-        let result = scan.set_value_from_str("12345");
-        assert!(result.is_ok());
-        assert_eq!(scan.value, 12345_u64.to_le_bytes().to_vec());
+    #[test]
+    pub fn test_changed_compare_modes() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        let (one, two) = (1_u32.to_le_bytes(), 2_u32.to_le_bytes());
+
+        scan.set_compare_mode(CompareMode::Changed);
+        assert!(scan.check_value().is_ok());
+        assert!(scan.value_matches_previous(&two, &one));
+        assert!(!scan.value_matches_previous(&one, &one));
+        assert_eq!(scan.init().err(), Some(ScanError::NeedsPreviousScan));
+
+        scan.set_compare_mode(CompareMode::Unchanged);
+        assert!(scan.value_matches_previous(&one, &one));
+        assert!(!scan.value_matches_previous(&two, &one));
+        assert_eq!(scan.baseline(&two, &one), two);
+
+        // compares with the value of the first scan instead of the previous one
+        scan.set_compare_mode(CompareMode::SameAsFirst);
+        assert_eq!(scan.baseline(&two, &one), one);
+        assert!(scan.value_matches_previous(&one, scan.baseline(&two, &one)));
+        assert!(!scan.value_matches_previous(&two, scan.baseline(&two, &one)));
+        assert_eq!(scan.init().err(), Some(ScanError::NeedsPreviousScan));
+
+        scan.set_compare_mode(CompareMode::Exact);
+        scan.set_value_from_str("2").unwrap();
+        assert!(scan.value_matches_previous(&two, &one));
+        assert!(!scan.value_matches_previous(&one, &one));
     }
 
     #[test]
-    pub fn test_set_value_from_str_i64_success() {
+    pub fn test_predicate() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::I64,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
 
-        let result = scan.set_value_from_str("-54321");
-        assert!(result.is_ok());
-        assert_eq!(scan.value, (-54321_i64).to_le_bytes().to_vec());
+        /// Values whose checksum byte is the sum of the others, increasing on next scans
+        struct Checksum;
+        impl ScanPredicate for Checksum {
+            fn matches(&self, bytes: &[u8], previous: Option<&[u8]>) -> bool {
+                let sum = bytes[..3].iter().fold(0_u8, |sum, b| sum.wrapping_add(*b));
+                sum == bytes[3] && previous.is_none_or(|previous| bytes > previous)
+            }
+        }
+
+        let mut scan = test_scan(ValueType::U32);
+        // the value only sets the size of the compared bytes
+        scan.set_value_from_str("0").unwrap();
+        scan.set_compare_mode(CompareMode::Increased);
+        scan.set_predicate(Some(Box::new(Checksum)));
+        let (low, high, wrong) = ([1, 2, 3, 6], [2, 2, 3, 7], [2, 2, 3, 6]);
+        assert!(scan.value_matches(&low));
+        assert!(!scan.value_matches(&wrong));
+        // only the scan value's size is given to the predicate
+        assert!(scan.value_matches(&[1, 2, 3, 6, 0xff]));
+        assert!(!scan.value_matches(&low[..3]));
+        assert!(scan.value_matches_previous(&high, &low));
+        assert!(!scan.value_matches_previous(&low, &high));
+        assert!(!scan.value_matches_previous(&wrong, &low));
+
+        // the compare mode is back without the predicate
+        scan.set_predicate(None);
+        assert!(!scan.value_matches(&low));
+        assert!(scan.value_matches_previous(&wrong, &low));
     }
 
     #[test]
-    pub fn test_set_value_from_str_u32_success() {
+    pub fn test_increased_decreased_compare_modes() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::I32);
+        let (low, high) = ((-5_i32).to_le_bytes(), 3_i32.to_le_bytes());
+
+        scan.set_compare_mode(CompareMode::Increased);
+        assert!(scan.check_value().is_ok());
+        assert!(scan.value_matches_previous(&high, &low));
+        assert!(!scan.value_matches_previous(&low, &high));
+        assert!(!scan.value_matches_previous(&low, &low));
+
+        scan.set_compare_mode(CompareMode::Decreased);
+        assert!(scan.value_matches_previous(&low, &high));
+        assert!(!scan.value_matches_previous(&high, &low));
+
+        // large unsigned values are compared as numbers, not bytes
+        let mut scan = test_scan(ValueType::U128);
+        scan.set_compare_mode(CompareMode::Increased);
+        assert!(scan.value_matches_previous(&u128::MAX.to_le_bytes(), &256_u128.to_le_bytes()));
+
+        let mut scan = test_scan(ValueType::F32);
+        scan.set_compare_mode(CompareMode::Decreased);
+        assert!(scan.value_matches_previous(&0.5_f32.to_le_bytes(), &1.5_f32.to_le_bytes()));
+        assert!(!scan.value_matches_previous(&f32::NAN.to_le_bytes(), &1.5_f32.to_le_bytes()));
+
+        let mut scan = test_scan(ValueType::U16);
+        scan.set_endianness(Endianness::Big);
+        scan.set_compare_mode(CompareMode::Increased);
+        assert!(scan.value_matches_previous(&[1, 0], &[0, 255]));
+
+        let mut scan = test_scan(ValueType::String);
+        scan.set_compare_mode(CompareMode::Increased);
+        assert_eq!(scan.check_value(), Err(ScanError::TypeMismatch));
+    }
 
-        let result = scan.set_value_from_str("31337");
-        assert!(result.is_ok());
-        assert_eq!(scan.value, 31337_u32.to_le_bytes().to_vec());
+    #[test]
+    pub fn test_changed_by_compare_modes() {
+        use super::*;
+        let mut scan = test_scan(ValueType::I32);
+        scan.set_value_from_str("25").unwrap();
+        let value = |v: i32| v.to_le_bytes();
+
+        scan.set_compare_mode(CompareMode::DecreasedBy);
+        assert!(scan.value_matches_previous(&value(75), &value(100)));
+        assert!(scan.value_matches_previous(&value(-30), &value(-5)));
+        assert!(!scan.value_matches_previous(&value(76), &value(100)));
+        assert!(!scan.value_matches_previous(&value(125), &value(100)));
+        assert_eq!(scan.init().err(), Some(ScanError::NeedsPreviousScan));
+
+        scan.set_compare_mode(CompareMode::IncreasedBy);
+        assert!(scan.value_matches_previous(&value(125), &value(100)));
+        assert!(!scan.value_matches_previous(&value(75), &value(100)));
+
+        let mut scan = test_scan(ValueType::F32);
+        scan.set_value_from_str("0.1").unwrap();
+        scan.set_compare_mode(CompareMode::IncreasedBy);
+        let (previous, current) = (1.2_f32, 1.2_f32 + 0.1);
+        assert!(scan.value_matches_previous(&current.to_le_bytes(), &previous.to_le_bytes()));
+
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_compare_mode(CompareMode::IncreasedBy);
+        assert_eq!(scan.check_value(), Err(ScanError::EmptyValue));
     }
 
     #[test]
-    pub fn test_set_value_from_str_i32_success() {
+    pub fn test_big_endian_scan() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::I32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
+        scan.set_value_from_str("258").unwrap();
+        assert!(scan.value_matches(&258_u32.to_le_bytes()));
+        assert!(!scan.value_matches(&258_u32.to_be_bytes()));
+
+        scan.set_endianness(Endianness::Big);
+        assert!(scan.value_matches(&258_u32.to_be_bytes()));
+        assert!(!scan.value_matches(&258_u32.to_le_bytes()));
+        scan.set_compare_mode(CompareMode::WithinPercent);
+        assert!(scan.value_matches(&260_u32.to_be_bytes()));
+
+        // writes outside the watchlist follow the scan
+        assert_eq!(scan.get_endianness(0x1000), Endianness::Big);
+    }
 
-        let result = scan.set_value_from_str("-999");
-        assert!(result.is_ok());
-        assert_eq!(scan.value, (-999_i32).to_le_bytes().to_vec());
+    #[test]
+    pub fn test_format_value() {
+        use super::*;
+        let hex = DisplayFormat {
+            integers: IntegerFormat::Hex,
+            thousands_separator: true,
+        };
+        let grouped = DisplayFormat {
+            integers: IntegerFormat::Decimal,
+            thousands_separator: true,
+        };
+
+        let value = (-1234567_i32).to_le_bytes();
+        assert_eq!(
+            ValueType::I32
+                .format_value(&value, &DisplayFormat::default())
+                .unwrap(),
+            "-1234567"
+        );
+        assert_eq!(
+            ValueType::I32.format_value(&value, &grouped).unwrap(),
+            "-1_234_567"
+        );
+        assert_eq!(
+            ValueType::I32.format_value(&value, &hex).unwrap(),
+            "0xffed2979"
+        );
+        assert_eq!(
+            ValueType::U64
+                .format_value(&100_u64.to_le_bytes(), &grouped)
+                .unwrap(),
+            "100"
+        );
+        assert_eq!(
+            ValueType::String.format_value(b"1234", &grouped).unwrap(),
+            "1234"
+        );
+        assert_eq!(ValueType::Hex.format_value(&[0xab], &hex).unwrap(), "ab");
     }
 
     #[test]
     pub fn test_set_value_from_str_invalid_value() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
         let result = scan.set_value_from_str("not_a_number");
         assert!(result.is_err());
@@ -836,18 +5587,7 @@ mod test {
     #[test]
     pub fn test_set_value_from_str_overflow() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
         // This value is too large for u32
         let result = scan.set_value_from_str("99999999999999");
@@ -1022,6 +5762,40 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn test_set_address_range_mismatch() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        scan.start_address = Some(0x1000);
+        scan.end_address = Some(0x2000);
+
+        let result = scan.set_address_range(Some(0x3000), Some(0x2000));
+        assert!(matches!(result.unwrap_err(), ScanError::AddressMismatch));
+        assert_eq!(scan.start_address, Some(0x1000));
+        assert_eq!(scan.end_address, Some(0x2000));
+    }
+
+    #[test]
+    pub fn test_address_range_32bit() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+        scan.pointer_width = 4;
+
+        assert!(scan.set_end_address("0x100000000").is_ok());
+        assert!(matches!(
+            scan.set_end_address("0x100000001"),
+            Err(ScanError::AddressOutOfRange(0x100000001))
+        ));
+        assert!(matches!(
+            scan.set_address_range(Some(0x7fff_0000_0000), None),
+            Err(ScanError::AddressOutOfRange(0x7fff_0000_0000))
+        ));
+        assert_eq!(scan.end_address, Some(0x100000000));
+
+        scan.pointer_width = 8;
+        assert!(scan.set_end_address("0x7fff00000000").is_ok());
+    }
+
     #[test]
     #[ignore = "requires root"]
     pub fn test_set_end_address_success() {
@@ -1192,21 +5966,10 @@ mod test {
     #[test]
     pub fn test_add_to_watchlist_success() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
-        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![]);
-        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![]);
+        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
+        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![], None);
 
         scan.add_to_watchlist(result1);
         assert_eq!(scan.watchlist.len(), 1);
@@ -1220,20 +5983,9 @@ mod test {
     #[test]
     pub fn test_add_to_watchlist_duplicate_ignores() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
-        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![]);
+        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
 
         scan.add_to_watchlist(result.clone());
         assert_eq!(scan.watchlist.len(), 1);
@@ -1246,21 +5998,10 @@ mod test {
     #[test]
     pub fn test_remove_from_watchlist_success() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
-        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![]);
-        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![]);
+        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
+        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![], None);
 
         scan.add_to_watchlist(result1.clone());
         scan.add_to_watchlist(result2.clone());
@@ -1272,23 +6013,58 @@ mod test {
     }
 
     #[test]
-    pub fn test_remove_from_watchlist_not_present() {
+    pub fn test_result_module_name() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
+        let path = |path: Option<&str>| {
+            ScanResult::new(
+                0x1000,
+                ValueType::U32,
+                vec![0; 4],
+                vec![],
+                path.map(String::from),
+            )
         };
 
-        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![]);
-        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![]);
+        assert_eq!(
+            path(Some("/usr/lib/libc.so.6")).get_module_name(),
+            Some("libc.so.6")
+        );
+        assert_eq!(path(Some("[heap]")).get_module_name(), Some("[heap]"));
+        assert_eq!(path(None).get_module_name(), None);
+    }
+
+    #[test]
+    pub fn test_remove_addresses_from_watchlist() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+
+        for address in [0x1000, 0x2000, 0x3000] {
+            scan.add_to_watchlist(ScanResult::new(
+                address,
+                ValueType::U32,
+                vec![0; 4],
+                vec![],
+                None,
+            ));
+        }
+
+        let removed =
+            scan.remove_addresses_from_watchlist(&HashSet::from([0x1000, 0x3000, 0x4000]));
+        assert_eq!(removed, 2);
+        assert_eq!(scan.watchlist.len(), 1);
+        assert_eq!(scan.watchlist[0].address, 0x2000);
+
+        scan.clear_watchlist();
+        assert!(scan.watchlist.is_empty());
+    }
+
+    #[test]
+    pub fn test_remove_from_watchlist_not_present() {
+        use super::*;
+        let mut scan = test_scan(ValueType::U32);
+
+        let result1 = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
+        let result2 = ScanResult::new(0x2000, ValueType::U32, vec![5, 6, 7, 8], vec![], None);
 
         scan.add_to_watchlist(result1);
         assert_eq!(scan.watchlist.len(), 1);
@@ -1302,26 +6078,268 @@ mod test {
     #[test]
     pub fn test_remove_from_watchlist_empty() {
         use super::*;
-        let mut scan = Scan {
-            pid: 0,
-            value: vec![],
-            value_type: ValueType::U32,
-            results: vec![],
-            watchlist: vec![],
-            start_address: None,
-            end_address: None,
-            read_size: None,
-            memory_regions: vec![],
-            memory_permissions: vec![],
-        };
+        let mut scan = test_scan(ValueType::U32);
 
-        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![]);
+        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
 
         // Try to remove from empty watchlist
         scan.remove_from_watchlist(result.address);
         assert_eq!(scan.watchlist.len(), 0);
     }
 
+    #[test]
+    pub fn test_result_is_static() {
+        use super::*;
+        let module = Some(String::from("/usr/bin/game"));
+        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], module);
+        assert!(result.is_static());
+
+        let heap = Some(String::from("[heap]"));
+        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], heap);
+        assert!(!result.is_static());
+
+        let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
+        assert!(!result.is_static());
+
+        for path in [
+            "/memfd:wayland-shm (deleted)",
+            "/memfd:jit",
+            "/dev/dri/renderD128",
+            "/dev/zero",
+            "/usr/lib/libgame.so (deleted)",
+        ] {
+            let path = Some(String::from(path));
+            let result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], path);
+            assert!(!result.is_static());
+        }
+    }
+
+    #[test]
+    pub fn test_result_location() {
+        use super::*;
+        let mut result = ScanResult::new(
+            0x1000,
+            ValueType::U32,
+            vec![0; 4],
+            vec![],
+            Some(String::from("/usr/lib/libgame.so")),
+        );
+        assert_eq!(result.get_location(), "libgame.so");
+        result.file_offset = Some(0x1c010);
+        assert_eq!(result.get_location(), "libgame.so@0x1c010");
+
+        let heap = ScanResult::new(
+            0x1000,
+            ValueType::U32,
+            vec![0; 4],
+            vec![],
+            Some("[heap]".into()),
+        );
+        assert_eq!(heap.get_location(), "[heap]");
+        let anon = ScanResult::new(0x1000, ValueType::U32, vec![0; 4], vec![], None);
+        assert_eq!(anon.get_location(), "anon");
+    }
+
+    #[test]
+    pub fn test_result_has_changed() {
+        use super::*;
+        let mut result = ScanResult::new(0x1000, ValueType::U32, vec![1, 2, 3, 4], vec![], None);
+        assert!(!result.has_changed());
+
+        result.previous_value = std::mem::replace(&mut result.value, vec![5, 6, 7, 8]);
+        assert!(result.has_changed());
+    }
+
+    #[test]
+    pub fn test_value_matches_within_percent() {
+        use super::*;
+        let mut scan = test_scan(ValueType::I32);
+        scan.value = 100_i32.to_le_bytes().to_vec();
+        scan.set_compare_mode(CompareMode::WithinPercent);
+        scan.set_compare_percent(10.0).unwrap();
+
+        assert!(scan.value_matches(&100_i32.to_le_bytes()));
+        assert!(scan.value_matches(&90_i32.to_le_bytes()));
+        assert!(scan.value_matches(&110_i32.to_le_bytes()));
+        assert!(!scan.value_matches(&111_i32.to_le_bytes()));
+        assert!(!scan.value_matches(&(-100_i32).to_le_bytes()));
+        assert!(!scan.value_matches(&[100]));
+
+        assert!(scan.set_compare_percent(-1.0).is_err());
+        assert!(scan.set_compare_percent(f64::NAN).is_err());
+
+        scan.set_compare_mode(CompareMode::Exact);
+        assert!(!scan.value_matches(&101_i32.to_le_bytes()));
+        assert!(scan.value_matches(&100_i32.to_le_bytes()));
+
+        let mut scan = test_scan(ValueType::String);
+        scan.value = b"abc".to_vec();
+        scan.set_compare_mode(CompareMode::WithinPercent);
+        assert!(matches!(scan.check_value(), Err(ScanError::TypeMismatch)));
+    }
+
+    #[test]
+    pub fn test_result_first_value() {
+        use super::*;
+        let mut result = ScanResult::new(
+            0x1000,
+            ValueType::I32,
+            10_i32.to_le_bytes().to_vec(),
+            vec![],
+            None,
+        );
+        assert_eq!(result.get_drift(), Some(0));
+
+        result.previous_value = std::mem::replace(&mut result.value, 4_i32.to_le_bytes().to_vec());
+        result.previous_value = std::mem::replace(&mut result.value, 7_i32.to_le_bytes().to_vec());
+        assert_eq!(
+            result.get_first_string(&DisplayFormat::default()).unwrap(),
+            "10"
+        );
+        assert_eq!(result.get_drift(), Some(-3));
+
+        let result = ScanResult::new(0x1000, ValueType::String, b"abc".to_vec(), vec![], None);
+        assert_eq!(result.get_drift(), None);
+    }
+
+    #[test]
+    pub fn test_result_views() {
+        use super::*;
+
+        let result = |address: u64| {
+            ScanResult::new(
+                address,
+                ValueType::U32,
+                vec![0; 4],
+                vec![MemoryRegionPerms::Write],
+                None,
+            )
+        };
+        let addresses =
+            |results: &Vec<ScanResult>| -> Vec<u64> { results.iter().map(|r| r.address).collect() };
+
+        let mut scan = test_scan(ValueType::U32);
+        assert!(matches!(
+            scan.set_result_view(ResultSetView::Intersection),
+            Err(ScanError::NothingPinned)
+        ));
+
+        scan.results = vec![result(1), result(2), result(3)];
+        scan.pin_results();
+        scan.results = vec![result(2), result(3), result(4)];
+
+        let view = scan.set_result_view(ResultSetView::Intersection).unwrap();
+        assert_eq!(addresses(view), vec![2, 3]);
+        let view = scan.set_result_view(ResultSetView::OnlyPinned).unwrap();
+        assert_eq!(addresses(view), vec![1]);
+        let view = scan.set_result_view(ResultSetView::OnlyCurrent).unwrap();
+        assert_eq!(addresses(view), vec![4]);
+        let view = scan.set_result_view(ResultSetView::Current).unwrap();
+        assert_eq!(addresses(view), vec![2, 3, 4]);
+
+        // pinning a combined view keeps what is shown
+        scan.set_result_view(ResultSetView::OnlyPinned).unwrap();
+        scan.pin_results();
+        assert_eq!(scan.result_view, ResultSetView::Current);
+        assert_eq!(addresses(scan.pinned.as_ref().unwrap()), vec![1]);
+    }
+
+    #[test]
+    pub fn test_pointer_value() {
+        use super::*;
+        let scan = test_scan(ValueType::Pointer);
+
+        let value = scan.value_from_str("0x7ffd1000").unwrap();
+        assert_eq!(value, 0x7ffd1000_usize.to_le_bytes().to_vec());
+        assert_eq!(scan.value_from_str("7ffd1000").unwrap(), value);
+        assert!(scan.value_from_str("-1").is_err());
+
+        assert_eq!(
+            ValueType::Pointer.get_value_string(&value).unwrap(),
+            "0x7ffd1000"
+        );
+        let grouped = DisplayFormat {
+            integers: IntegerFormat::Decimal,
+            thousands_separator: true,
+        };
+        assert_eq!(
+            ValueType::Pointer.format_value(&value, &grouped).unwrap(),
+            "0x7ffd1000"
+        );
+
+        let value = Scan::apply_step(ValueType::Pointer, &value, 8).unwrap();
+        assert_eq!(value, 0x7ffd1008_usize.to_le_bytes().to_vec());
+
+        // 32-bit targets store 4 byte pointers
+        let scan = test_scan(ValueType::Pointer.for_pointer_width(4));
+        assert_eq!(scan.value_type, ValueType::Pointer32);
+        let value = scan.value_from_str("0xf7ffd000").unwrap();
+        assert_eq!(value, 0xf7ffd000_u32.to_le_bytes().to_vec());
+        assert_eq!(scan.value_type.get_pointer(&value), Some(0xf7ffd000));
+        assert_eq!(scan.value_type.get_string(), "pointer (4B)");
+        assert!(scan.value_from_str("0x100000000").is_err());
+        let value = Scan::apply_step(ValueType::Pointer32, &value, 0x0800_3000).unwrap();
+        assert_eq!(value, 0_u32.to_le_bytes().to_vec());
+        assert_eq!(ValueType::U32.for_pointer_width(4), ValueType::U32);
+    }
+
+    #[test]
+    #[ignore = "requires root"]
+    pub fn test_check_pointer() {
+        use super::*;
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let proc = Command::new("./target/debug/examples/simple_program")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let mut proc = crate::core::utils::ChildGuard(proc);
+        let stdout = proc.0.stdout.take().expect("child had no stdout");
+        let mut line = String::new();
+        let mut reader = BufReader::new(stdout);
+        reader.read_line(&mut line).unwrap();
+        let address = usize::from_str_radix(line.trim().trim_start_matches("0x"), 16).unwrap();
+
+        let mut scan = test_scan(ValueType::Pointer);
+        scan.pid = proc.0.id();
+        assert!(
+            scan.check_pointer(ValueType::Pointer, &address.to_le_bytes())
+                .is_ok()
+        );
+        assert!(
+            scan.check_pointer(ValueType::Pointer, &0_usize.to_le_bytes())
+                .is_ok()
+        );
+        assert_eq!(
+            scan.check_pointer(ValueType::Pointer, &1_usize.to_le_bytes()),
+            Err(ScanError::UnmappedPointer(1))
+        );
+    }
+
+    #[test]
+    pub fn test_apply_step() {
+        use super::*;
+        let value = Scan::apply_step(ValueType::U32, &41_u32.to_le_bytes(), 1).unwrap();
+        assert_eq!(value, 42_u32.to_le_bytes().to_vec());
+
+        let value = Scan::apply_step(ValueType::I64, &5_i64.to_le_bytes(), -10).unwrap();
+        assert_eq!(value, (-5_i64).to_le_bytes().to_vec());
+
+        // wraps like the target's own arithmetic would
+        let value = Scan::apply_step(ValueType::U32, &0_u32.to_le_bytes(), -1).unwrap();
+        assert_eq!(value, u32::MAX.to_le_bytes().to_vec());
+
+        let value = Scan::apply_step(ValueType::I32, &i32::MAX.to_le_bytes(), 1).unwrap();
+        assert_eq!(value, i32::MIN.to_le_bytes().to_vec());
+
+        let result = Scan::apply_step(ValueType::String, b"abc", 1);
+        assert!(matches!(result.unwrap_err(), ScanError::TypeMismatch));
+    }
+
     #[test]
     #[ignore = "requires root"]
     pub fn test_string_search_without_read_size() {
@@ -1529,6 +6547,7 @@ mod test {
             ValueType::U32,
             vec![10, 20, 30, 40],
             vec![],
+            None,
         );
         scan.add_to_watchlist(fake_result);
         assert_eq!(scan.watchlist.len(), 2);
@@ -1622,4 +6641,124 @@ mod test {
         assert!(readonly.perms.contains(&MemoryRegionPerms::Read));
         assert!(!readonly.perms.contains(&MemoryRegionPerms::Write));
     }
+
+    #[test]
+    pub fn test_memory_backend() {
+        use super::*;
+
+        let mut data = vec![0; 0x2000];
+        data[0x10..0x14].copy_from_slice(&31337_u32.to_le_bytes());
+        data[0x1ff0..0x1ff4].copy_from_slice(&31337_u32.to_le_bytes());
+        let memory = MockMemory(std::sync::Mutex::new(data));
+
+        let mut scan = Scan::with_backend(
+            Box::new(memory),
+            std::process::id(),
+            31337_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scan.memory.named_ranges(scan.pid).unwrap()[0].name, "heap");
+
+        scan.init().unwrap();
+        let addresses: Vec<u64> = scan.results.iter().map(|r| r.address).collect();
+        assert_eq!(
+            addresses,
+            vec![MOCK_BASE as u64 + 0x10, MOCK_BASE as u64 + 0x1ff0]
+        );
+
+        scan.update_value(MOCK_BASE as u64 + 0x10, "42").unwrap();
+        scan.set_value_from_str("42").unwrap();
+        scan.next_scan().unwrap();
+        assert_eq!(scan.results.len(), 1);
+        assert_eq!(scan.results[0].address, MOCK_BASE as u64 + 0x10);
+        assert_eq!(scan.results[0].value, 42_u32.to_le_bytes());
+    }
+
+    #[test]
+    pub fn test_step_value() {
+        use super::*;
+
+        let mut data = vec![0; 0x1000];
+        data[0x10..0x14].copy_from_slice(&[200, 7, 7, 7]);
+        let memory = MockMemory(std::sync::Mutex::new(data));
+        let mut scan = Scan::with_backend(
+            Box::new(memory),
+            std::process::id(),
+            vec![],
+            ValueType::U32,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        // a u8 watchlist entry stays a single byte while the scan looks for u32 values
+        let address = MOCK_BASE as u64 + 0x10;
+        assert_eq!(
+            scan.step_value(address, ValueType::U8, 1).unwrap(),
+            vec![201]
+        );
+        assert_eq!(
+            scan.memory.read(scan.pid, address as usize, 4).unwrap(),
+            vec![201, 7, 7, 7]
+        );
+        assert_eq!(
+            scan.step_value(address, ValueType::String, 1),
+            Err(ScanError::TypeMismatch)
+        );
+    }
+
+    #[test]
+    pub fn test_step_refreshed_watchlist() {
+        use super::*;
+
+        let mut data = vec![0; 0x1000];
+        data[0x10..0x14].copy_from_slice(&[200, 7, 7, 7]);
+        let memory = MockMemory(std::sync::Mutex::new(data));
+        let mut scan = Scan::with_backend(
+            Box::new(memory),
+            std::process::id(),
+            1_u32.to_le_bytes().to_vec(),
+            ValueType::U32,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        // added while the scan looked for u8 values, the scan has moved on to u32 since
+        let address = MOCK_BASE as u64 + 0x10;
+        scan.watchlist.push(ScanResult::new(
+            address,
+            ValueType::U8,
+            vec![200],
+            vec![],
+            Some("[heap]".to_owned()),
+        ));
+
+        scan.step_value(address, ValueType::U8, 1).unwrap();
+        scan.refresh().unwrap();
+        let entry = &scan.watchlist[0];
+        assert_eq!(
+            (entry.value_type, entry.value.clone()),
+            (ValueType::U8, vec![201])
+        );
+
+        scan.step_value(address, entry.value_type, 1).unwrap();
+        scan.update_value(address, "210").unwrap();
+        scan.refresh().unwrap();
+        assert_eq!(scan.watchlist[0].value, vec![210]);
+        assert_eq!(
+            scan.memory.read(scan.pid, address as usize, 4).unwrap(),
+            vec![210, 7, 7, 7]
+        );
+        assert_eq!(
+            scan.update_value(address, "256"),
+            Err(ScanError::InvalidValue)
+        );
+    }
 }
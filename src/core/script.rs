@@ -0,0 +1,292 @@
+use std::{
+    fmt::Display,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::core::{
+    mem::{MemoryBackend, MemoryError, NamedRange},
+    scan::{Endianness, ScanError, ValueType},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    Io(String),
+    Parse(usize, String),
+    UnknownModule(usize, String),
+    Value(usize, ScanError),
+    Memory(MemoryError),
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not access the script: {e}"),
+            Self::Parse(line, content) => write!(f, "Line {line}: can not parse `{content}`"),
+            Self::UnknownModule(line, module) => {
+                write!(f, "Line {line}: {module} is not loaded by the target")
+            }
+            Self::Value(line, e) => write!(f, "Line {line}: {e}"),
+            Self::Memory(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Where an edit was written, addresses inside modules are kept relative
+/// so the script still works after ASLR moved them
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    Module(String, u64),
+    Absolute(u64),
+}
+
+impl Location {
+    /// `path` is the mapping the address belongs to, `ranges` are the named ranges of the target
+    pub fn new(address: u64, path: Option<&str>, ranges: &[NamedRange]) -> Location {
+        let module = path
+            .filter(|path| path.starts_with('/'))
+            .and_then(|path| path.rsplit('/').next())
+            .and_then(|name| ranges.iter().find(|r| r.name == name));
+
+        match module {
+            Some(range) => Location::Module(range.name.clone(), address - range.start),
+            None => Location::Absolute(address),
+        }
+    }
+
+    pub fn resolve(&self, ranges: &[NamedRange]) -> Option<u64> {
+        match self {
+            Location::Module(name, offset) => ranges
+                .iter()
+                .find(|r| r.name == *name)
+                .map(|r| r.start + offset),
+            Location::Absolute(address) => Some(*address),
+        }
+    }
+
+    fn parse(location: &str) -> Option<Location> {
+        let parse_hex = |hex: &str| u64::from_str_radix(hex.strip_prefix("0x")?, 16).ok();
+
+        // module names can contain `+` themselves, libstdc++.so.6 for one
+        match location.rsplit_once('+') {
+            Some((name, offset)) => Some(Location::Module(name.to_owned(), parse_hex(offset)?)),
+            None => Some(Location::Absolute(parse_hex(location)?)),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Module(name, offset) => write!(f, "{name}+0x{offset:x}"),
+            Location::Absolute(address) => write!(f, "0x{address:x}"),
+        }
+    }
+}
+
+/// A single recorded write, stored as a tab separated line:
+/// `location  type  value  [be]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptEntry {
+    pub location: Location,
+    pub value_type: ValueType,
+    /// Value as shown in the watchlist, it is parsed like typed input when replayed
+    pub value: String,
+    pub endianness: Endianness,
+}
+
+impl ScriptEntry {
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{}\t{}\t{}",
+            self.location,
+            self.value_type.get_name(),
+            self.value
+        );
+        if self.endianness == Endianness::Big {
+            line.push_str("\tbe");
+        }
+        line
+    }
+
+    pub fn parse(line: &str) -> Option<ScriptEntry> {
+        let mut columns = line.split('\t');
+        let location = Location::parse(columns.next()?)?;
+        let value_type = ValueType::from_name(columns.next()?)?;
+        let value = columns.next()?.to_owned();
+        let endianness = match columns.next() {
+            None => Endianness::Little,
+            Some("be") => Endianness::Big,
+            Some(_) => return None,
+        };
+
+        Some(ScriptEntry {
+            location,
+            value_type,
+            value,
+            endianness,
+        })
+    }
+}
+
+/// Script that edits of the process `name` are recorded to, in the working directory
+pub fn get_script_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("{}-edits.tsv", name.replace(['/', ' '], "_")))
+}
+
+/// Appends an edit to the script at `path`
+pub fn record(path: &Path, entry: &ScriptEntry) -> Result<(), ScriptError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| ScriptError::Io(format!("{}: {e}", path.display())))?;
+    writeln!(file, "{}", entry.to_line()).map_err(|e| ScriptError::Io(e.to_string()))
+}
+
+/// Writes every edit of the script to the process `pid` through `memory`, returns how many
+/// were written. Nothing is written unless every line can be parsed and resolved.
+pub fn replay(memory: &dyn MemoryBackend, pid: u32, path: &Path) -> Result<usize, ScriptError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ScriptError::Io(format!("{}: {e}", path.display())))?;
+    let ranges = memory.named_ranges(pid).map_err(ScriptError::Memory)?;
+
+    let writes = resolve_script(&content, &ranges)?;
+    for (address, value) in &writes {
+        memory
+            .write(pid, *address as usize, value)
+            .map_err(ScriptError::Memory)?;
+    }
+    Ok(writes.len())
+}
+
+/// Addresses and bytes to write for every line, empty lines and `#` comments are skipped
+fn resolve_script(
+    content: &str,
+    ranges: &[NamedRange],
+) -> Result<Vec<(u64, Vec<u8>)>, ScriptError> {
+    let mut writes = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let line_number = i + 1;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let entry = ScriptEntry::parse(line)
+            .ok_or_else(|| ScriptError::Parse(line_number, line.to_owned()))?;
+        let address = entry
+            .location
+            .resolve(ranges)
+            .ok_or_else(|| ScriptError::UnknownModule(line_number, entry.location.to_string()))?;
+        let value = entry
+            .value_type
+            .parse_value(&entry.value)
+            .map_err(|e| ScriptError::Value(line_number, e))?;
+        writes.push((address, entry.endianness.convert(entry.value_type, &value)));
+    }
+    Ok(writes)
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn ranges() -> Vec<NamedRange> {
+        let range = |name: &str, start: u64| NamedRange {
+            name: name.to_owned(),
+            start,
+            end: start + 0x10000,
+        };
+        vec![
+            range("heap", 0x5000_0000),
+            range("game", 0x40_0000),
+            range("libstdc++.so.6", 0x7f00_0000_0000),
+        ]
+    }
+
+    #[test]
+    pub fn test_location() {
+        let ranges = ranges();
+
+        let location = Location::new(0x40_2010, Some("/opt/game"), &ranges);
+        assert_eq!(location, Location::Module("game".to_owned(), 0x2010));
+        assert_eq!(location.to_string(), "game+0x2010");
+        assert_eq!(location.resolve(&ranges), Some(0x40_2010));
+
+        // the heap moves between runs, but there is nothing better to anchor to
+        let location = Location::new(0x5000_0010, Some("[heap]"), &ranges);
+        assert_eq!(location, Location::Absolute(0x5000_0010));
+        assert_eq!(
+            Location::new(0x1234, None, &ranges),
+            Location::Absolute(0x1234)
+        );
+
+        assert_eq!(
+            Location::parse("libstdc++.so.6+0x10"),
+            Some(Location::Module("libstdc++.so.6".to_owned(), 0x10))
+        );
+        assert_eq!(Location::parse("0x1234"), Some(Location::Absolute(0x1234)));
+        assert_eq!(Location::parse("game+10"), None);
+        assert_eq!(
+            Location::Module("gone.so".to_owned(), 0).resolve(&ranges),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_script_entry() {
+        let entry = ScriptEntry {
+            location: Location::Module("game".to_owned(), 0x2010),
+            value_type: ValueType::U32,
+            value: "100".to_owned(),
+            endianness: Endianness::Little,
+        };
+        assert_eq!(entry.to_line(), "game+0x2010\tu32\t100");
+        assert_eq!(ScriptEntry::parse(&entry.to_line()), Some(entry.clone()));
+
+        let entry = ScriptEntry {
+            value_type: ValueType::String,
+            value: "a b\\x09c".to_owned(),
+            endianness: Endianness::Big,
+            ..entry
+        };
+        assert_eq!(entry.to_line(), "game+0x2010\tstring\ta b\\x09c\tbe");
+        assert_eq!(ScriptEntry::parse(&entry.to_line()), Some(entry));
+
+        assert_eq!(ScriptEntry::parse("game+0x10\tf128\t1"), None);
+        assert_eq!(ScriptEntry::parse("game+0x10\tu32\t1\tmiddle"), None);
+        assert_eq!(ScriptEntry::parse("game+0x10\tu32"), None);
+    }
+
+    #[test]
+    pub fn test_resolve_script() {
+        let ranges = ranges();
+        let script =
+            "# recorded edits\n\ngame+0x10\tu32\t7\ngame+0x20\tu32\t0x10\tbe\n0x1000\thex\tff00\n";
+
+        let writes = resolve_script(script, &ranges).unwrap();
+        assert_eq!(
+            writes,
+            vec![
+                (0x40_0010, 7_u32.to_le_bytes().to_vec()),
+                (0x40_0020, 0x10_u32.to_be_bytes().to_vec()),
+                (0x1000, vec![0xff, 0x00]),
+            ]
+        );
+
+        assert_eq!(
+            resolve_script("game+0x10\tu32\t7\ngone.so+0x10\tu32\t1", &ranges),
+            Err(ScriptError::UnknownModule(2, "gone.so+0x10".to_owned()))
+        );
+        assert_eq!(
+            resolve_script("game+0x10\tu32\tseven", &ranges),
+            Err(ScriptError::Value(1, ScanError::InvalidValue))
+        );
+        assert!(matches!(
+            resolve_script("game 0x10 u32 7", &ranges),
+            Err(ScriptError::Parse(1, _))
+        ));
+    }
+}
@@ -1,8 +1,66 @@
+use std::path::Path;
+
 mod core;
+mod repl;
 mod tui;
 
 fn main() {
-    if let Err(e) = tui::run() {
-        panic!("{}", e);
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("replay") => replay(&args[2..]),
+        Some("dump") => dump(&args[2..]),
+        Some("repl") => {
+            if let Err(e) = repl::run() {
+                panic!("{}", e);
+            }
+        }
+        _ => {
+            if let Err(e) = tui::run() {
+                panic!("{}", e);
+            }
+        }
+    }
+}
+
+/// Memory access set in the config file, the same backend the scans use
+fn configured_backend() -> Box<dyn core::mem::MemoryBackend> {
+    let config = core::config::Config::load().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        core::config::Config::default()
+    });
+    config.scan.backend.unwrap_or_default().backend()
+}
+
+/// `cheat-engine-rs dump <pid> <file>` saves the memory of a process for offline scans
+fn dump(args: &[String]) {
+    let (Some(pid), Some(file)) = (args.first().and_then(|pid| pid.parse().ok()), args.get(1))
+    else {
+        eprintln!("Usage: cheat-engine-rs dump <pid> <file>");
+        std::process::exit(2);
+    };
+
+    match core::dump::write_dump(configured_backend().as_ref(), pid, Path::new(file)) {
+        Ok(count) => println!("Dumped {count} regions of process {pid} to {file}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `cheat-engine-rs replay <script> <pid>` writes recorded edits without the TUI
+fn replay(args: &[String]) {
+    let (Some(script), Some(pid)) = (args.first(), args.get(1).and_then(|pid| pid.parse().ok()))
+    else {
+        eprintln!("Usage: cheat-engine-rs replay <script> <pid>");
+        std::process::exit(2);
+    };
+
+    match core::script::replay(configured_backend().as_ref(), pid, Path::new(script)) {
+        Ok(count) => println!("Wrote {count} values to process {pid}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
     }
 }
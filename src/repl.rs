@@ -0,0 +1,368 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use crate::core::{
+    config::Config,
+    dump::{DUMP_PID, DumpMemory},
+    scan::{DisplayFormat, Scan, ScanResult, ValueType, common_addresses, evaluate_address},
+};
+
+/// Results printed after a scan, `list` shows more
+const PRINTED_RESULTS: usize = 20;
+
+const HELP: &str = "\
+attach <pid> [pid...]     attach to one or more processes, scans run in all of them
+load <file> [base]        scan a memory dump instead, a single region dump needs its base address
+scan <type> <value>       first scan, type is one of u64 i64 u128 i128 u32 i32 u16 i16 u8 i8 f32 f64 pointer pointer32 string widestring latin1 shiftjis hex bit
+next <value>              keep the results that now hold <value>
+refresh                   re-read the values of the results
+list [count]              print the results of every process
+common [count]            print the addresses that are a result in every process
+write <address> <value>   write to an address in every process, `module+offset` works too
+help                      show this help
+quit                      leave the repl";
+
+#[derive(Debug, Clone, PartialEq)]
+enum ReplCommand {
+    Attach(Vec<u32>),
+    Load(String, Option<u64>),
+    Scan(ValueType, String),
+    Next(String),
+    Refresh,
+    List(usize),
+    Common(usize),
+    Write(String, String),
+    Help,
+    Quit,
+}
+
+/// Parses one input line, the value is the rest of the line so strings can contain spaces
+fn parse_command(line: &str) -> Result<ReplCommand, String> {
+    let line = line.trim();
+    let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+    let args = args.trim();
+
+    let command = match name {
+        "attach" => ReplCommand::Attach(
+            args.split_whitespace()
+                .map(|pid| pid.parse().map_err(|_| format!("Invalid pid: `{pid}`")))
+                .collect::<Result<_, _>>()?,
+        ),
+        "load" => {
+            let (file, base) = args.split_once(' ').unwrap_or((args, ""));
+            let base = match base.trim() {
+                "" => None,
+                base => Some(
+                    u64::from_str_radix(base.trim_start_matches("0x"), 16)
+                        .map_err(|_| format!("Invalid base address: `{base}`"))?,
+                ),
+            };
+            ReplCommand::Load(file.to_owned(), base)
+        }
+        "scan" => {
+            let (value_type, value) = args.split_once(' ').unwrap_or((args, ""));
+            let value_type = ValueType::from_name(value_type)
+                .ok_or_else(|| format!("Unknown type: `{value_type}`"))?;
+            ReplCommand::Scan(value_type, value.trim_start().to_owned())
+        }
+        "next" => ReplCommand::Next(args.to_owned()),
+        "refresh" => ReplCommand::Refresh,
+        "list" if args.is_empty() => ReplCommand::List(usize::MAX),
+        "list" => ReplCommand::List(
+            args.parse()
+                .map_err(|_| format!("Invalid count: `{args}`"))?,
+        ),
+        "common" if args.is_empty() => ReplCommand::Common(usize::MAX),
+        "common" => ReplCommand::Common(
+            args.parse()
+                .map_err(|_| format!("Invalid count: `{args}`"))?,
+        ),
+        "write" => {
+            let (address, value) = args
+                .split_once(' ')
+                .ok_or("Usage: write <address> <value>")?;
+            ReplCommand::Write(address.to_owned(), value.trim_start().to_owned())
+        }
+        "help" | "?" => ReplCommand::Help,
+        "quit" | "exit" => ReplCommand::Quit,
+        _ => return Err(format!("Unknown command: `{name}`, try `help`")),
+    };
+
+    if let ReplCommand::Scan(_, value) | ReplCommand::Next(value) = &command
+        && value.is_empty()
+    {
+        return Err(String::from("A value is required"));
+    }
+    if command == ReplCommand::Attach(vec![]) {
+        return Err(String::from("Usage: attach <pid> [pid...]"));
+    }
+    if command == ReplCommand::Load(String::new(), None) {
+        return Err(String::from("Usage: load <file> [base]"));
+    }
+    Ok(command)
+}
+
+fn format_result(result: &ScanResult) -> String {
+    format!(
+        "0x{:x} {} | {}",
+        result.address,
+        result.get_location(),
+        result
+            .get_display_string(&DisplayFormat::default())
+            .unwrap_or("TypeMismatch".to_owned())
+    )
+}
+
+fn format_results(results: &[ScanResult], count: usize) -> String {
+    let mut output = format!("{} results", results.len());
+    for result in results.iter().take(count) {
+        output.push('\n');
+        output.push_str(&format_result(result));
+    }
+    if results.len() > count {
+        output.push_str("\n...");
+    }
+    output
+}
+
+/// One scan per attached process, they all run the same commands
+#[derive(Default)]
+struct Repl {
+    scans: Vec<Scan>,
+}
+
+impl Repl {
+    /// Runs `f` for every process, the output of a single process is printed as is
+    /// and the output of several gets a `pid` header each
+    fn for_each_scan(
+        &mut self,
+        mut f: impl FnMut(&mut Scan) -> Result<String, String>,
+    ) -> Result<String, String> {
+        if self.scans.is_empty() {
+            return Err(String::from("Attach to a process or load a dump first"));
+        }
+        if let [scan] = self.scans.as_mut_slice() {
+            return f(scan);
+        }
+
+        let outputs: Vec<String> = self
+            .scans
+            .iter_mut()
+            .map(|scan| {
+                let output = f(scan).unwrap_or_else(|e| format!("error: {e}"));
+                format!("pid {}: {output}", scan.pid)
+            })
+            .collect();
+        Ok(outputs.join("\n"))
+    }
+
+    fn execute(&mut self, command: ReplCommand) -> Result<String, String> {
+        match command {
+            ReplCommand::Attach(pids) => {
+                // memory is accessed the way the config file asks for, like in the TUI
+                let backend = Config::load()
+                    .map_err(|e| e.to_string())?
+                    .scan
+                    .backend
+                    .unwrap_or_default();
+                let scans = pids
+                    .iter()
+                    .map(|&pid| {
+                        Scan::with_backend(
+                            backend.backend(),
+                            pid,
+                            vec![],
+                            ValueType::U32,
+                            None,
+                            None,
+                            None,
+                        )
+                        .map_err(|e| format!("Could not attach to {pid}: {e}"))
+                    })
+                    .collect::<Result<_, _>>()?;
+                self.scans = scans;
+                Ok(format!("Attached to {}", join_pids(&pids)))
+            }
+            ReplCommand::Load(file, base) => {
+                let path = Path::new(&file);
+                let memory = match base {
+                    Some(base) => DumpMemory::open_region(path, base),
+                    None => DumpMemory::open(path),
+                }
+                .map_err(|e| e.to_string())?;
+                let scan = Scan::with_backend(
+                    Box::new(memory),
+                    DUMP_PID,
+                    vec![],
+                    ValueType::U32,
+                    None,
+                    None,
+                    None,
+                )
+                .map_err(|e| e.to_string())?;
+                self.scans = vec![scan];
+                Ok(format!("Loaded {file}, writes only change the loaded copy"))
+            }
+            ReplCommand::Scan(value_type, value) => self.for_each_scan(|scan| {
+                let pointer_width = scan.pointer_width();
+                scan.set_value_type(value_type.for_pointer_width(pointer_width), Some(&value))
+                    .map_err(|e| e.to_string())?;
+                let results = scan.init().map_err(|e| e.to_string())?;
+                Ok(format_results(results, PRINTED_RESULTS))
+            }),
+            ReplCommand::Next(value) => self.for_each_scan(|scan| {
+                scan.set_value_from_str(&value).map_err(|e| e.to_string())?;
+                let results = scan.next_scan().map_err(|e| e.to_string())?;
+                Ok(format_results(results, PRINTED_RESULTS))
+            }),
+            ReplCommand::Refresh => self.for_each_scan(|scan| {
+                let results = scan.refresh().map_err(|e| e.to_string())?;
+                Ok(format_results(results, PRINTED_RESULTS))
+            }),
+            ReplCommand::List(count) => {
+                self.for_each_scan(|scan| Ok(format_results(&scan.results, count)))
+            }
+            ReplCommand::Common(count) => {
+                let result_sets: Vec<&[ScanResult]> = self
+                    .scans
+                    .iter()
+                    .map(|scan| scan.results.as_slice())
+                    .collect();
+                if result_sets.len() < 2 {
+                    return Err(String::from("Attach to at least two processes first"));
+                }
+                let common = common_addresses(&result_sets);
+                let mut output =
+                    format!("{} addresses are a result in every process", common.len());
+                for address in common.iter().take(count) {
+                    output.push_str(&format!("\n0x{address:x}"));
+                }
+                if common.len() > count {
+                    output.push_str("\n...");
+                }
+                Ok(output)
+            }
+            ReplCommand::Write(address, value) => self.for_each_scan(|scan| {
+                let ranges = scan.named_ranges().map_err(|e| e.to_string())?;
+                let address = evaluate_address(&address, &ranges).map_err(|e| e.to_string())?;
+                scan.update_value(address, &value)
+                    .map_err(|e| e.to_string())?;
+                Ok(format!("0x{address:x} set to {value}"))
+            }),
+            ReplCommand::Help => Ok(HELP.to_owned()),
+            ReplCommand::Quit => Ok(String::new()),
+        }
+    }
+}
+
+fn join_pids(pids: &[u32]) -> String {
+    pids.iter()
+        .map(|pid| pid.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Line based alternative to the TUI for plain SSH sessions and serial consoles,
+/// prompts are left out when stdin is not a terminal so commands can be piped in
+pub fn run() -> io::Result<()> {
+    use std::io::IsTerminal;
+
+    let interactive = io::stdin().is_terminal();
+    let mut repl = Repl::default();
+    let mut stdout = io::stdout();
+    let prompt = |stdout: &mut io::Stdout| {
+        if interactive {
+            write!(stdout, "> ")?;
+            stdout.flush()?;
+        }
+        Ok::<(), io::Error>(())
+    };
+
+    prompt(&mut stdout)?;
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            match parse_command(&line) {
+                Ok(ReplCommand::Quit) => break,
+                Ok(command) => match repl.execute(command) {
+                    Ok(output) => writeln!(stdout, "{output}")?,
+                    Err(e) => writeln!(stdout, "error: {e}")?,
+                },
+                Err(e) => writeln!(stdout, "error: {e}")?,
+            }
+        }
+        prompt(&mut stdout)?;
+    }
+    Ok(())
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    pub fn test_parse_command() {
+        assert_eq!(
+            parse_command("attach 123"),
+            Ok(ReplCommand::Attach(vec![123]))
+        );
+        assert_eq!(
+            parse_command("attach 123 456"),
+            Ok(ReplCommand::Attach(vec![123, 456]))
+        );
+        assert_eq!(parse_command("common 3"), Ok(ReplCommand::Common(3)));
+        assert_eq!(
+            parse_command("load game.dump"),
+            Ok(ReplCommand::Load("game.dump".to_owned(), None))
+        );
+        assert_eq!(
+            parse_command("load heap.bin 0x55d0c2a3b000"),
+            Ok(ReplCommand::Load(
+                "heap.bin".to_owned(),
+                Some(0x55d0c2a3b000)
+            ))
+        );
+        assert_eq!(
+            parse_command("scan u32 31337"),
+            Ok(ReplCommand::Scan(ValueType::U32, "31337".to_owned()))
+        );
+        assert_eq!(
+            parse_command("  scan string hello world "),
+            Ok(ReplCommand::Scan(
+                ValueType::String,
+                "hello world".to_owned()
+            ))
+        );
+        assert_eq!(
+            parse_command("scan f32 1.5"),
+            Ok(ReplCommand::Scan(ValueType::F32, "1.5".to_owned()))
+        );
+        assert_eq!(
+            parse_command("next 31338"),
+            Ok(ReplCommand::Next("31338".to_owned()))
+        );
+        assert_eq!(parse_command("list"), Ok(ReplCommand::List(usize::MAX)));
+        assert_eq!(parse_command("list 5"), Ok(ReplCommand::List(5)));
+        assert_eq!(
+            parse_command("write libgame.so+0x10 42"),
+            Ok(ReplCommand::Write(
+                "libgame.so+0x10".to_owned(),
+                "42".to_owned()
+            ))
+        );
+        assert_eq!(parse_command("exit"), Ok(ReplCommand::Quit));
+
+        assert!(parse_command("attach me").is_err());
+        assert!(parse_command("attach").is_err());
+        assert!(parse_command("attach 123 me").is_err());
+        assert!(parse_command("load").is_err());
+        assert!(parse_command("load heap.bin base").is_err());
+        assert!(parse_command("scan f16 1.5").is_err());
+        assert!(parse_command("scan u32").is_err());
+        assert!(parse_command("next").is_err());
+        assert!(parse_command("write 0x1000").is_err());
+        assert!(parse_command("jump").is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     error::Error,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
@@ -8,8 +11,19 @@ use crate::tui::utils::cursor;
 use crate::{
     core::{
         self,
+        audit::{AuditAction, AuditLog},
+        config::Config,
+        mem::{
+            MemoryError, MemoryRegion, MemoryRegionPerms, NamedRange, RegionStats,
+            get_memory_regions, get_named_ranges, get_region_stats,
+        },
         proc::{ProcInfo, get_list},
-        scan::{Scan, ScanError, ValueType},
+        rpc::{RpcCall, RpcError, RpcOutcome},
+        scan::{
+            CompareMode, DEFAULT_COMPARE_PERCENT, ProgressCallback, Scan, ScanError, ScanProgress,
+            StringTerminator, StructType, ValueType,
+        },
+        script::{self, Location, ScriptEntry},
     },
     tui::utils,
 };
@@ -20,11 +34,20 @@ use ratatui::{
     widgets::{ListState, ScrollbarState},
 };
 
+/// How often the progress of a running scan is redrawn
+const SCAN_PROGRESS_REDRAW: Duration = Duration::from_millis(50);
+
 #[derive(Clone, PartialEq)]
 pub enum CurrentScreen {
     ProcessList,
     Scan,
+    MemoryMap,
+    RegionPresets,
+    MatchDensity,
     ValueEditing,
+    ImportAddresses,
+    ClearingWatchlist,
+    MacosSetup,
     Exiting,
 }
 
@@ -36,6 +59,9 @@ pub enum SelectedInput {
     EndAddress,
     ResultValue,
     ReadSize,
+    ComparePercent,
+    Step,
+    ImportPath,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,12 +69,103 @@ pub enum ScanViewWidget {
     ScanResults,
     ValueInput,
     PermissionsCheckbox,
+    HeapStackCheckbox,
+    CompareModeSelect,
+    AlignmentSelect,
+    ComparePercent,
     ValueTypeSelect,
     ReadSize,
+    BigEndianCheckbox,
     StartAddressInput,
     EndAddressInput,
+    ScanOptions,
     AppMessage,
     WatchList,
+    StepInput,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScanOption {
+    SkipSwapped,
+    SkipNonResident,
+    ExcludeSharedLibs,
+    ExcludeSharedMappings,
+    SkipFileBacked,
+    SkipZeros,
+    SkipOverlapping,
+    PauseTarget,
+    WatchSingleResult,
+    NulTerminateStrings,
+    PadStrings,
+}
+
+impl ScanOption {
+    pub fn get_string(&self) -> &str {
+        match self {
+            ScanOption::SkipSwapped => "Skip swapped pages",
+            ScanOption::SkipNonResident => "Skip non-resident memory",
+            ScanOption::ExcludeSharedLibs => "Exclude shared libraries",
+            ScanOption::ExcludeSharedMappings => "Exclude shared mappings",
+            ScanOption::SkipFileBacked => "Skip file-backed mappings",
+            ScanOption::SkipZeros => "Skip zero values",
+            ScanOption::SkipOverlapping => "Skip overlapping matches",
+            ScanOption::PauseTarget => "Pause the process while scanning",
+            ScanOption::WatchSingleResult => "Watch last remaining result",
+            ScanOption::NulTerminateStrings => "NUL-terminate shorter strings",
+            ScanOption::PadStrings => "Pad shorter strings with NUL",
+        }
+    }
+}
+
+/// Regions the permissions selector of the scan view scans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegionPerms {
+    Writable,
+    Readable,
+    /// Readable and writable regions without code
+    Data,
+    Code,
+}
+
+impl RegionPerms {
+    pub fn get_string(&self) -> &str {
+        match self {
+            RegionPerms::Writable => "W",
+            RegionPerms::Readable => "R+W",
+            RegionPerms::Data => "R+W -X",
+            RegionPerms::Code => "X",
+        }
+    }
+
+    pub fn next(&self) -> RegionPerms {
+        match self {
+            RegionPerms::Writable => RegionPerms::Readable,
+            RegionPerms::Readable => RegionPerms::Data,
+            RegionPerms::Data => RegionPerms::Code,
+            RegionPerms::Code => RegionPerms::Writable,
+        }
+    }
+
+    fn search_perms(&self) -> Vec<MemoryRegionPerms> {
+        match self {
+            RegionPerms::Writable => vec![MemoryRegionPerms::Write],
+            RegionPerms::Readable | RegionPerms::Data => {
+                vec![MemoryRegionPerms::Write, MemoryRegionPerms::Read]
+            }
+            RegionPerms::Code => vec![MemoryRegionPerms::Execute],
+        }
+    }
+
+    /// Selector state for the permissions of the config file
+    fn from_config(perms: &[MemoryRegionPerms]) -> RegionPerms {
+        if perms == [MemoryRegionPerms::Execute] {
+            RegionPerms::Code
+        } else if perms.contains(&MemoryRegionPerms::Read) {
+            RegionPerms::Readable
+        } else {
+            RegionPerms::Writable
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,6 +174,18 @@ pub enum ProcessListWidget {
     ProcessFilter,
 }
 
+/// Row of the results list, results are listed under their module when grouping is on
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultRow {
+    Group {
+        name: String,
+        count: usize,
+        expanded: bool,
+    },
+    /// Index into the scan results
+    Result(usize),
+}
+
 #[derive(Clone, PartialEq)]
 pub enum InputMode {
     Normal,
@@ -89,6 +218,56 @@ pub enum AppAction {
     New,
     Refresh,
     Next,
+    Repeat,
+}
+
+/// Matches a next scan starts from, after an unknown initial value scan every candidate
+/// counts as a result
+fn previous_count(scan: &Scan) -> usize {
+    if scan.has_snapshot() {
+        scan.stats.matches
+    } else {
+        scan.match_count()
+    }
+}
+
+/// Scan running on the worker thread, with what is needed to report it once it is done
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScanJob {
+    First {
+        value: String,
+    },
+    Unknown,
+    Next {
+        value: String,
+        previous_count: usize,
+    },
+}
+
+impl ScanJob {
+    /// Returns the number of matches, or candidates for an unknown initial value scan
+    fn run(&self, scan: &mut Scan) -> Result<usize, ScanError> {
+        match self {
+            ScanJob::First { .. } => {
+                scan.init()?;
+            }
+            ScanJob::Unknown => return scan.init_unknown(),
+            ScanJob::Next { .. } => {
+                scan.next_scan()?;
+            }
+        }
+        Ok(scan.match_count())
+    }
+}
+
+/// The scan is owned by the worker thread until it hands it back
+pub struct RunningScan {
+    job: ScanJob,
+    handle: JoinHandle<(Scan, Result<usize, ScanError>)>,
+    progress_receiver: Receiver<ScanProgress>,
+    pub progress: ScanProgress,
+    /// JSON-RPC request that started the scan, answered once it is done
+    rpc_call: Option<RpcCall>,
 }
 
 // Command pattern for user actions
@@ -112,18 +291,43 @@ pub enum Command {
     // Screen commands
     ShowProcessList,
     SelectProcess,
+    ShowMemoryMap,
+    ShowRegionPresets,
+    SelectRegionPreset,
+    ShowMatchDensity,
+    SelectMatchRegion,
 
     // Scan commands
     NewScan,
     NextScan,
     RefreshScan,
-    ToggleReadWrite,
+    RepeatScan,
+    ToggleCheckbox,
+    ToggleChangedFirst,
+    ToggleGroupResults,
+    ToggleStats,
+    ToggleIntegerFormat,
+    PinResults,
+    CycleResultView,
+    ReloadRegions,
 
     // Result commands
     AddToWatchlist,
     RemoveFromWatchlist,
+    ClearWatchlist,
+    ConfirmClearWatchlist,
     EditValue,
     CopyValue,
+    CopyWatchlist,
+    ImportAddresses,
+    CopyPtraceFix,
+    CopyCodesign,
+    IncrementValue,
+    DecrementValue,
+    ToggleEndianness,
+    FilterValidPointers,
+    ToggleRecording,
+    ExportAuditLog,
 
     // List commands
     MoveUp,
@@ -137,6 +341,31 @@ pub enum Command {
     CancelQuit,
 }
 
+impl Command {
+    /// Commands that don't need the scan, the others wait until a running scan is done
+    fn works_while_scanning(&self) -> bool {
+        matches!(
+            self,
+            Command::NextWidget
+                | Command::PrevWidget
+                | Command::ExitInsertMode
+                | Command::InsertChar(_)
+                | Command::DeleteChar
+                | Command::MoveCursorLeft
+                | Command::MoveCursorRight
+                | Command::ToggleStats
+                | Command::ToggleIntegerFormat
+                | Command::MoveUp
+                | Command::MoveDown
+                | Command::MoveToTop
+                | Command::MoveToBottom
+                | Command::Quit
+                | Command::ConfirmQuit
+                | Command::CancelQuit
+        )
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Direction {
     Up,
@@ -171,6 +400,11 @@ pub struct KeyBindings {
     // Screen-specific bindings
     process_list_normal: HashMap<KeyPress, Command>,
     scan_view_normal: HashMap<KeyPress, Command>,
+    memory_map_normal: HashMap<KeyPress, Command>,
+    region_presets_normal: HashMap<KeyPress, Command>,
+    match_density_normal: HashMap<KeyPress, Command>,
+    clearing_watchlist_screen: HashMap<KeyPress, Command>,
+    macos_setup_screen: HashMap<KeyPress, Command>,
     exiting_screen: HashMap<KeyPress, Command>,
     insert_mode: HashMap<KeyPress, Command>,
     // Global bindings (work across all screens)
@@ -182,6 +416,11 @@ impl KeyBindings {
         let mut bindings = KeyBindings {
             process_list_normal: HashMap::new(),
             scan_view_normal: HashMap::new(),
+            memory_map_normal: HashMap::new(),
+            region_presets_normal: HashMap::new(),
+            match_density_normal: HashMap::new(),
+            clearing_watchlist_screen: HashMap::new(),
+            macos_setup_screen: HashMap::new(),
             exiting_screen: HashMap::new(),
             insert_mode: HashMap::new(),
             global: HashMap::new(),
@@ -223,6 +462,10 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
             Command::MoveToBottom,
         );
+        self.process_list_normal.insert(
+            KeyPress::new(KeyCode::Char('L'), KeyModifiers::SHIFT),
+            Command::ExportAuditLog,
+        );
         self.process_list_normal.insert(
             KeyPress::new(KeyCode::Char('r'), KeyModifiers::NONE),
             Command::ShowProcessList,
@@ -231,6 +474,10 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Enter, KeyModifiers::NONE),
             Command::SelectProcess,
         );
+        self.process_list_normal.insert(
+            KeyPress::new(KeyCode::Char('f'), KeyModifiers::NONE),
+            Command::CopyPtraceFix,
+        );
         self.process_list_normal.insert(
             KeyPress::new(KeyCode::Tab, KeyModifiers::NONE),
             Command::NextWidget,
@@ -253,9 +500,41 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Char('r'), KeyModifiers::NONE),
             Command::RefreshScan,
         );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('.'), KeyModifiers::NONE),
+            Command::RepeatScan,
+        );
         self.scan_view_normal.insert(
             KeyPress::new(KeyCode::Char(' '), KeyModifiers::NONE),
-            Command::ToggleReadWrite,
+            Command::ToggleCheckbox,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('v'), KeyModifiers::NONE),
+            Command::ToggleChangedFirst,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('o'), KeyModifiers::NONE),
+            Command::ToggleGroupResults,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('i'), KeyModifiers::NONE),
+            Command::ToggleStats,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('x'), KeyModifiers::NONE),
+            Command::ToggleIntegerFormat,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            Command::PinResults,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('M'), KeyModifiers::SHIFT),
+            Command::ReloadRegions,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('b'), KeyModifiers::NONE),
+            Command::CycleResultView,
         );
         self.scan_view_normal.insert(
             KeyPress::new(KeyCode::Char('w'), KeyModifiers::NONE),
@@ -265,10 +544,42 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Char('d'), KeyModifiers::NONE),
             Command::RemoveFromWatchlist,
         );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('D'), KeyModifiers::SHIFT),
+            Command::ClearWatchlist,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('e'), KeyModifiers::NONE),
+            Command::ToggleEndianness,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('P'), KeyModifiers::SHIFT),
+            Command::FilterValidPointers,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('R'), KeyModifiers::SHIFT),
+            Command::ToggleRecording,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('L'), KeyModifiers::SHIFT),
+            Command::ExportAuditLog,
+        );
         self.scan_view_normal.insert(
             KeyPress::new(KeyCode::Char('u'), KeyModifiers::NONE),
             Command::EditValue,
         );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('+'), KeyModifiers::NONE),
+            Command::IncrementValue,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('+'), KeyModifiers::SHIFT),
+            Command::IncrementValue,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('-'), KeyModifiers::NONE),
+            Command::DecrementValue,
+        );
         self.scan_view_normal.insert(
             KeyPress::new(KeyCode::Char('c'), KeyModifiers::NONE),
             Command::CopyValue,
@@ -277,6 +588,18 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Char('y'), KeyModifiers::NONE),
             Command::CopyValue,
         );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('C'), KeyModifiers::SHIFT),
+            Command::CopyWatchlist,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('I'), KeyModifiers::SHIFT),
+            Command::ImportAddresses,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('f'), KeyModifiers::NONE),
+            Command::CopyPtraceFix,
+        );
         self.scan_view_normal.insert(
             KeyPress::new(KeyCode::Tab, KeyModifiers::NONE),
             Command::NextWidget,
@@ -313,6 +636,140 @@ impl KeyBindings {
             KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
             Command::MoveToBottom,
         );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('m'), KeyModifiers::NONE),
+            Command::ShowMemoryMap,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('p'), KeyModifiers::NONE),
+            Command::ShowRegionPresets,
+        );
+        self.scan_view_normal.insert(
+            KeyPress::new(KeyCode::Char('h'), KeyModifiers::NONE),
+            Command::ShowMatchDensity,
+        );
+
+        // Memory map bindings (normal mode)
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Down, KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Char('k'), KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Up, KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Command::MoveToBottom,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Char('r'), KeyModifiers::NONE),
+            Command::ShowMemoryMap,
+        );
+        self.memory_map_normal.insert(
+            KeyPress::new(KeyCode::Esc, KeyModifiers::NONE),
+            Command::GoBack,
+        );
+
+        // Region preset picker bindings (normal mode)
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Down, KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Char('k'), KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Up, KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Command::MoveToBottom,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Enter, KeyModifiers::NONE),
+            Command::SelectRegionPreset,
+        );
+        self.region_presets_normal.insert(
+            KeyPress::new(KeyCode::Esc, KeyModifiers::NONE),
+            Command::GoBack,
+        );
+
+        // Match density bindings (normal mode)
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Down, KeyModifiers::NONE),
+            Command::MoveDown,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Char('k'), KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Up, KeyModifiers::NONE),
+            Command::MoveUp,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Command::MoveToBottom,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Enter, KeyModifiers::NONE),
+            Command::SelectMatchRegion,
+        );
+        self.match_density_normal.insert(
+            KeyPress::new(KeyCode::Esc, KeyModifiers::NONE),
+            Command::GoBack,
+        );
+
+        // Watchlist clearing confirmation bindings
+        self.clearing_watchlist_screen.insert(
+            KeyPress::new(KeyCode::Char('y'), KeyModifiers::NONE),
+            Command::ConfirmClearWatchlist,
+        );
+        self.clearing_watchlist_screen.insert(
+            KeyPress::new(KeyCode::Enter, KeyModifiers::NONE),
+            Command::ConfirmClearWatchlist,
+        );
+        self.clearing_watchlist_screen.insert(
+            KeyPress::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            Command::GoBack,
+        );
+        self.clearing_watchlist_screen.insert(
+            KeyPress::new(KeyCode::Esc, KeyModifiers::NONE),
+            Command::GoBack,
+        );
+
+        // macOS setup screen bindings
+        self.macos_setup_screen.insert(
+            KeyPress::new(KeyCode::Char('f'), KeyModifiers::NONE),
+            Command::CopyCodesign,
+        );
+        self.macos_setup_screen.insert(
+            KeyPress::new(KeyCode::Enter, KeyModifiers::NONE),
+            Command::GoBack,
+        );
+        self.macos_setup_screen.insert(
+            KeyPress::new(KeyCode::Esc, KeyModifiers::NONE),
+            Command::GoBack,
+        );
 
         // Exiting screen bindings
         self.exiting_screen.insert(
@@ -398,6 +855,13 @@ impl KeyBindings {
             InputMode::Normal => match screen {
                 CurrentScreen::ProcessList => self.process_list_normal.get(&key_press).cloned(),
                 CurrentScreen::Scan => self.scan_view_normal.get(&key_press).cloned(),
+                CurrentScreen::MemoryMap => self.memory_map_normal.get(&key_press).cloned(),
+                CurrentScreen::RegionPresets => self.region_presets_normal.get(&key_press).cloned(),
+                CurrentScreen::MatchDensity => self.match_density_normal.get(&key_press).cloned(),
+                CurrentScreen::ClearingWatchlist => {
+                    self.clearing_watchlist_screen.get(&key_press).cloned()
+                }
+                CurrentScreen::MacosSetup => self.macos_setup_screen.get(&key_press).cloned(),
                 _ => None,
             },
         }
@@ -430,6 +894,9 @@ pub struct InputBuffers {
     pub end_address: String,
     pub result_value: String,
     pub read_size: String,
+    pub compare_percent: String,
+    pub step: String,
+    pub import_path: String,
 }
 
 impl InputBuffers {
@@ -441,6 +908,9 @@ impl InputBuffers {
             end_address: String::new(),
             result_value: String::new(),
             read_size: String::new(),
+            compare_percent: DEFAULT_COMPARE_PERCENT.to_string(),
+            step: String::from("1"),
+            import_path: String::new(),
         }
     }
 
@@ -452,6 +922,9 @@ impl InputBuffers {
             SelectedInput::EndAddress => &mut self.end_address,
             SelectedInput::ResultValue => &mut self.result_value,
             SelectedInput::ReadSize => &mut self.read_size,
+            SelectedInput::ComparePercent => &mut self.compare_percent,
+            SelectedInput::Step => &mut self.step,
+            SelectedInput::ImportPath => &mut self.import_path,
         }
     }
 
@@ -463,6 +936,9 @@ impl InputBuffers {
             SelectedInput::EndAddress => &self.end_address,
             SelectedInput::ResultValue => &self.result_value,
             SelectedInput::ReadSize => &self.read_size,
+            SelectedInput::ComparePercent => &self.compare_percent,
+            SelectedInput::Step => &self.step,
+            SelectedInput::ImportPath => &self.import_path,
         }
     }
 
@@ -477,6 +953,10 @@ pub struct ListStates {
     pub value_type: ListState,
     pub scan_results: ListState,
     pub scan_watchlist: ListState,
+    pub scan_options: ListState,
+    pub memory_map: ListState,
+    pub region_presets: ListState,
+    pub match_density: ListState,
 }
 
 impl ListStates {
@@ -486,6 +966,10 @@ impl ListStates {
             value_type: ListState::default(),
             scan_results: ListState::default(),
             scan_watchlist: ListState::default(),
+            scan_options: ListState::default(),
+            memory_map: ListState::default(),
+            region_presets: ListState::default(),
+            match_density: ListState::default(),
         }
     }
 }
@@ -495,6 +979,7 @@ pub struct ScrollStates {
     pub proc_list_vertical: ScrollbarState,
     pub scan_results_vertical: ScrollbarState,
     pub scan_watchlist_vertical: ScrollbarState,
+    pub memory_map_vertical: ScrollbarState,
 }
 
 impl ScrollStates {
@@ -503,6 +988,7 @@ impl ScrollStates {
             proc_list_vertical: ScrollbarState::default(),
             scan_results_vertical: ScrollbarState::default(),
             scan_watchlist_vertical: ScrollbarState::default(),
+            memory_map_vertical: ScrollbarState::default(),
         }
     }
 }
@@ -524,11 +1010,17 @@ impl WidgetSelection {
                 ScanViewWidget::ScanResults,
                 ScanViewWidget::ValueInput,
                 ScanViewWidget::PermissionsCheckbox,
+                ScanViewWidget::HeapStackCheckbox,
+                ScanViewWidget::CompareModeSelect,
+                ScanViewWidget::AlignmentSelect,
                 ScanViewWidget::ValueTypeSelect,
+                ScanViewWidget::BigEndianCheckbox,
                 ScanViewWidget::StartAddressInput,
                 ScanViewWidget::EndAddressInput,
+                ScanViewWidget::ScanOptions,
                 ScanViewWidget::AppMessage,
                 ScanViewWidget::WatchList,
+                ScanViewWidget::StepInput,
             ],
             scan_view_selected_widget: ScanViewWidget::ValueInput,
             scan_view_selected_widget_index: 1,
@@ -574,51 +1066,308 @@ pub struct App {
     pub ui: UiState,
     pub scan: Option<core::scan::Scan>,
     pub proc_list: Vec<core::proc::ProcInfo>,
+    pub memory_map: Vec<(MemoryRegion, Option<RegionStats>)>,
+    pub region_presets: Vec<NamedRange>,
+    /// Regions of the last scan with their number of results, densest first
+    pub match_density: Vec<(NamedRange, usize)>,
     pub selected_value_type: usize,
     pub selected_process: Option<ProcInfo>,
     pub selected_value: Option<core::scan::ScanResult>,
     pub value_types: Vec<ValueType>,
+    /// Pointer size of the selected process in bytes
+    pub pointer_width: usize,
+    pub scan_options: Vec<ScanOption>,
     pub app_message: AppMessage,
     pub app_action: Option<AppAction>,
+    pub running_scan: Option<RunningScan>,
+    /// Last first or next scan and its compare mode, `Command::RepeatScan` runs it again
+    pub last_scan: Option<(ScanJob, CompareMode)>,
     pub key_bindings: KeyBindings,
-    pub include_readonly_regions: bool,
+    pub region_perms: RegionPerms,
+    /// First scans only read the heap, stacks and anonymous memory
+    pub heap_stack_only: bool,
+    pub skip_swapped_pages: bool,
+    pub skip_non_resident_regions: bool,
+    pub exclude_shared_libs: bool,
+    pub exclude_shared_mappings: bool,
+    pub skip_file_backed: bool,
+    pub skip_zeros: bool,
+    pub pause_target: bool,
+    pub watch_single_result: bool,
+    pub string_terminator: StringTerminator,
+    pub group_results: bool,
+    /// Module groups that list their results, the others are collapsed
+    pub expanded_groups: HashSet<String>,
+    /// Watchlist addresses marked with Space for removal
+    pub marked_watchlist: HashSet<u64>,
+    pub changed_first: bool,
+    pub show_stats: bool,
+    pub watch_step: i64,
+    /// Script that memory edits are appended to while recording
+    pub recording: Option<PathBuf>,
+    /// Attaches, scans and writes of the session, kept for review
+    pub audit_log: AuditLog,
+    pub ptrace_scope: Option<u8>,
+    pub macos_setup: Option<core::mem::MacosSetup>,
+    pub config: Config,
     pub clipboard: Option<arboard::Clipboard>,
+    /// Requests of the JSON-RPC socket, when one is configured
+    rpc_calls: Option<Receiver<RpcCall>>,
 }
 
 impl App {
     pub fn new() -> App {
+        let (config, mut app_message) = match Config::load() {
+            Ok(config) => (config, AppMessage::default()),
+            Err(e) => (
+                Config::default(),
+                AppMessage::new(&format!("{e}"), AppMessageType::Error),
+            ),
+        };
+        #[cfg(unix)]
+        let rpc_calls = config.rpc.socket.as_ref().and_then(|path| {
+            core::rpc::serve(path)
+                .inspect_err(|e| {
+                    app_message = AppMessage::new(
+                        &format!("Could not listen on {}: {e}", path.display()),
+                        AppMessageType::Error,
+                    )
+                })
+                .ok()
+        });
+        #[cfg(not(unix))]
+        let rpc_calls = None;
+
         App {
             state: AppState::new(),
             ui: UiState::new(),
             scan: None,
             proc_list: vec![],
+            memory_map: vec![],
+            region_presets: vec![],
+            match_density: vec![],
             selected_value_type: 0,
             selected_value: None,
             selected_process: None,
             value_types: vec![
                 ValueType::U64,
                 ValueType::I64,
+                ValueType::U128,
+                ValueType::I128,
                 ValueType::U32,
                 ValueType::I32,
+                ValueType::U16,
+                ValueType::I16,
+                ValueType::U8,
+                ValueType::I8,
+                ValueType::F32,
+                ValueType::F64,
+                ValueType::Pointer,
                 ValueType::String,
+                ValueType::WideString,
+                ValueType::Latin1String,
+                ValueType::ShiftJisString,
                 ValueType::Hex,
+                ValueType::Bit,
+            ]
+            .into_iter()
+            .chain(
+                config
+                    .structs
+                    .iter()
+                    .cloned()
+                    .map(StructType::into_value_type),
+            )
+            .collect(),
+            pointer_width: 8,
+            scan_options: vec![
+                ScanOption::SkipSwapped,
+                ScanOption::SkipNonResident,
+                ScanOption::ExcludeSharedLibs,
+                ScanOption::ExcludeSharedMappings,
+                ScanOption::SkipFileBacked,
+                ScanOption::SkipZeros,
+                ScanOption::SkipOverlapping,
+                ScanOption::PauseTarget,
+                ScanOption::WatchSingleResult,
+                ScanOption::NulTerminateStrings,
+                ScanOption::PadStrings,
             ],
-            app_message: AppMessage::default(),
+            app_message,
             app_action: None,
+            running_scan: None,
+            last_scan: None,
             key_bindings: KeyBindings::default(),
-            include_readonly_regions: false,
+            region_perms: RegionPerms::Writable,
+            heap_stack_only: false,
+            skip_swapped_pages: false,
+            skip_non_resident_regions: false,
+            exclude_shared_libs: false,
+            exclude_shared_mappings: false,
+            skip_file_backed: false,
+            skip_zeros: false,
+            pause_target: false,
+            watch_single_result: false,
+            string_terminator: StringTerminator::None,
+            group_results: false,
+            expanded_groups: HashSet::new(),
+            marked_watchlist: HashSet::new(),
+            changed_first: false,
+            show_stats: false,
+            watch_step: 1,
+            recording: None,
+            audit_log: AuditLog::default(),
+            ptrace_scope: core::mem::get_ptrace_scope(),
+            macos_setup: core::mem::get_macos_setup(),
+            config,
             clipboard: arboard::Clipboard::new().ok(),
+            rpc_calls,
         }
     }
 
-    fn get_memory_permissions(&self) -> Vec<core::mem::MemoryRegionPerms> {
-        if self.include_readonly_regions {
-            vec![
-                core::mem::MemoryRegionPerms::Write,
-                core::mem::MemoryRegionPerms::Read,
-            ]
-        } else {
-            vec![core::mem::MemoryRegionPerms::Write]
+    /// Guidance for when Yama will block attaching to other processes
+    pub fn ptrace_hint(&self) -> Option<&'static str> {
+        self.ptrace_scope
+            .and_then(|scope| core::mem::ptrace_scope_hint(scope, core::mem::is_root()))
+    }
+
+    pub fn can_fix_ptrace_scope(&self) -> bool {
+        // scope 3 can not be lowered without a reboot
+        self.ptrace_hint().is_some() && self.ptrace_scope != Some(3)
+    }
+
+    fn scan_error_message(&self, e: &ScanError) -> String {
+        if let ScanError::Memory(MemoryError::ProcessAttach(_) | MemoryError::NoPermission(_)) = e
+            && let Some(hint) = self.ptrace_hint()
+        {
+            if self.can_fix_ptrace_scope() {
+                return format!("{hint} (f: copy fix command)");
+            }
+            return hint.to_owned();
+        }
+
+        format!("Error while scanning: {e}")
+    }
+
+    pub fn is_scan_option_enabled(&self, option: ScanOption) -> bool {
+        match option {
+            ScanOption::SkipSwapped => self.skip_swapped_pages,
+            ScanOption::SkipNonResident => self.skip_non_resident_regions,
+            ScanOption::ExcludeSharedLibs => self.exclude_shared_libs,
+            ScanOption::ExcludeSharedMappings => self.exclude_shared_mappings,
+            ScanOption::SkipFileBacked => self.skip_file_backed,
+            ScanOption::SkipZeros => self.skip_zeros,
+            ScanOption::PauseTarget => self.pause_target,
+            ScanOption::SkipOverlapping => self
+                .scan
+                .as_ref()
+                .is_some_and(|scan| scan.skip_overlapping()),
+            ScanOption::WatchSingleResult => self.watch_single_result,
+            ScanOption::NulTerminateStrings => self.string_terminator == StringTerminator::Nul,
+            ScanOption::PadStrings => self.string_terminator == StringTerminator::Pad,
+        }
+    }
+
+    fn toggle_scan_option(&mut self, option: ScanOption) {
+        match option {
+            ScanOption::SkipSwapped => {
+                self.skip_swapped_pages = !self.skip_swapped_pages;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_skip_swapped(self.skip_swapped_pages);
+                }
+            }
+            ScanOption::SkipNonResident => {
+                self.skip_non_resident_regions = !self.skip_non_resident_regions;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_skip_non_resident(self.skip_non_resident_regions);
+                }
+            }
+            ScanOption::ExcludeSharedLibs => {
+                self.exclude_shared_libs = !self.exclude_shared_libs;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_exclude_shared_libs(self.exclude_shared_libs);
+                }
+            }
+            ScanOption::ExcludeSharedMappings => {
+                self.exclude_shared_mappings = !self.exclude_shared_mappings;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_exclude_shared_mappings(self.exclude_shared_mappings);
+                }
+            }
+            ScanOption::SkipFileBacked => {
+                self.skip_file_backed = !self.skip_file_backed;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_skip_file_backed(self.skip_file_backed);
+                }
+            }
+            ScanOption::SkipZeros => {
+                self.skip_zeros = !self.skip_zeros;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_skip_zeros(self.skip_zeros);
+                }
+            }
+            ScanOption::WatchSingleResult => {
+                self.watch_single_result = !self.watch_single_result;
+            }
+            // the two string options exclude each other
+            ScanOption::NulTerminateStrings | ScanOption::PadStrings => {
+                let terminator = match option {
+                    ScanOption::NulTerminateStrings => StringTerminator::Nul,
+                    _ => StringTerminator::Pad,
+                };
+                self.string_terminator = if self.string_terminator == terminator {
+                    StringTerminator::None
+                } else {
+                    terminator
+                };
+                if let Some(scan) = &mut self.scan {
+                    scan.set_string_terminator(self.string_terminator);
+                }
+            }
+            ScanOption::PauseTarget => {
+                self.pause_target = !self.pause_target;
+                if let Some(scan) = &mut self.scan {
+                    scan.set_pause_target(self.pause_target);
+                }
+            }
+            ScanOption::SkipOverlapping => {
+                if let Some(scan) = &mut self.scan {
+                    scan.set_skip_overlapping(!scan.skip_overlapping());
+                }
+            }
+        }
+    }
+
+    // keeps the scrollbar and the selection in bounds after the watchlist shrinks
+    fn update_watchlist_state(&mut self) {
+        let len = self.scan.as_ref().map_or(0, |scan| scan.watchlist.len());
+        self.ui.scroll_states.scan_watchlist_vertical = self
+            .ui
+            .scroll_states
+            .scan_watchlist_vertical
+            .content_length(len);
+        match self.ui.list_states.scan_watchlist.selected() {
+            Some(_) if len == 0 => self.ui.list_states.scan_watchlist.select(None),
+            Some(selected) if selected >= len => {
+                self.ui.list_states.scan_watchlist.select(Some(len - 1))
+            }
+            _ => {}
+        }
+    }
+
+    fn add_to_watchlist(&mut self, result: core::scan::ScanResult) {
+        let Some(scan) = &mut self.scan else {
+            return;
+        };
+
+        scan.add_to_watchlist(result);
+        self.ui.scroll_states.scan_watchlist_vertical = self
+            .ui
+            .scroll_states
+            .scan_watchlist_vertical
+            .content_length(scan.watchlist.len());
+        if self.ui.list_states.scan_watchlist.selected().is_none() && !scan.watchlist.is_empty() {
+            self.ui.list_states.scan_watchlist.select(Some(0));
         }
     }
 
@@ -651,8 +1400,35 @@ impl App {
             return;
         }
 
-        let result = Scan::new(
-            self.selected_process.as_ref().unwrap().pid,
+        // pointers follow the bitness of the target
+        let pid = self.selected_process.as_ref().unwrap().pid;
+        self.pointer_width = core::proc::get_pointer_width(pid).unwrap_or(8);
+        for value_type in &mut self.value_types {
+            *value_type = value_type.for_pointer_width(self.pointer_width);
+        }
+
+        // start from the defaults of the config file
+        let defaults = &self.config.scan;
+        self.selected_value_type = defaults
+            .value_type
+            .map(|value_type| value_type.for_pointer_width(self.pointer_width))
+            .and_then(|value_type| self.value_types.iter().position(|t| *t == value_type))
+            .unwrap_or(0);
+        self.region_perms = defaults
+            .permissions
+            .as_deref()
+            .map_or(RegionPerms::Writable, RegionPerms::from_config);
+        self.skip_file_backed = defaults.skip_file_backed.unwrap_or(false);
+        if let Some(read_size) = defaults.read_size {
+            self.ui.input_buffers.read_size = read_size.to_string();
+        }
+        if let Some(percent) = defaults.percent {
+            self.ui.input_buffers.compare_percent = percent.to_string();
+        }
+
+        let result = Scan::with_backend(
+            defaults.backend.unwrap_or_default().backend(),
+            pid,
             vec![],
             *self
                 .value_types
@@ -660,9 +1436,15 @@ impl App {
                 .unwrap_or(&ValueType::U64),
             None,
             None,
-            None,
+            Some(self.region_perms.search_perms()),
         );
 
+        let attach_failed = matches!(
+            result,
+            Err(ScanError::Memory(
+                MemoryError::ProcessAttach(_) | MemoryError::NoPermission(_)
+            ))
+        );
         match result {
             Err(e) => {
                 self.app_message = AppMessage::new(
@@ -670,16 +1452,224 @@ impl App {
                     AppMessageType::Error,
                 )
             }
-            Ok(scan) => self.scan = Some(scan),
-        }
-
-        self.ui.list_states.value_type.select(Some(0));
-        self.ui.scroll_states.scan_results_vertical =
-            self.ui.scroll_states.scan_results_vertical.position(0);
-        self.ui.scroll_states.scan_watchlist_vertical =
-            self.ui.scroll_states.scan_watchlist_vertical.position(0);
-        self.go_to(CurrentScreen::Scan);
+            Ok(mut scan) => {
+                if scan.value_type.is_variable_size()
+                    && let Err(e) = scan.set_read_size(self.config.scan.read_size)
+                {
+                    self.app_message = AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                }
+                scan.set_compare_mode(self.config.scan.comparison.unwrap_or(CompareMode::Exact));
+                scan.set_skip_file_backed(self.skip_file_backed);
+                // the swapped bytes are part of the stats shown after a first scan
+                scan.set_count_swapped(true);
+                if let Some(percent) = self.config.scan.percent
+                    && let Err(e) = scan.set_compare_percent(percent)
+                {
+                    self.app_message = AppMessage::new(
+                        &format!("Invalid percent in config: {e}"),
+                        AppMessageType::Error,
+                    );
+                }
+                if let Err(e) = scan.set_compare_epsilon(self.config.scan.epsilon) {
+                    self.app_message = AppMessage::new(
+                        &format!("Invalid epsilon in config: {e}"),
+                        AppMessageType::Error,
+                    );
+                }
+                if let Some(alignment) = self.config.scan.alignment
+                    && let Err(e) = scan.set_alignment(alignment)
+                {
+                    self.app_message =
+                        AppMessage::new(&format!("Config: {e}"), AppMessageType::Error);
+                }
+                if let Some(block_size) = self.config.scan.block_size_kb
+                    && let Err(e) = scan.set_block_size(block_size.saturating_mul(1 << 10))
+                {
+                    self.app_message = AppMessage::new(
+                        &format!("Invalid block_size_kb in config: {e}"),
+                        AppMessageType::Error,
+                    );
+                }
+                if let Some(budget) = self.config.scan.memory_budget_mb {
+                    scan.set_memory_budget(budget.saturating_mul(1 << 20));
+                }
+                if let Some(max_results) = self.config.scan.max_results
+                    && let Err(e) = scan.set_max_results(max_results)
+                {
+                    self.app_message = AppMessage::new(
+                        &format!("Invalid max_results in config: {e}"),
+                        AppMessageType::Error,
+                    );
+                }
+                let name = self.selected_process.as_ref().unwrap().name.clone();
+                self.audit_log.record(pid, AuditAction::Attach { name });
+                self.scan = Some(scan);
+            }
+        }
+
+        self.update_read_size_widget();
+        self.update_compare_percent_widget();
+        self.ui
+            .list_states
+            .value_type
+            .select(Some(self.selected_value_type));
+        self.ui.list_states.scan_options.select(Some(0));
+        self.ui.scroll_states.scan_results_vertical =
+            self.ui.scroll_states.scan_results_vertical.position(0);
+        self.ui.scroll_states.scan_watchlist_vertical =
+            self.ui.scroll_states.scan_watchlist_vertical.position(0);
+        self.go_to(CurrentScreen::Scan);
         self.select_widget(ScanViewWidget::ValueInput);
+
+        // explain the macOS requirements instead of only showing the error code
+        if attach_failed && self.macos_setup.is_some() {
+            self.ui.input_mode = InputMode::Normal;
+            self.go_to(CurrentScreen::MacosSetup);
+        }
+    }
+
+    fn show_memory_map(&mut self) {
+        let Some(process) = &self.selected_process else {
+            return;
+        };
+
+        // smaps is linux only, elsewhere the regions are listed without usage stats
+        let memory_map = match get_region_stats(process.pid) {
+            Ok(regions) => Ok(regions
+                .into_iter()
+                .map(|(region, stats)| (region, Some(stats)))
+                .collect()),
+            Err(_) => get_memory_regions(
+                process.pid,
+                None,
+                None,
+                Some(&[MemoryRegionPerms::Read, MemoryRegionPerms::Write]),
+            )
+            .map(|regions| regions.into_iter().map(|region| (region, None)).collect()),
+        };
+
+        match memory_map {
+            Err(e) => {
+                self.app_message = AppMessage::new(
+                    &format!("Error reading memory map: {e}"),
+                    AppMessageType::Error,
+                );
+            }
+            Ok(memory_map) => {
+                self.memory_map = memory_map;
+                self.ui.list_states.memory_map.select(Some(0));
+                self.ui.scroll_states.memory_map_vertical = self
+                    .ui
+                    .scroll_states
+                    .memory_map_vertical
+                    .content_length(self.memory_map.len())
+                    .position(0);
+                if self.state.current_screen != CurrentScreen::MemoryMap {
+                    self.go_to(CurrentScreen::MemoryMap);
+                }
+            }
+        }
+    }
+
+    fn show_region_presets(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        match get_named_ranges(scan.pid) {
+            Err(e) => {
+                self.app_message = AppMessage::new(
+                    &format!("Error reading memory regions: {e}"),
+                    AppMessageType::Error,
+                );
+            }
+            Ok(ranges) if ranges.is_empty() => {
+                self.app_message = AppMessage::new("No named regions found", AppMessageType::Info);
+            }
+            Ok(ranges) => {
+                self.region_presets = ranges;
+                self.ui.list_states.region_presets.select(Some(0));
+                self.go_to(CurrentScreen::RegionPresets);
+            }
+        }
+    }
+
+    fn show_match_density(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        self.match_density = scan.get_match_density();
+        if self.match_density.is_empty() {
+            self.app_message = AppMessage::new("No results to count", AppMessageType::Info);
+            return;
+        }
+        self.ui.list_states.match_density.select(Some(0));
+        self.go_to(CurrentScreen::MatchDensity);
+    }
+
+    fn select_region_preset(&mut self) {
+        let Some(range) = self
+            .ui
+            .list_states
+            .region_presets
+            .selected()
+            .and_then(|i| self.region_presets.get(i))
+            .cloned()
+        else {
+            return;
+        };
+
+        // modules only scan their own mappings, the heap and stacks are plain ranges
+        if let Some(scan) = &mut self.scan
+            && let Ok((start, end)) = scan.set_module(&range.name)
+        {
+            self.ui.input_buffers.start_address = format!("{start:x}");
+            self.ui.input_buffers.end_address = format!("{end:x}");
+            self.app_message = AppMessage::new(
+                &format!("Scanning only the mappings of {}", range.name),
+                AppMessageType::Info,
+            );
+            self.go_back();
+            return;
+        }
+        self.set_scan_range(&range);
+    }
+
+    fn select_match_region(&mut self) {
+        let Some((range, _)) = self
+            .ui
+            .list_states
+            .match_density
+            .selected()
+            .and_then(|i| self.match_density.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        self.set_scan_range(&range);
+    }
+
+    /// Limits the next first scan to `range` and goes back to the scan view
+    fn set_scan_range(&mut self, range: &NamedRange) {
+        let Some(scan) = &mut self.scan else {
+            return;
+        };
+
+        match scan.set_address_range(Some(range.start), Some(range.end)) {
+            Err(e) => {
+                self.app_message = AppMessage::new(&format!("{e}"), AppMessageType::Error);
+            }
+            Ok(_) => {
+                self.ui.input_buffers.start_address = format!("{:x}", range.start);
+                self.ui.input_buffers.end_address = format!("{:x}", range.end);
+                self.app_message = AppMessage::new(
+                    &format!("Address range set to {}", range.name),
+                    AppMessageType::Info,
+                );
+            }
+        }
+        self.go_back();
     }
 
     fn go_to(&mut self, screen: CurrentScreen) {
@@ -716,7 +1706,24 @@ impl App {
         self.ui.input_buffers.start_address = String::new();
         self.ui.input_buffers.end_address = String::new();
         self.ui.input_buffers.read_size = String::new();
-        self.include_readonly_regions = false;
+        self.ui.input_buffers.compare_percent = DEFAULT_COMPARE_PERCENT.to_string();
+        self.region_perms = RegionPerms::Writable;
+        self.heap_stack_only = false;
+        self.skip_swapped_pages = false;
+        self.skip_non_resident_regions = false;
+        self.exclude_shared_libs = false;
+        self.exclude_shared_mappings = false;
+        self.skip_file_backed = false;
+        self.skip_zeros = false;
+        self.pause_target = false;
+        self.watch_single_result = false;
+        self.string_terminator = StringTerminator::None;
+        self.group_results = false;
+        self.expanded_groups.clear();
+        self.marked_watchlist.clear();
+        self.changed_first = false;
+        self.recording = None;
+        self.last_scan = None;
         self.scan = None;
         self.selected_process = None;
         self.app_message = AppMessage::default();
@@ -728,6 +1735,8 @@ impl App {
             ScanViewWidget::StartAddressInput => self.insert_mode_for(SelectedInput::StartAddress),
             ScanViewWidget::EndAddressInput => self.insert_mode_for(SelectedInput::EndAddress),
             ScanViewWidget::ReadSize => self.insert_mode_for(SelectedInput::ReadSize),
+            ScanViewWidget::ComparePercent => self.insert_mode_for(SelectedInput::ComparePercent),
+            ScanViewWidget::StepInput => self.insert_mode_for(SelectedInput::Step),
             _ => {
                 self.ui.input_mode = InputMode::Normal;
             }
@@ -775,6 +1784,15 @@ impl App {
         self.ui.selected_input = Some(selected_input);
     }
 
+    /// The value editor of a bit value, where bits are toggled instead of typed
+    pub fn is_editing_bits(&self) -> bool {
+        self.state.current_screen == CurrentScreen::ValueEditing
+            && self
+                .selected_value
+                .as_ref()
+                .is_some_and(|value| value.value_type == ValueType::Bit)
+    }
+
     pub fn next_widget(&mut self) {
         match self.state.current_screen {
             CurrentScreen::Scan => {
@@ -846,6 +1864,7 @@ impl App {
 
     fn check_value_before_scan(&mut self) -> bool {
         if let Some(scan) = &self.scan
+            && scan.compare_mode.needs_value()
             && let Err(e) = scan.value_from_str(&self.ui.input_buffers.scan_value)
         {
             self.app_message = AppMessage::new(&format!("{e}"), AppMessageType::Error);
@@ -857,71 +1876,338 @@ impl App {
     }
 
     fn new_scan(&mut self) {
+        // an empty value starts an unknown initial value scan for numeric types
+        if self.ui.input_buffers.scan_value.trim().is_empty()
+            && self
+                .scan
+                .as_ref()
+                .is_some_and(|scan| !scan.value_type.is_variable_size())
+        {
+            self.start_scan(ScanJob::Unknown);
+            return;
+        }
         if !self.check_value_before_scan() {
             return;
         }
-        match &mut self.scan {
-            None => {}
-            Some(scan) => match scan.init() {
-                Err(e) => {
-                    self.app_message = AppMessage::new(
-                        &format!("Error while scanning: {e}"),
-                        AppMessageType::Error,
-                    );
+        self.start_scan(ScanJob::First {
+            value: self.ui.input_buffers.scan_value.clone(),
+        });
+    }
+
+    fn next_scan(&mut self) {
+        if !self.check_value_before_scan() {
+            return;
+        }
+        let Some(scan) = &self.scan else {
+            return;
+        };
+        let previous_count = previous_count(scan);
+        // comparisons with the previous scan are logged by their name
+        let value = if scan.compare_mode.needs_value() {
+            self.ui.input_buffers.scan_value.clone()
+        } else {
+            scan.compare_mode.get_string().to_owned()
+        };
+        self.start_scan(ScanJob::Next {
+            value,
+            previous_count,
+        });
+    }
+
+    /// Runs the last first or next scan again with the value and comparison it used
+    fn repeat_scan(&mut self) {
+        let (Some((job, compare_mode)), Some(scan)) = (self.last_scan.clone(), &mut self.scan)
+        else {
+            self.app_message = AppMessage::new("No scan to repeat yet", AppMessageType::Info);
+            return;
+        };
+
+        scan.set_compare_mode(compare_mode);
+        let value = match &job {
+            ScanJob::First { value } => Some(value),
+            ScanJob::Next { value, .. } if compare_mode.needs_value() => Some(value),
+            _ => None,
+        };
+        if let Some(value) = value {
+            if let Err(e) = scan.set_value_from_str(value) {
+                self.app_message = AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                return;
+            }
+            self.ui.input_buffers.scan_value = value.clone();
+        }
+        let job = match job {
+            ScanJob::Next { value, .. } => ScanJob::Next {
+                value,
+                previous_count: previous_count(scan),
+            },
+            job => job,
+        };
+        self.start_scan(job);
+    }
+
+    /// Hands the scan to a worker thread, `poll_running_scan` takes it back once it is done
+    fn start_scan(&mut self, job: ScanJob) {
+        let Some(mut scan) = self.scan.take() else {
+            return;
+        };
+        self.last_scan = Some((job.clone(), scan.compare_mode));
+
+        let (sender, progress_receiver) = mpsc::channel();
+        // nobody listening is not an error, the scan goes on
+        scan.set_progress_callback(Some(ProgressCallback::new(move |progress| {
+            let _ = sender.send(progress);
+        })));
+        let thread_job = job.clone();
+        let handle = thread::spawn(move || {
+            let result = thread_job.run(&mut scan);
+            (scan, result)
+        });
+        self.running_scan = Some(RunningScan {
+            job,
+            handle,
+            progress_receiver,
+            progress: ScanProgress::default(),
+            rpc_call: None,
+        });
+    }
+
+    /// Answers the requests of the JSON-RPC socket, the scan is busy while it runs in the
+    /// background
+    fn poll_rpc_calls(&mut self) {
+        let Some(calls) = &self.rpc_calls else {
+            return;
+        };
+        let calls: Vec<RpcCall> = calls.try_iter().collect();
+        for call in calls {
+            if self.running_scan.is_some() {
+                call.respond(Err(RpcError::scan("A scan is running")));
+                continue;
+            }
+            let outcome = core::rpc::dispatch(
+                self.scan.as_mut(),
+                &call.request.method,
+                &call.request.params,
+            );
+            match outcome {
+                Ok(RpcOutcome::Done(result)) => call.respond(Ok(result)),
+                Ok(RpcOutcome::FirstScan(value)) => {
+                    self.sync_value_type_select();
+                    self.start_scan(ScanJob::First { value });
+                    self.hand_rpc_call_to_scan(call);
                 }
-                Ok(results) => {
-                    if !results.is_empty() {
-                        self.ui.list_states.scan_results.select(Some(0));
-                        self.select_widget(ScanViewWidget::ScanResults);
+                Ok(RpcOutcome::NextScan(value)) => {
+                    let Some(scan) = &self.scan else {
+                        continue;
+                    };
+                    let previous_count = previous_count(scan);
+                    self.start_scan(ScanJob::Next {
+                        value,
+                        previous_count,
+                    });
+                    self.hand_rpc_call_to_scan(call);
+                }
+                Ok(RpcOutcome::Written(address, value)) => {
+                    if let Some(scan) = &self.scan {
+                        let value_type = scan.get_value_type(address);
+                        let path = scan.region_path(address);
+                        let value = value_type.parse_value(&value).unwrap_or_default();
+                        self.record_edit(address, path, value_type, &value);
                     }
-                    self.app_message = AppMessage::default();
+                    self.app_action = Some(AppAction::Refresh);
+                    call.respond(Ok(serde_json::Value::Null));
                 }
-            },
+                Err(e) => call.respond(Err(e)),
+            }
         }
+    }
 
-        if let Some(scan) = &self.scan {
-            self.ui.scroll_states.scan_results_vertical = self
-                .ui
-                .scroll_states
-                .scan_results_vertical
-                .content_length(scan.results.len());
-            self.ui.scroll_states.scan_results_vertical =
-                self.ui.scroll_states.scan_results_vertical.position(0);
+    /// Selects the type of the scan in the value type list after it was changed
+    /// over RPC, along with the widgets that depend on it
+    fn sync_value_type_select(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+        if let Some(index) = self.value_types.iter().position(|t| *t == scan.value_type) {
+            self.selected_value_type = index;
+            self.ui.list_states.value_type.select(Some(index));
         }
+        self.update_read_size_widget();
+        self.update_compare_percent_widget();
     }
 
-    fn next_scan(&mut self) {
-        if !self.check_value_before_scan() {
+    /// The scan just started answers `call` once it is done
+    fn hand_rpc_call_to_scan(&mut self, call: RpcCall) {
+        match &mut self.running_scan {
+            Some(running) => running.rpc_call = Some(call),
+            None => call.respond(Err(RpcError::scan("No process is attached"))),
+        }
+    }
+
+    /// Updates the progress of the running scan and finishes it when the thread is done
+    fn poll_running_scan(&mut self) {
+        let Some(running) = &mut self.running_scan else {
             return;
+        };
+        if let Some(progress) = running.progress_receiver.try_iter().last() {
+            running.progress = progress;
         }
-        match &mut self.scan {
-            None => {}
-            Some(scan) => match scan.next_scan() {
-                Err(e) => {
-                    self.app_message = AppMessage::new(
-                        &format!("Error while scanning: {e}"),
-                        AppMessageType::Error,
-                    );
+        if !running.handle.is_finished() {
+            return;
+        }
+
+        let running = self.running_scan.take().unwrap();
+        let Ok((mut scan, result)) = running.handle.join() else {
+            if let Some(call) = running.rpc_call {
+                call.respond(Err(RpcError::scan("The scan failed unexpectedly")));
+            }
+            self.app_message = AppMessage::new(
+                "The scan failed unexpectedly, select the process again",
+                AppMessageType::Error,
+            );
+            return;
+        };
+        scan.set_progress_callback(None);
+        self.scan = Some(scan);
+        if let Some(call) = running.rpc_call {
+            call.respond(core::rpc::scan_response(result.clone()));
+        }
+        match running.job {
+            ScanJob::First { value } => self.finish_new_scan(result, value),
+            ScanJob::Unknown => self.finish_unknown_value_scan(result),
+            ScanJob::Next {
+                value,
+                previous_count,
+            } => self.finish_next_scan(result, value, previous_count),
+        }
+    }
+
+    fn finish_new_scan(&mut self, result: Result<usize, ScanError>, value: String) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+        match result {
+            Err(e) => {
+                self.app_message =
+                    AppMessage::new(&self.scan_error_message(&e), AppMessageType::Error);
+            }
+            Ok(count) => {
+                self.audit_log.record(
+                    scan.pid,
+                    AuditAction::FirstScan {
+                        value,
+                        results: count,
+                    },
+                );
+                let mut stats_msg = utils::format_scan_stats(&scan.stats, scan.skip_swapped());
+                if scan.truncated_count() > 0 {
+                    stats_msg = format!("{count} matches (truncated). {stats_msg}");
                 }
-                Ok(results) => {
-                    if !results.is_empty() {
-                        self.ui.list_states.scan_results.select(Some(0));
-                        self.select_widget(ScanViewWidget::ScanResults);
-                    }
-                    self.app_message = AppMessage::default();
+                if count > 0 {
+                    self.ui.list_states.scan_results.select(Some(0));
+                    self.select_widget(ScanViewWidget::ScanResults);
                 }
-            },
+                self.app_message = AppMessage::new(&stats_msg, AppMessageType::Info);
+            }
         }
 
-        if let Some(scan) = &self.scan {
-            self.ui.scroll_states.scan_results_vertical = self
-                .ui
-                .scroll_states
-                .scan_results_vertical
-                .content_length(scan.results.len());
-            self.ui.scroll_states.scan_results_vertical =
-                self.ui.scroll_states.scan_results_vertical.position(0);
+        self.reset_results_scroll();
+    }
+
+    fn finish_unknown_value_scan(&mut self, result: Result<usize, ScanError>) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        match result {
+            Err(e) => {
+                self.app_message =
+                    AppMessage::new(&self.scan_error_message(&e), AppMessageType::Error);
+            }
+            Ok(candidates) => {
+                self.audit_log.record(
+                    scan.pid,
+                    AuditAction::FirstScan {
+                        value: String::from("unknown"),
+                        results: candidates,
+                    },
+                );
+                let stats_msg = utils::format_scan_stats(&scan.stats, scan.skip_swapped());
+                self.app_message = AppMessage::new(
+                    &format!(
+                        "{candidates} candidates, change the value in the target and run a next scan. {stats_msg}"
+                    ),
+                    AppMessageType::Info,
+                );
+            }
         }
+        self.reset_results_scroll();
+    }
+
+    fn finish_next_scan(
+        &mut self,
+        result: Result<usize, ScanError>,
+        value: String,
+        previous_count: usize,
+    ) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+        let mut single_result = None;
+        match result {
+            Err(e) => {
+                self.app_message =
+                    AppMessage::new(&self.scan_error_message(&e), AppMessageType::Error);
+            }
+            Ok(count) => {
+                self.audit_log.record(
+                    scan.pid,
+                    AuditAction::NextScan {
+                        value,
+                        results: count,
+                    },
+                );
+                if count == 1 {
+                    single_result = scan.results.first().cloned();
+                }
+                if count > 0 {
+                    self.ui.list_states.scan_results.select(Some(0));
+                    self.select_widget(ScanViewWidget::ScanResults);
+                }
+                self.app_message = AppMessage::default();
+            }
+        }
+
+        if let Some(threshold) = self.config.scan.notify_below
+            && let Some(scan) = &self.scan
+            && previous_count >= threshold
+            && scan.match_count() < threshold
+        {
+            utils::ring_bell();
+            self.app_message = AppMessage::new(
+                &format!(
+                    "{} results left, below the notify threshold of {threshold}",
+                    scan.match_count()
+                ),
+                AppMessageType::Info,
+            );
+        }
+
+        // a single remaining result is almost always the address that was searched for
+        if self.watch_single_result
+            && let Some(result) = single_result
+            && let Some(scan) = &self.scan
+            && !scan.watchlist.iter().any(|w| w.address == result.address)
+        {
+            let address = result.address;
+            self.add_to_watchlist(result);
+            self.app_message = AppMessage::new(
+                &format!("One result left, 0x{address:x} added to watchlist"),
+                AppMessageType::Info,
+            );
+        }
+
+        self.order_results();
+        self.reset_results_scroll();
     }
 
     fn refresh_scan(&mut self) {
@@ -932,15 +2218,105 @@ impl App {
             None => {}
             Some(scan) => {
                 if let Err(e) = scan.refresh() {
-                    self.app_message = AppMessage::new(
-                        &format!("Error while scanning: {e}"),
-                        AppMessageType::Error,
-                    );
+                    self.app_message =
+                        AppMessage::new(&self.scan_error_message(&e), AppMessageType::Error);
                 } else {
                     self.app_message = AppMessage::default();
                 }
             }
         }
+
+        self.order_results();
+    }
+
+    /// Rows of the results list, one per result or, when grouped,
+    /// a header per module followed by the results of expanded groups
+    pub fn result_rows(&self) -> Vec<ResultRow> {
+        let Some(scan) = &self.scan else {
+            return vec![];
+        };
+
+        if !self.group_results {
+            return (0..scan.results.len()).map(ResultRow::Result).collect();
+        }
+
+        // groups keep the order in which their modules first appear in the results
+        let mut groups: Vec<(&str, Vec<usize>)> = vec![];
+        let mut group_index: HashMap<&str, usize> = HashMap::new();
+        for (i, result) in scan.results.iter().enumerate() {
+            let name = result.get_module_name().unwrap_or("anon");
+            let idx = *group_index.entry(name).or_insert_with(|| {
+                groups.push((name, vec![]));
+                groups.len() - 1
+            });
+            groups[idx].1.push(i);
+        }
+
+        let mut rows = vec![];
+        for (name, indices) in groups {
+            let expanded = self.expanded_groups.contains(name);
+            rows.push(ResultRow::Group {
+                name: name.to_owned(),
+                count: indices.len(),
+                expanded,
+            });
+            if expanded {
+                rows.extend(indices.into_iter().map(ResultRow::Result));
+            }
+        }
+        rows
+    }
+
+    /// Index into the scan results of the selected row, `None` for group headers
+    fn selected_result_index(&self) -> Option<usize> {
+        let selected = self.ui.list_states.scan_results.selected()?;
+        match self.result_rows().into_iter().nth(selected)? {
+            ResultRow::Result(idx) => Some(idx),
+            ResultRow::Group { .. } => None,
+        }
+    }
+
+    /// Expands or collapses the selected group, returns false when no group header is selected
+    fn toggle_selected_group(&mut self) -> bool {
+        let Some(selected) = self.ui.list_states.scan_results.selected() else {
+            return false;
+        };
+        let Some(ResultRow::Group { name, .. }) = self.result_rows().into_iter().nth(selected)
+        else {
+            return false;
+        };
+
+        if !self.expanded_groups.remove(&name) {
+            self.expanded_groups.insert(name);
+        }
+        let len = self.result_rows().len();
+        self.ui.scroll_states.scan_results_vertical = self
+            .ui
+            .scroll_states
+            .scan_results_vertical
+            .content_length(len);
+        true
+    }
+
+    fn reset_results_scroll(&mut self) {
+        let len = self.result_rows().len();
+        self.ui.scroll_states.scan_results_vertical = self
+            .ui
+            .scroll_states
+            .scan_results_vertical
+            .content_length(len)
+            .position(0);
+    }
+
+    // Results are collected in address order, changed rows are moved to the top on demand
+    fn order_results(&mut self) {
+        if let Some(scan) = &mut self.scan {
+            if self.changed_first {
+                scan.results.sort_by_key(|r| !r.has_changed());
+            } else {
+                scan.results.sort_by_key(|r| r.address);
+            }
+        }
     }
 
     fn handle_normal_mode_event(&mut self, key: KeyEvent) {
@@ -974,7 +2350,14 @@ impl App {
             match selected_input {
                 SelectedInput::ResultValue => {
                     let result = self.selected_value.as_ref().unwrap();
-                    match scan.update_value(result.address, &self.ui.input_buffers.result_value) {
+                    let value = &self.ui.input_buffers.result_value;
+                    let value_type = scan.get_value_type(result.address);
+                    let written = if result.is_read_only() {
+                        scan.update_protected_value(result.address, value)
+                    } else {
+                        scan.update_value(result.address, value)
+                    };
+                    match written {
                         Err(e) => match e {
                             ScanError::EmptyValue => {
                                 self.app_message = AppMessage::new(
@@ -987,7 +2370,7 @@ impl App {
                                     &format!(
                                         "Invalid value: {:.10} for type: {}",
                                         self.ui.input_buffers.result_value,
-                                        scan.value_type.get_string(),
+                                        value_type.get_string(),
                                     ),
                                     AppMessageType::Error,
                                 );
@@ -998,6 +2381,10 @@ impl App {
                                     AppMessageType::Error,
                                 );
                             }
+                            ScanError::UnmappedPointer(_) | ScanError::ProcessChanged(_) => {
+                                self.app_message =
+                                    AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                            }
                             _ => {}
                         },
                         Ok(_) => {
@@ -1009,10 +2396,49 @@ impl App {
                                 ),
                                 AppMessageType::Info,
                             );
+                            let value = value_type
+                                .parse_value(&self.ui.input_buffers.result_value)
+                                .unwrap_or_default();
+                            let (address, path) = (result.address, result.path.clone());
+                            self.record_edit(address, path, value_type, &value);
                         }
                     }
                     self.go_back();
                 }
+                SelectedInput::ImportPath => {
+                    let path = self.ui.input_buffers.import_path.trim().to_owned();
+                    if path.is_empty() {
+                        self.go_back();
+                        return;
+                    }
+
+                    let imported = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("Could not read {path}: {e}"))
+                        .and_then(|content| {
+                            scan.import_addresses(&content)
+                                .map_err(|e| format!("Import failed: {e}"))
+                        });
+                    self.go_back();
+                    match imported {
+                        Err(e) => {
+                            self.app_message = AppMessage::new(&e, AppMessageType::Error);
+                        }
+                        Ok(count) => {
+                            self.app_message = AppMessage::new(
+                                &format!(
+                                    "Imported {count} readable addresses from {path}, next scans only check them"
+                                ),
+                                AppMessageType::Info,
+                            );
+                            self.order_results();
+                            self.reset_results_scroll();
+                            if count > 0 {
+                                self.ui.list_states.scan_results.select(Some(0));
+                                self.select_widget(ScanViewWidget::ScanResults);
+                            }
+                        }
+                    }
+                }
                 SelectedInput::ScanValue => {
                     if !self.ui.input_buffers.scan_value.is_empty()
                         && scan
@@ -1057,13 +2483,40 @@ impl App {
                         }
                     }
                 }
+                SelectedInput::ComparePercent => {
+                    match self.ui.input_buffers.compare_percent.parse::<f64>() {
+                        Ok(percent) if scan.set_compare_percent(percent).is_ok() => {
+                            self.app_message = AppMessage::default();
+                        }
+                        _ => {
+                            self.app_message = AppMessage::new(
+                                "Percent should be a non-negative number",
+                                AppMessageType::Error,
+                            );
+                            self.insert_mode_for(SelectedInput::ComparePercent);
+                        }
+                    }
+                }
+                SelectedInput::Step => match self.ui.input_buffers.step.parse::<i64>() {
+                    Ok(step) if step != 0 => {
+                        self.watch_step = step;
+                        self.app_message = AppMessage::default();
+                    }
+                    _ => {
+                        self.app_message = AppMessage::new(
+                            "Step should be a non-zero integer",
+                            AppMessageType::Error,
+                        );
+                        self.insert_mode_for(SelectedInput::Step);
+                    }
+                },
                 SelectedInput::StartAddress => {
                     if let Err(e) = scan.set_start_address(&self.ui.input_buffers.start_address) {
                         match e {
                             ScanError::InvalidAddress => {
                                 self.app_message = AppMessage::new(
                                     &format!(
-                                        "Invalid hex value: {:.16}",
+                                        "Invalid address: {:.24}",
                                         self.ui.input_buffers.start_address
                                     ),
                                     AppMessageType::Error,
@@ -1081,7 +2534,10 @@ impl App {
                                     AppMessageType::Error,
                                 );
                             }
-                            _ => {}
+                            e => {
+                                self.app_message =
+                                    AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                            }
                         }
                         self.insert_mode_for(SelectedInput::StartAddress);
                     } else {
@@ -1094,7 +2550,7 @@ impl App {
                             ScanError::InvalidAddress => {
                                 self.app_message = AppMessage::new(
                                     &format!(
-                                        "Invalid hex value: {:.16}",
+                                        "Invalid address: {:.24}",
                                         self.ui.input_buffers.end_address
                                     ),
                                     AppMessageType::Error,
@@ -1112,7 +2568,10 @@ impl App {
                                     AppMessageType::Error,
                                 );
                             }
-                            _ => {}
+                            e => {
+                                self.app_message =
+                                    AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                            }
                         }
                         self.insert_mode_for(SelectedInput::EndAddress);
                     } else {
@@ -1126,6 +2585,14 @@ impl App {
 
     // Command handler
     pub fn handle_command(&mut self, cmd: Command) {
+        if self.running_scan.is_some() && !cmd.works_while_scanning() {
+            self.app_message = AppMessage::new(
+                "Wait for the scan to finish, the scan view stays usable meanwhile",
+                AppMessageType::Info,
+            );
+            return;
+        }
+
         match cmd {
             // Navigation commands
             Command::NextWidget => {
@@ -1160,6 +2627,13 @@ impl App {
             }
 
             // Character input commands
+            // space flips the bit under the cursor when editing a bit value
+            Command::InsertChar(' ') if self.is_editing_bits() => {
+                cursor::toggle_bit(
+                    &mut self.ui.input_buffers.result_value,
+                    self.ui.character_index,
+                );
+            }
             Command::InsertChar(c) => {
                 if let Some(selected_input) = &self.ui.selected_input {
                     let current_input = self.ui.input_buffers.get_mut(selected_input);
@@ -1198,6 +2672,11 @@ impl App {
             // Screen commands
             Command::ShowProcessList => self.show_process_list(),
             Command::SelectProcess => self.select_process(),
+            Command::ShowMemoryMap => self.show_memory_map(),
+            Command::ShowRegionPresets => self.show_region_presets(),
+            Command::SelectRegionPreset => self.select_region_preset(),
+            Command::ShowMatchDensity => self.show_match_density(),
+            Command::SelectMatchRegion => self.select_match_region(),
 
             // Scan commands
             Command::NewScan => {
@@ -1214,6 +2693,13 @@ impl App {
                     self.app_action = Some(AppAction::Next);
                 }
             }
+            Command::RepeatScan => {
+                if self.scan.is_some() {
+                    self.app_message =
+                        AppMessage::new("Repeating last scan...", AppMessageType::Info);
+                    self.app_action = Some(AppAction::Repeat);
+                }
+            }
             Command::RefreshScan => {
                 if self.scan.is_some() {
                     self.app_message =
@@ -1221,12 +2707,14 @@ impl App {
                     self.app_action = Some(AppAction::Refresh);
                 }
             }
-            Command::ToggleReadWrite => {
-                if self.ui.selected_widgets.scan_view_selected_widget
-                    == ScanViewWidget::PermissionsCheckbox
-                {
-                    self.include_readonly_regions = !self.include_readonly_regions;
-                    let perms = self.get_memory_permissions();
+            Command::ToggleCheckbox => match self.ui.selected_widgets.scan_view_selected_widget {
+                ScanViewWidget::PermissionsCheckbox => {
+                    self.region_perms = self.region_perms.next();
+                    let perms = self.region_perms.search_perms();
+                    let exclude_executable = self.region_perms == RegionPerms::Data;
+                    if let Some(scan) = &mut self.scan {
+                        scan.set_exclude_executable(exclude_executable);
+                    }
                     if let Some(scan) = &mut self.scan
                         && let Err(e) = scan.set_mem_permissions(perms)
                     {
@@ -1236,47 +2724,260 @@ impl App {
                         );
                     }
                 }
+                ScanViewWidget::HeapStackCheckbox => {
+                    self.heap_stack_only = !self.heap_stack_only;
+                    if let Some(scan) = &mut self.scan {
+                        scan.set_heap_stack_only(self.heap_stack_only);
+                    }
+                }
+                ScanViewWidget::BigEndianCheckbox => {
+                    if let Some(scan) = &mut self.scan {
+                        scan.set_endianness(scan.endianness().next());
+                    }
+                }
+                ScanViewWidget::ScanOptions => {
+                    if let Some(selected) = self.ui.list_states.scan_options.selected()
+                        && let Some(option) = self.scan_options.get(selected)
+                    {
+                        self.toggle_scan_option(*option);
+                    }
+                }
+                ScanViewWidget::ScanResults => {
+                    self.toggle_selected_group();
+                }
+                ScanViewWidget::WatchList => {
+                    if let Some(scan) = &self.scan
+                        && let Some(selected) = self.ui.list_states.scan_watchlist.selected()
+                        && let Some(result) = scan.watchlist.get(selected)
+                        && !self.marked_watchlist.remove(&result.address)
+                    {
+                        self.marked_watchlist.insert(result.address);
+                    }
+                }
+                ScanViewWidget::CompareModeSelect => {
+                    if let Some(scan) = &mut self.scan {
+                        scan.set_compare_mode(scan.compare_mode.next());
+                        self.update_compare_percent_widget();
+                    }
+                }
+                ScanViewWidget::AlignmentSelect => {
+                    if let Some(scan) = &mut self.scan {
+                        let alignment = match scan.alignment() {
+                            8 => 1,
+                            alignment => alignment * 2,
+                        };
+                        scan.set_alignment(alignment).unwrap();
+                    }
+                }
+                _ => {}
+            },
+
+            Command::ToggleStats => self.show_stats = !self.show_stats,
+            Command::ToggleIntegerFormat => {
+                self.config.display.integers = self.config.display.integers.next();
+                self.app_message = AppMessage::new(
+                    &format!(
+                        "Integers are shown in {}",
+                        self.config.display.integers.get_string()
+                    ),
+                    AppMessageType::Info,
+                );
+            }
+            Command::ReloadRegions => {
+                if let Some(scan) = &mut self.scan {
+                    self.app_message = match scan.reload_regions() {
+                        Ok(count) => AppMessage::new(
+                            &format!("Memory maps reloaded, {count} regions to scan"),
+                            AppMessageType::Info,
+                        ),
+                        Err(e) => AppMessage::new(&format!("{e}"), AppMessageType::Error),
+                    };
+                }
+            }
+            Command::PinResults => {
+                if let Some(scan) = &mut self.scan {
+                    scan.pin_results();
+                    self.app_message = AppMessage::new(
+                        &format!("Pinned {} results as A", scan.results.len()),
+                        AppMessageType::Info,
+                    );
+                }
+            }
+            Command::CycleResultView => {
+                if let Some(scan) = &mut self.scan {
+                    let view = scan.result_view.next();
+                    match scan.set_result_view(view) {
+                        Err(e) => {
+                            self.app_message =
+                                AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                        }
+                        Ok(results) => {
+                            let len = results.len();
+                            self.ui.list_states.scan_results.select(if len == 0 {
+                                None
+                            } else {
+                                Some(0)
+                            });
+                            self.reset_results_scroll();
+                            self.app_message = AppMessage::new(
+                                &format!("Showing {}: {len} results", view.get_string()),
+                                AppMessageType::Info,
+                            );
+                        }
+                    }
+                    self.order_results();
+                }
+            }
+            Command::ToggleGroupResults => {
+                if self.scan.is_some() {
+                    self.group_results = !self.group_results;
+                    self.ui.list_states.scan_results.select(Some(0));
+                    self.reset_results_scroll();
+                    let msg = if self.group_results {
+                        "Results are grouped by module, Space/Enter expands a group"
+                    } else {
+                        "Results are shown as a flat list"
+                    };
+                    self.app_message = AppMessage::new(msg, AppMessageType::Info);
+                }
+            }
+            Command::ToggleChangedFirst => {
+                if self.scan.is_some() {
+                    self.changed_first = !self.changed_first;
+                    self.order_results();
+                    self.ui.list_states.scan_results.select(Some(0));
+                    self.ui.scroll_states.scan_results_vertical =
+                        self.ui.scroll_states.scan_results_vertical.position(0);
+                    let msg = if self.changed_first {
+                        "Changed results are shown first"
+                    } else {
+                        "Results are shown in address order"
+                    };
+                    self.app_message = AppMessage::new(msg, AppMessageType::Info);
+                }
             }
 
             // Result commands
             Command::AddToWatchlist => {
-                if let Some(scan) = &mut self.scan
+                if let Some(scan) = &self.scan
                     && self.ui.selected_widgets.scan_view_selected_widget
                         == ScanViewWidget::ScanResults
-                    && let Some(selected) = self.ui.list_states.scan_results.selected()
-                    && let Some(result) = scan.results.get(selected)
+                    && let Some(selected) = self.selected_result_index()
+                    && let Some(result) = scan.results.get(selected).cloned()
                 {
-                    scan.add_to_watchlist(result.clone());
-                    self.ui.scroll_states.scan_watchlist_vertical = self
-                        .ui
-                        .scroll_states
-                        .scan_watchlist_vertical
-                        .content_length(scan.watchlist.len());
-                    if self.ui.list_states.scan_watchlist.selected().is_none()
-                        && !scan.watchlist.is_empty()
-                    {
-                        self.ui.list_states.scan_watchlist.select(Some(0));
-                    }
+                    self.add_to_watchlist(result);
                     self.app_message =
                         AppMessage::new("Address added to watchlist", AppMessageType::Info);
                 }
             }
             Command::RemoveFromWatchlist => {
-                if let Some(scan) = &mut self.scan
-                    && self.ui.selected_widgets.scan_view_selected_widget
-                        == ScanViewWidget::WatchList
-                    && let Some(selected) = self.ui.list_states.scan_watchlist.selected()
+                if self.ui.selected_widgets.scan_view_selected_widget != ScanViewWidget::WatchList {
+                    return;
+                }
+                let Some(scan) = &mut self.scan else {
+                    return;
+                };
+
+                // marked entries go all at once, otherwise only the selected one
+                if !self.marked_watchlist.is_empty() {
+                    let removed = scan.remove_addresses_from_watchlist(&self.marked_watchlist);
+                    self.marked_watchlist.clear();
+                    self.app_message = AppMessage::new(
+                        &format!("{removed} addresses removed from watchlist"),
+                        AppMessageType::Info,
+                    );
+                } else if let Some(selected) = self.ui.list_states.scan_watchlist.selected()
                     && let Some(result) = scan.watchlist.get(selected)
                 {
                     scan.remove_from_watchlist(result.address);
-                    self.ui.scroll_states.scan_watchlist_vertical = self
-                        .ui
-                        .scroll_states
-                        .scan_watchlist_vertical
-                        .content_length(scan.watchlist.len());
                     self.app_message =
                         AppMessage::new("Address removed from watchlist", AppMessageType::Info);
                 }
+                self.update_watchlist_state();
+            }
+            Command::FilterValidPointers => {
+                let Some(scan) = &mut self.scan else {
+                    return;
+                };
+                match scan.filter_valid_pointers(self.pointer_width) {
+                    Err(e) => {
+                        self.app_message =
+                            AppMessage::new(&self.scan_error_message(&e), AppMessageType::Error);
+                    }
+                    Ok(removed) => {
+                        self.app_message = AppMessage::new(
+                            &format!(
+                                "Removed {removed} results that are not valid pointers, {} left",
+                                scan.results.len()
+                            ),
+                            AppMessageType::Info,
+                        );
+                        self.order_results();
+                        self.reset_results_scroll();
+                    }
+                }
+            }
+            Command::ToggleRecording => {
+                if self.scan.is_none() {
+                    return;
+                }
+
+                if let Some(path) = self.recording.take() {
+                    self.app_message = AppMessage::new(
+                        &format!("Stopped recording, edits were saved to {}", path.display()),
+                        AppMessageType::Info,
+                    );
+                } else if let Some(process) = &self.selected_process {
+                    let path = script::get_script_path(&process.name);
+                    self.app_message = AppMessage::new(
+                        &format!("Recording edits to {}", path.display()),
+                        AppMessageType::Info,
+                    );
+                    self.recording = Some(path);
+                }
+            }
+            Command::ExportAuditLog => {
+                let path = core::audit::get_export_path();
+                self.app_message = match self.audit_log.export(Path::new(&path)) {
+                    Ok(count) => AppMessage::new(
+                        &format!("Exported {count} audit log entries to {path}"),
+                        AppMessageType::Info,
+                    ),
+                    Err(e) => AppMessage::new(
+                        &format!("Could not export the audit log: {e}"),
+                        AppMessageType::Error,
+                    ),
+                };
+            }
+            Command::ToggleEndianness => {
+                if let Some(scan) = &mut self.scan
+                    && self.ui.selected_widgets.scan_view_selected_widget
+                        == ScanViewWidget::WatchList
+                    && let Some(selected) = self.ui.list_states.scan_watchlist.selected()
+                    && let Some(address) = scan.watchlist.get(selected).map(|w| w.address)
+                    && let Some(endianness) = scan.toggle_watchlist_endianness(address)
+                {
+                    self.app_message = AppMessage::new(
+                        &format!("0x{address:x} is now read as {}", endianness.get_string()),
+                        AppMessageType::Info,
+                    );
+                }
+            }
+            Command::ClearWatchlist => {
+                if let Some(scan) = &self.scan
+                    && !scan.watchlist.is_empty()
+                {
+                    self.go_to(CurrentScreen::ClearingWatchlist);
+                }
+            }
+            Command::ConfirmClearWatchlist => {
+                if let Some(scan) = &mut self.scan {
+                    scan.clear_watchlist();
+                }
+                self.marked_watchlist.clear();
+                self.update_watchlist_state();
+                self.app_message = AppMessage::new("Watchlist cleared", AppMessageType::Info);
+                self.go_back();
             }
             Command::EditValue => match self.ui.selected_widgets.scan_view_selected_widget {
                 ScanViewWidget::ValueInput => self.insert_mode_for(SelectedInput::ScanValue),
@@ -1284,15 +2985,21 @@ impl App {
                     self.insert_mode_for(SelectedInput::StartAddress)
                 }
                 ScanViewWidget::EndAddressInput => self.insert_mode_for(SelectedInput::EndAddress),
+                ScanViewWidget::StepInput => self.insert_mode_for(SelectedInput::Step),
                 ScanViewWidget::ScanResults | ScanViewWidget::WatchList => {
+                    if self.ui.selected_widgets.scan_view_selected_widget
+                        == ScanViewWidget::ScanResults
+                        && self.toggle_selected_group()
+                    {
+                        return;
+                    }
+
+                    let selected_index = match self.ui.selected_widgets.scan_view_selected_widget {
+                        ScanViewWidget::ScanResults => self.selected_result_index(),
+                        _ => self.ui.list_states.scan_watchlist.selected(),
+                    };
                     self.selected_value = self.scan.as_ref().and_then(|scan| {
-                        let selected_index =
-                            match self.ui.selected_widgets.scan_view_selected_widget {
-                                ScanViewWidget::ScanResults => {
-                                    self.ui.list_states.scan_results.selected()
-                                }
-                                _ => self.ui.list_states.scan_watchlist.selected(),
-                            }?;
+                        let selected_index = selected_index?;
 
                         let list = match self.ui.selected_widgets.scan_view_selected_widget {
                             ScanViewWidget::ScanResults => &scan.results,
@@ -1302,23 +3009,18 @@ impl App {
                         list.get(selected_index).cloned()
                     });
 
+                    // read-only values can be edited too, the editor tells the page
+                    // protection is lifted for the write
                     if let Some(selected_value) = &self.selected_value {
-                        if selected_value.is_read_only() {
-                            self.app_message = AppMessage::new(
-                                "Cannot edit read-only memory region",
-                                AppMessageType::Error,
-                            );
-                        } else {
-                            match selected_value.get_string() {
-                                Err(e) => {
-                                    self.app_message =
-                                        AppMessage::new(&format!("{e}"), AppMessageType::Info);
-                                }
-                                Ok(result_value) => {
-                                    self.ui.input_buffers.result_value = result_value;
-                                    self.insert_mode_for(SelectedInput::ResultValue);
-                                    self.go_to(CurrentScreen::ValueEditing);
-                                }
+                        match selected_value.get_display_string(&self.config.display) {
+                            Err(e) => {
+                                self.app_message =
+                                    AppMessage::new(&format!("{e}"), AppMessageType::Info);
+                            }
+                            Ok(result_value) => {
+                                self.ui.input_buffers.result_value = result_value;
+                                self.insert_mode_for(SelectedInput::ResultValue);
+                                self.go_to(CurrentScreen::ValueEditing);
                             }
                         }
                     } else {
@@ -1328,23 +3030,100 @@ impl App {
                         );
                     }
                 }
-                ScanViewWidget::PermissionsCheckbox => {
-                    self.handle_command(Command::ToggleReadWrite);
+                ScanViewWidget::PermissionsCheckbox
+                | ScanViewWidget::HeapStackCheckbox
+                | ScanViewWidget::BigEndianCheckbox
+                | ScanViewWidget::CompareModeSelect
+                | ScanViewWidget::AlignmentSelect
+                | ScanViewWidget::ScanOptions => {
+                    self.handle_command(Command::ToggleCheckbox);
                 }
                 _ => {}
             },
+            Command::IncrementValue => self.step_watch_value(self.watch_step),
+            Command::DecrementValue => self.step_watch_value(self.watch_step.wrapping_neg()),
+            Command::CopyPtraceFix => {
+                if !self.can_fix_ptrace_scope() {
+                    return;
+                }
+                if let Some(clipboard) = &mut self.clipboard {
+                    if clipboard.set_text(core::mem::PTRACE_SCOPE_FIX_CMD).is_ok() {
+                        self.app_message = AppMessage::new(
+                            "Fix command copied to clipboard",
+                            AppMessageType::Info,
+                        );
+                    } else {
+                        self.app_message =
+                            AppMessage::new("Failed to copy to clipboard", AppMessageType::Error);
+                    }
+                } else {
+                    self.app_message =
+                        AppMessage::new("Clipboard not available", AppMessageType::Error);
+                }
+            }
+            Command::CopyCodesign => {
+                if let Some(clipboard) = &mut self.clipboard {
+                    if clipboard.set_text(core::mem::MACOS_SIGN_CMD).is_ok() {
+                        self.app_message = AppMessage::new(
+                            "Sign command copied to clipboard",
+                            AppMessageType::Info,
+                        );
+                    } else {
+                        self.app_message =
+                            AppMessage::new("Failed to copy to clipboard", AppMessageType::Error);
+                    }
+                } else {
+                    self.app_message =
+                        AppMessage::new("Clipboard not available", AppMessageType::Error);
+                }
+            }
+            Command::ImportAddresses => {
+                if self.scan.is_some() {
+                    self.insert_mode_for(SelectedInput::ImportPath);
+                    self.go_to(CurrentScreen::ImportAddresses);
+                }
+            }
+            Command::CopyWatchlist => {
+                let Some(scan) = &self.scan else {
+                    return;
+                };
+                if scan.watchlist.is_empty() {
+                    self.app_message = AppMessage::new("Watchlist is empty", AppMessageType::Error);
+                    return;
+                }
+
+                // without the module list every entry is listed by its absolute address
+                let ranges = get_named_ranges(scan.pid).unwrap_or_default();
+                let table = utils::format_watchlist_table(&scan.watchlist, &ranges);
+                if let Some(clipboard) = &mut self.clipboard {
+                    if clipboard.set_text(table).is_ok() {
+                        self.app_message = AppMessage::new(
+                            &format!(
+                                "Copied {} watchlist entries as a table",
+                                scan.watchlist.len()
+                            ),
+                            AppMessageType::Info,
+                        );
+                    } else {
+                        self.app_message =
+                            AppMessage::new("Failed to copy to clipboard", AppMessageType::Error);
+                    }
+                } else {
+                    self.app_message =
+                        AppMessage::new("Clipboard not available", AppMessageType::Error);
+                }
+            }
             Command::CopyValue => {
+                let selected_index = match self.ui.selected_widgets.scan_view_selected_widget {
+                    ScanViewWidget::ScanResults => self.selected_result_index(),
+                    _ => self.ui.list_states.scan_watchlist.selected(),
+                };
                 if let Some(scan) = &self.scan
                     && (self.ui.selected_widgets.scan_view_selected_widget
                         == ScanViewWidget::ScanResults
                         || self.ui.selected_widgets.scan_view_selected_widget
                             == ScanViewWidget::WatchList)
                 {
-                    let selected_index = match self.ui.selected_widgets.scan_view_selected_widget {
-                        ScanViewWidget::ScanResults => self.ui.list_states.scan_results.selected(),
-                        _ => self.ui.list_states.scan_watchlist.selected(),
-                    };
-
                     let list = match self.ui.selected_widgets.scan_view_selected_widget {
                         ScanViewWidget::ScanResults => &scan.results,
                         _ => &scan.watchlist,
@@ -1409,6 +3188,101 @@ impl App {
         }
     }
 
+    /// Logs a successful write and appends it to the script while recording
+    fn record_edit(
+        &mut self,
+        address: u64,
+        path: Option<String>,
+        value_type: ValueType,
+        value: &[u8],
+    ) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+        self.audit_log.record(
+            scan.pid,
+            AuditAction::Write {
+                address,
+                value: value_type.get_value_string(value).unwrap_or_default(),
+            },
+        );
+
+        let Some(script) = &self.recording else {
+            return;
+        };
+
+        let entry = get_named_ranges(scan.pid)
+            .map_err(script::ScriptError::Memory)
+            .map(|ranges| ScriptEntry {
+                location: Location::new(address, path.as_deref(), &ranges),
+                value_type,
+                value: value_type.get_value_string(value).unwrap_or_default(),
+                endianness: scan.get_endianness(address),
+            });
+        if let Err(e) = entry.and_then(|entry| script::record(script, &entry)) {
+            self.app_message = AppMessage::new(
+                &format!("Edit was not recorded: {e}"),
+                AppMessageType::Error,
+            );
+        }
+    }
+
+    fn step_watch_value(&mut self, step: i64) {
+        if self.ui.selected_widgets.scan_view_selected_widget != ScanViewWidget::WatchList {
+            return;
+        }
+
+        if let Some(scan) = &mut self.scan
+            && let Some(selected) = self.ui.list_states.scan_watchlist.selected()
+            && let Some(result) = scan.watchlist.get(selected)
+        {
+            if result.is_read_only() {
+                self.app_message = AppMessage::new(
+                    "Read-only memory region, edit the value with Enter to write it anyway",
+                    AppMessageType::Error,
+                );
+                return;
+            }
+
+            let (address, value_type) = (result.address, result.value_type);
+            let path = result.path.clone();
+            match scan.step_value(address, value_type, step) {
+                Err(ScanError::TypeMismatch) => {
+                    self.app_message = AppMessage::new(
+                        &format!(
+                            "Values of type {} can not be stepped",
+                            value_type.get_string()
+                        ),
+                        AppMessageType::Error,
+                    );
+                }
+                Err(e @ ScanError::ProcessChanged(_)) => {
+                    self.app_message = AppMessage::new(&format!("{e}"), AppMessageType::Error);
+                }
+                Err(e) => {
+                    self.app_message = AppMessage::new(
+                        &format!("Error while updating memory address: {e}"),
+                        AppMessageType::Error,
+                    );
+                }
+                Ok(value) => {
+                    self.app_action = Some(AppAction::Refresh);
+                    self.app_message = AppMessage::new(
+                        &format!(
+                            "Value at address 0x{:x} set to {}",
+                            address,
+                            value_type
+                                .format_value(&value, &self.config.display)
+                                .unwrap_or_default()
+                        ),
+                        AppMessageType::Info,
+                    );
+                    self.record_edit(address, path, value_type, &value);
+                }
+            }
+        }
+    }
+
     // Handle navigation (list movement)
     fn handle_navigate(&mut self, dir: Direction) {
         match self.state.current_screen {
@@ -1426,14 +3300,42 @@ impl App {
                     );
                 }
             }
+            CurrentScreen::MemoryMap => {
+                utils::handle_list_navigation(
+                    dir,
+                    &mut self.ui.list_states.memory_map,
+                    self.memory_map.len(),
+                    Some(&mut self.ui.scroll_states.memory_map_vertical),
+                    &mut self.ui.last_g_press_time,
+                );
+            }
+            CurrentScreen::RegionPresets => {
+                utils::handle_list_navigation(
+                    dir,
+                    &mut self.ui.list_states.region_presets,
+                    self.region_presets.len(),
+                    None,
+                    &mut self.ui.last_g_press_time,
+                );
+            }
+            CurrentScreen::MatchDensity => {
+                utils::handle_list_navigation(
+                    dir,
+                    &mut self.ui.list_states.match_density,
+                    self.match_density.len(),
+                    None,
+                    &mut self.ui.last_g_press_time,
+                );
+            }
             CurrentScreen::Scan => {
+                let result_rows_len = self.result_rows().len();
                 if let Some(scan) = &mut self.scan {
                     match self.ui.selected_widgets.scan_view_selected_widget {
                         ScanViewWidget::ScanResults => {
                             utils::handle_list_navigation(
                                 dir,
                                 &mut self.ui.list_states.scan_results,
-                                scan.results.len(),
+                                result_rows_len,
                                 Some(&mut self.ui.scroll_states.scan_results_vertical),
                                 &mut self.ui.last_g_press_time,
                             );
@@ -1472,48 +3374,86 @@ impl App {
                                         AppMessageType::Error,
                                     );
                                 }
-
-                                // when string or hex type is selected ReadSize option should be available
-                                if scan.value_type == ValueType::String || scan.value_type == ValueType::Hex {
-                                    let idx = self
-                                        .ui
-                                        .selected_widgets
-                                        .scan_view_widgets
-                                        .iter()
-                                        .position(|x| *x == ScanViewWidget::ValueTypeSelect)
-                                        .unwrap();
-                                    if !self
-                                        .ui
-                                        .selected_widgets
-                                        .scan_view_widgets
-                                        .contains(&ScanViewWidget::ReadSize)
-                                    {
-                                        self.ui
-                                            .selected_widgets
-                                            .scan_view_widgets
-                                            .insert(idx + 1, ScanViewWidget::ReadSize);
-                                    }
-                                } else if let Some(idx) = self
-                                    .ui
-                                    .selected_widgets
-                                    .scan_view_widgets
-                                    .iter()
-                                    .position(|x| *x == ScanViewWidget::ReadSize)
-                                {
-                                    self.ui.selected_widgets.scan_view_widgets.remove(idx);
+                                // an invalid config value is reported when the scan view opens
+                                if let Some(alignment) = self.config.scan.alignment {
+                                    let _ = scan.set_alignment(alignment);
                                 }
 
                                 self.app_message = AppMessage::default();
                             }
                         }
+                        ScanViewWidget::ScanOptions => {
+                            utils::handle_list_navigation(
+                                dir,
+                                &mut self.ui.list_states.scan_options,
+                                self.scan_options.len(),
+                                None,
+                                &mut self.ui.last_g_press_time,
+                            );
+                        }
                         _ => {}
                     }
                 }
+
+                if self.ui.selected_widgets.scan_view_selected_widget
+                    == ScanViewWidget::ValueTypeSelect
+                {
+                    self.update_read_size_widget();
+                }
             }
             _ => {}
         }
     }
 
+    // when string or hex type is selected ReadSize option should be available
+    fn update_read_size_widget(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        let visible = scan.value_type.is_variable_size();
+        self.set_widget_visible(
+            ScanViewWidget::ReadSize,
+            ScanViewWidget::ValueTypeSelect,
+            visible,
+        );
+    }
+
+    // the percent input is only needed by the within percent comparison
+    fn update_compare_percent_widget(&mut self) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        let visible = scan.compare_mode == CompareMode::WithinPercent;
+        self.set_widget_visible(
+            ScanViewWidget::ComparePercent,
+            ScanViewWidget::CompareModeSelect,
+            visible,
+        );
+    }
+
+    // adds or removes an optional widget from the Tab order right after `after`
+    fn set_widget_visible(&mut self, widget: ScanViewWidget, after: ScanViewWidget, visible: bool) {
+        let widgets = &mut self.ui.selected_widgets.scan_view_widgets;
+        if visible {
+            let idx = widgets.iter().position(|x| *x == after).unwrap();
+            if !widgets.contains(&widget) {
+                widgets.insert(idx + 1, widget);
+            }
+        } else if let Some(idx) = widgets.iter().position(|x| *x == widget) {
+            widgets.remove(idx);
+        }
+
+        // keep the selection index pointing at the selected widget
+        if let Some(idx) = widgets
+            .iter()
+            .position(|x| *x == self.ui.selected_widgets.scan_view_selected_widget)
+        {
+            self.ui.selected_widgets.scan_view_selected_widget_index = idx;
+        }
+    }
+
     fn handle_insert_mode_event(&mut self, key: KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
@@ -1532,11 +3472,18 @@ impl App {
         let tick_rate = Duration::from_millis(250);
         let mut last_tick = Instant::now();
         self.show_process_list();
+        // preflight, nothing can be attached without root or the debugger entitlement
+        if self.macos_setup.is_some_and(|setup| !setup.can_attach()) {
+            self.ui.input_mode = InputMode::Normal;
+            self.go_to(CurrentScreen::MacosSetup);
+        }
         loop {
             if self.state.should_exit {
                 return Ok(());
             }
 
+            self.poll_running_scan();
+            self.poll_rpc_calls();
             terminal.draw(|f| super::ui::draw_ui(f, self))?;
 
             if let Some(app_action) = &mut self.app_action {
@@ -1544,12 +3491,19 @@ impl App {
                     AppAction::New => self.new_scan(),
                     AppAction::Next => self.next_scan(),
                     AppAction::Refresh => self.refresh_scan(),
+                    AppAction::Repeat => self.repeat_scan(),
                 }
                 self.app_action = None;
                 continue;
             }
 
-            let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+            // redraw the progress of a running scan more often
+            // and answer requests of the socket sooner
+            let timeout = if self.running_scan.is_some() || self.rpc_calls.is_some() {
+                SCAN_PROGRESS_REDRAW
+            } else {
+                tick_rate.saturating_sub(last_tick.elapsed())
+            };
             if event::poll(timeout)?
                 && let Event::Key(key) = event::read()?
             {
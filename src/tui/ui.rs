@@ -4,22 +4,29 @@ use ratatui::{
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Clear, HighlightSpacing, List, ListItem, Paragraph, Scrollbar,
+        Block, Borders, Clear, Gauge, HighlightSpacing, List, ListItem, Paragraph, Scrollbar,
         ScrollbarOrientation, Wrap,
     },
 };
 
 use crate::{
-    core::scan::ValueType,
-    tui::app::{App, AppMessageType, CurrentScreen, InputMode, ScanViewWidget, SelectedInput},
+    core::scan::{CompareMode, Endianness, ScanProgress, ScanResult},
+    tui::{
+        app::{
+            App, AppMessageType, CurrentScreen, InputMode, ResultRow, ScanViewWidget, SelectedInput,
+        },
+        utils::{format_bytes, format_perms},
+    },
 };
 
 pub fn draw_process_list(frame: &mut Frame, app: &mut App) {
+    let ptrace_hint = app.ptrace_hint();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(100),
             Constraint::Length(3),
+            Constraint::Length(if ptrace_hint.is_some() { 1 } else { 0 }),
             Constraint::Length(2),
         ])
         .split(frame.area());
@@ -73,21 +80,43 @@ pub fn draw_process_list(frame: &mut Frame, app: &mut App) {
         )),
     }
 
+    // Attach warning, replaced by the app message once the fix command is copied
+    if let Some(hint) = ptrace_hint {
+        let warning = if app.app_message.msg.is_empty() {
+            hint
+        } else {
+            app.app_message.msg.as_str()
+        };
+        let warning_bar = Paragraph::new(warning)
+            .style(Style::default().fg(Color::Red))
+            .alignment(Alignment::Center);
+        frame.render_widget(warning_bar, chunks[2]);
+    }
+
     // Help text
-    let help_text = Line::from(vec![
+    let mut help_text_items = vec![
         Span::from("↑/k: Up | ").fg(Color::Green),
         Span::from("↓/j: Down | ").fg(Color::Green),
         Span::from("Tab/Shift Tab: Change Pane | ").fg(Color::Green),
         Span::from("r: Refresh | ").fg(Color::Green),
         Span::from("Enter: Select | ").fg(Color::Green),
-        Span::from("q: Quit").fg(Color::Green),
-    ]);
+    ];
 
-    let help_bar = Paragraph::new(help_text)
+    if app.can_fix_ptrace_scope() {
+        help_text_items.push(Span::from("f: Copy fix command | ").fg(Color::Green));
+    }
+
+    if !app.audit_log.entries().is_empty() {
+        help_text_items.push(Span::from("L: Export audit log | ").fg(Color::Green));
+    }
+
+    help_text_items.push(Span::from("q: Quit").fg(Color::Green));
+
+    let help_bar = Paragraph::new(Line::from(help_text_items))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::NONE));
 
-    frame.render_widget(help_bar, chunks[2]);
+    frame.render_widget(help_bar, chunks[3]);
 }
 
 fn get_active_widget_style(app: &App, widget: ScanViewWidget) -> Style {
@@ -98,9 +127,24 @@ fn get_active_widget_style(app: &App, widget: ScanViewWidget) -> Style {
     }
 }
 
+/// Shows the parsed address once the input is accepted, so there is no doubt how it was read
+fn get_address_title(app: &App, label: &str, input: SelectedInput, address: Option<u64>) -> String {
+    match address {
+        Some(address)
+            if app.ui.input_mode != InputMode::Insert || app.ui.selected_input != Some(input) =>
+        {
+            format!("{label} = 0x{address:x}")
+        }
+        _ => format!("{label} - hex, d:dec or module+off (optional)"),
+    }
+}
+
 fn get_message_style(app: &App) -> Style {
     let mut style = match app.app_message.msg_type {
         AppMessageType::Info => Style::default(),
+        AppMessageType::Error if app.config.ui.high_contrast => {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        }
         AppMessageType::Error => Style::default().bg(Color::Red),
     };
 
@@ -110,6 +154,46 @@ fn get_message_style(app: &App) -> Style {
     style
 }
 
+/// High contrast mode marks static addresses with `S` and an underline, not only with green
+fn mark_static(app: &App, result: &ScanResult, style: Style) -> (&'static str, Style) {
+    if !app.config.ui.high_contrast {
+        return ("", style);
+    }
+
+    if result.is_static() {
+        ("S ", style.add_modifier(Modifier::UNDERLINED))
+    } else {
+        ("  ", style)
+    }
+}
+
+fn draw_scan_progress(frame: &mut Frame, progress: &ScanProgress, area: Rect) {
+    let block = Block::bordered().title("Scanning");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut label = format!(
+        "{} of {}",
+        format_bytes(progress.bytes_scanned),
+        format_bytes(progress.bytes_total)
+    );
+    if progress.regions_total > 0 {
+        label.push_str(&format!(
+            ", {}/{} regions",
+            progress.regions_done, progress.regions_total
+        ));
+    }
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Blue))
+        .ratio(progress.ratio())
+        .label(label);
+    let gauge_area = Rect {
+        height: inner.height.min(1),
+        ..inner
+    };
+    frame.render_widget(gauge, gauge_area);
+}
+
 pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -121,7 +205,12 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         .split(frame.area());
 
     let scan_results_frame = chunks[0];
-    let watchlist_rect = chunks[1];
+    let watchlist_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(85), Constraint::Percentage(15)])
+        .split(chunks[1]);
+    let watchlist_rect = watchlist_chunks[0];
+    let step_rect = watchlist_chunks[1];
     let scan_view_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
@@ -136,47 +225,117 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         scan_result_items = &scan.results;
         watchlist_items = &scan.watchlist;
     }
+    let digits = address_digits(app);
 
-    let result_items: Vec<ListItem> = scan_result_items
-        .iter()
-        .map(|result| {
-            let color = if result.is_read_only() {
-                Color::DarkGray
-            } else {
-                Color::Green
-            };
-            ListItem::new(Line::from(format!(
-                "0x{:x} | {}",
-                result.address,
-                result.get_string().unwrap_or("TypeMismatch".to_owned())
+    let result_item = |result: &ScanResult| {
+        let color = if result.is_read_only() {
+            Color::DarkGray
+        } else if result.is_static() {
+            Color::Green
+        } else {
+            Color::White
+        };
+        let mut style = Style::new().fg(color);
+        if result.has_changed() {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        let (static_mark, style) = mark_static(app, result, style);
+        // first scan value, with the drift for numeric types
+        let first = match result.get_drift() {
+            Some(drift) => format!(
+                "{} ({drift:+})",
+                result
+                    .get_first_string(&app.config.display)
+                    .unwrap_or("TypeMismatch".to_owned())
+            ),
+            None => result
+                .get_first_string(&app.config.display)
+                .unwrap_or("TypeMismatch".to_owned()),
+        };
+        ListItem::new(Line::from(format!(
+            "{static_mark}0x{:0digits$x} {} {} | {} | first: {}{}",
+            result.address,
+            format_perms(&result.perms),
+            result.get_location(),
+            result
+                .get_display_string(&app.config.display)
+                .unwrap_or("TypeMismatch".to_owned()),
+            first,
+            if result.has_changed() { " *" } else { "" }
+        )))
+        .style(style)
+    };
+
+    let result_items: Vec<ListItem> = app
+        .result_rows()
+        .into_iter()
+        .map(|row| match row {
+            ResultRow::Group {
+                name,
+                count,
+                expanded,
+            } => ListItem::new(Line::from(format!(
+                "{} {name} ({count})",
+                if expanded { "▾" } else { "▸" }
             )))
-            .style(Style::new().fg(color))
+            .style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            ResultRow::Result(idx) => result_item(&scan_result_items[idx]),
         })
         .collect();
 
+    let changed_count = scan_result_items.iter().filter(|r| r.has_changed()).count();
+    let mut results_title = if app.changed_first {
+        format!("Scan Results ({changed_count} changed first)")
+    } else {
+        String::from("Scan Results")
+    };
+    if let Some(scan) = &app.scan
+        && scan.truncated_count() > 0
+    {
+        results_title.push_str(&format!(
+            " [{} matches (truncated), first {} shown]",
+            scan.match_count(),
+            scan.results.len()
+        ));
+    }
+    if let Some(scan) = &app.scan
+        && let Some(pinned) = &scan.pinned
+    {
+        results_title.push_str(&format!(
+            " [{}, A: {} pinned]",
+            scan.result_view.get_string(),
+            pinned.len()
+        ));
+    }
+
     let result_list_widget = List::new(result_items)
         .highlight_style(Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD))
         .highlight_symbol(">> ")
         .highlight_spacing(HighlightSpacing::Always)
         .block(
             Block::bordered()
-                .title("Scan Results")
+                .title(results_title)
                 .style(get_active_widget_style(app, ScanViewWidget::ScanResults)),
         );
 
-    frame.render_stateful_widget(
-        result_list_widget,
-        scan_results_rect,
-        &mut app.ui.list_states.scan_results,
-    );
+    // the results are replaced by the progress while a scan runs
+    if let Some(running) = &app.running_scan {
+        draw_scan_progress(frame, &running.progress, scan_results_rect);
+    } else {
+        frame.render_stateful_widget(
+            result_list_widget,
+            scan_results_rect,
+            &mut app.ui.list_states.scan_results,
+        );
 
-    frame.render_stateful_widget(
-        Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓")),
-        scan_results_rect,
-        &mut app.ui.scroll_states.scan_results_vertical,
-    );
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            scan_results_rect,
+            &mut app.ui.scroll_states.scan_results_vertical,
+        );
+    }
 
     // Watchlist
     let watchlist_items_display: Vec<ListItem> = watchlist_items
@@ -184,15 +343,35 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         .map(|result| {
             let color = if result.is_read_only() {
                 Color::Yellow
-            } else {
+            } else if result.is_static() {
                 Color::Green
+            } else {
+                Color::White
+            };
+            // marks are only shown once something is marked for removal
+            let mark = if app.marked_watchlist.is_empty() {
+                ""
+            } else if app.marked_watchlist.contains(&result.address) {
+                "[X] "
+            } else {
+                "[ ] "
             };
+            // only overridden byte orders are tagged
+            let endianness = match result.endianness {
+                Endianness::Little => "",
+                Endianness::Big => " BE",
+            };
+            let (static_mark, style) = mark_static(app, result, Style::new().fg(color));
             ListItem::new(Line::from(format!(
-                "0x{:x} | {}",
+                "{mark}{static_mark}0x{:0digits$x} {} {} | {}{endianness}",
                 result.address,
-                result.get_string().unwrap_or("TypeMismatch".to_owned())
+                format_perms(&result.perms),
+                result.get_location(),
+                result
+                    .get_display_string(&app.config.display)
+                    .unwrap_or("TypeMismatch".to_owned())
             )))
-            .style(Style::new().fg(color))
+            .style(style)
         })
         .collect();
 
@@ -219,23 +398,35 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         watchlist_rect,
         &mut app.ui.scroll_states.scan_watchlist_vertical,
     );
+
+    let step_input = Paragraph::new(app.ui.input_buffers.step.as_str())
+        .style(get_active_widget_style(app, ScanViewWidget::StepInput))
+        .block(Block::bordered().title("Step (+/-)"));
+    frame.render_widget(step_input, step_rect);
     //
     // Render Options
     let options_view_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(if app.show_stats { 8 } else { 0 }),
+            Constraint::Fill(1),
         ])
         .split(options_rect);
 
-    // Split Value input row to add checkbox
+    // Split Value input row to add the region selectors
     let value_input_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
+        .constraints([
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ])
         .split(options_view_chunks[0]);
 
     let value_input = Paragraph::new(app.ui.input_buffers.scan_value.as_str())
@@ -243,21 +434,72 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         .block(Block::bordered().title("Value"));
     frame.render_widget(value_input, value_input_chunks[0]);
 
-    // Readonly checkbox
-    let checkbox_symbol = if app.include_readonly_regions {
-        "[X]"
-    } else {
-        "[ ]"
-    };
-    let checkbox = Paragraph::new(checkbox_symbol)
+    // Permissions of the scanned regions
+    let checkbox = Paragraph::new(app.region_perms.get_string())
         .style(get_active_widget_style(
             app,
             ScanViewWidget::PermissionsCheckbox,
         ))
-        .block(Block::bordered().title("R+W"))
+        .block(Block::bordered().title("Perms"))
         .alignment(Alignment::Center);
     frame.render_widget(checkbox, value_input_chunks[1]);
 
+    let heap_stack_symbol = if app.heap_stack_only { "[X]" } else { "[ ]" };
+    let heap_stack_checkbox = Paragraph::new(heap_stack_symbol)
+        .style(get_active_widget_style(
+            app,
+            ScanViewWidget::HeapStackCheckbox,
+        ))
+        .block(Block::bordered().title("Heap/Stack"))
+        .alignment(Alignment::Center);
+    frame.render_widget(heap_stack_checkbox, value_input_chunks[2]);
+
+    // Comparison mode, the percent input is only shown for within percent scans
+    let compare_mode = app
+        .scan
+        .as_ref()
+        .map_or(CompareMode::Exact, |scan| scan.compare_mode);
+    let compare_block = Paragraph::new(compare_mode.get_string()).block(
+        Block::bordered()
+            .title("Compare (Space: change)")
+            .style(get_active_widget_style(
+                app,
+                ScanViewWidget::CompareModeSelect,
+            )),
+    );
+    let mut percent_box_x = 0;
+    let compare_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Percentage(if compare_mode == CompareMode::WithinPercent {
+                20
+            } else {
+                0
+            }),
+            Constraint::Percentage(20),
+        ])
+        .split(options_view_chunks[1]);
+    frame.render_widget(compare_block, compare_chunks[0]);
+
+    if compare_mode == CompareMode::WithinPercent {
+        let percent_input = Paragraph::new(app.ui.input_buffers.compare_percent.as_str())
+            .style(get_active_widget_style(app, ScanViewWidget::ComparePercent))
+            .block(Block::bordered().title("%"));
+        percent_box_x = compare_chunks[1].x;
+        frame.render_widget(percent_input, compare_chunks[1]);
+    }
+
+    let alignment = app.scan.as_ref().map_or(1, |scan| scan.alignment());
+    let alignment_block = Paragraph::new(format!("{alignment}B"))
+        .style(get_active_widget_style(
+            app,
+            ScanViewWidget::AlignmentSelect,
+        ))
+        .block(Block::bordered().title("Align"))
+        .alignment(Alignment::Center);
+    frame.render_widget(alignment_block, compare_chunks[2]);
+
     // Value Type Select
     let items: Vec<ListItem> = app
         .value_types
@@ -282,55 +524,157 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         )
         .highlight_symbol(">> ");
 
-    let mut read_size_box_x = 0;
-    if let Some(scan) = &app.scan
-        && (scan.value_type == ValueType::String || scan.value_type == ValueType::Hex)
-    {
-        let value_type_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
-            .split(options_view_chunks[1]);
+    // Split Value Type row to add the read size input and the byte order checkbox
+    let variable_size = app
+        .scan
+        .as_ref()
+        .is_some_and(|scan| scan.value_type.is_variable_size());
+    let value_type_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Percentage(if variable_size { 20 } else { 0 }),
+            Constraint::Percentage(20),
+        ])
+        .split(options_view_chunks[2]);
 
-        frame.render_stateful_widget(
-            list,
-            value_type_chunks[0],
-            &mut app.ui.list_states.value_type,
-        );
+    frame.render_stateful_widget(
+        list,
+        value_type_chunks[0],
+        &mut app.ui.list_states.value_type,
+    );
 
+    let mut read_size_box_x = 0;
+    if variable_size {
         let read_size_input = Paragraph::new(app.ui.input_buffers.read_size.as_str())
             .style(get_active_widget_style(app, ScanViewWidget::ReadSize))
             .block(Block::bordered().title("Read Size"));
         read_size_box_x = value_type_chunks[1].x;
         frame.render_widget(read_size_input, value_type_chunks[1]);
-    } else {
-        frame.render_stateful_widget(
-            list,
-            options_view_chunks[1],
-            &mut app.ui.list_states.value_type,
-        );
     }
+
+    let big_endian = app
+        .scan
+        .as_ref()
+        .is_some_and(|scan| scan.endianness() == Endianness::Big);
+    let big_endian_symbol = if big_endian { "[X]" } else { "[ ]" };
+    let big_endian_checkbox = Paragraph::new(big_endian_symbol)
+        .style(get_active_widget_style(
+            app,
+            ScanViewWidget::BigEndianCheckbox,
+        ))
+        .block(Block::bordered().title("Big-endian"))
+        .alignment(Alignment::Center);
+    frame.render_widget(big_endian_checkbox, value_type_chunks[2]);
     //
 
+    let (start_address, end_address) = app
+        .scan
+        .as_ref()
+        .map_or((None, None), |scan| scan.address_range());
+    // a module picked from the presets only scans its own mappings of the range
+    let start_label = match app.scan.as_ref().and_then(|scan| scan.module()) {
+        Some(module) => format!("Start Address ({module} only)"),
+        None => "Start Address".to_owned(),
+    };
     let start_address_input = Paragraph::new(app.ui.input_buffers.start_address.as_str())
         .style(get_active_widget_style(
             app,
             ScanViewWidget::StartAddressInput,
         ))
-        .block(Block::bordered().title("Start Address - hex (optional)"));
-    frame.render_widget(start_address_input, options_view_chunks[2]);
+        .block(Block::bordered().title(get_address_title(
+            app,
+            &start_label,
+            SelectedInput::StartAddress,
+            start_address,
+        )));
+    frame.render_widget(start_address_input, options_view_chunks[3]);
 
     let end_address_input = Paragraph::new(app.ui.input_buffers.end_address.as_str())
         .style(get_active_widget_style(
             app,
             ScanViewWidget::EndAddressInput,
         ))
-        .block(Block::bordered().title("End Address - hex (optional)"));
-    frame.render_widget(end_address_input, options_view_chunks[3]);
+        .block(Block::bordered().title(get_address_title(
+            app,
+            "End Address",
+            SelectedInput::EndAddress,
+            end_address,
+        )));
+    frame.render_widget(end_address_input, options_view_chunks[4]);
 
+    let scan_option_items: Vec<ListItem> = app
+        .scan_options
+        .iter()
+        .map(|option| {
+            let checkbox_symbol = if app.is_scan_option_enabled(*option) {
+                "[X]"
+            } else {
+                "[ ]"
+            };
+            ListItem::new(format!("{checkbox_symbol} {}", option.get_string()))
+        })
+        .collect();
+
+    let scan_options_list = List::new(scan_option_items)
+        .block(
+            Block::bordered()
+                .title("Scan Options")
+                .style(get_active_widget_style(app, ScanViewWidget::ScanOptions)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(
+        scan_options_list,
+        options_view_chunks[5],
+        &mut app.ui.list_states.scan_options,
+    );
+
+    if app.show_stats
+        && let Some(scan) = &app.scan
+    {
+        let stats = &scan.stats;
+        let stats_text = Text::from(vec![
+            Line::from(format!(
+                "Read: {} of {}",
+                format_bytes(stats.bytes_read),
+                format_bytes(stats.total_bytes)
+            )),
+            Line::from(format!("Regions: {}", stats.regions_scanned)),
+            Line::from(format!("Matches: {}", stats.matches)),
+            Line::from(format!("Elapsed: {:.1?}", stats.elapsed)),
+            Line::from(format!(
+                "Throughput: {}/s",
+                format_bytes(stats.throughput() as u64)
+            )),
+            Line::from(format!(
+                "Maps read {}s ago (M: reload)",
+                scan.regions_age().as_secs()
+            )),
+        ]);
+        let stats_box =
+            Paragraph::new(stats_text).block(Block::bordered().title("Scan Stats (i: hide)"));
+        frame.render_widget(stats_box, options_view_chunks[6]);
+    }
+
+    let msg_title = if app.config.ui.high_contrast
+        && app.app_message.msg_type == AppMessageType::Error
+        && !app.app_message.msg.is_empty()
+    {
+        "App Message [!] Error"
+    } else {
+        "App Message"
+    };
     let msg_box = Paragraph::new(app.app_message.msg.as_str())
         .style(get_message_style(app))
-        .block(Block::bordered().title("App Message"));
-    frame.render_widget(msg_box, options_view_chunks[4]);
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().title(msg_title));
+    frame.render_widget(msg_box, options_view_chunks[7]);
 
     match app.ui.input_mode {
         InputMode::Normal => {}
@@ -345,13 +689,21 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
                     }
                     SelectedInput::ReadSize => {
                         x = read_size_box_x + app.ui.character_index as u16 + 1;
+                        y = options_view_chunks[2].y + 1;
+                    }
+                    SelectedInput::ComparePercent => {
+                        x = percent_box_x + app.ui.character_index as u16 + 1;
                         y = options_view_chunks[1].y + 1;
                     }
                     SelectedInput::StartAddress => {
-                        y = options_view_chunks[2].y + 1;
+                        y = options_view_chunks[3].y + 1;
                     }
                     SelectedInput::EndAddress => {
-                        y = options_view_chunks[3].y + 1;
+                        y = options_view_chunks[4].y + 1;
+                    }
+                    SelectedInput::Step => {
+                        x = step_rect.x + app.ui.character_index as u16 + 1;
+                        y = step_rect.y + 1;
                     }
                     _ => {}
                 },
@@ -367,7 +719,8 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
     match app.ui.selected_widgets.scan_view_selected_widget {
         ScanViewWidget::ScanResults
         | ScanViewWidget::WatchList
-        | ScanViewWidget::ValueTypeSelect => {
+        | ScanViewWidget::ValueTypeSelect
+        | ScanViewWidget::ScanOptions => {
             help_text_items.extend(vec![
                 Span::from("↑/k: Up | ").fg(Color::Green),
                 Span::from("↓/j: Down | ").fg(Color::Green),
@@ -376,28 +729,74 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
         _ => {}
     }
 
+    match app.ui.selected_widgets.scan_view_selected_widget {
+        ScanViewWidget::PermissionsCheckbox
+        | ScanViewWidget::HeapStackCheckbox
+        | ScanViewWidget::BigEndianCheckbox
+        | ScanViewWidget::CompareModeSelect
+        | ScanViewWidget::AlignmentSelect
+        | ScanViewWidget::ScanOptions => {
+            help_text_items.push(Span::from("Space: Toggle | ").fg(Color::Green));
+        }
+        _ => {}
+    }
+
     help_text_items.push(Span::from("s: New Scan | ").fg(Color::Green));
+    help_text_items.push(Span::from("m: Memory Map | ").fg(Color::Green));
+    help_text_items.push(Span::from("p: Region Presets | ").fg(Color::Green));
+    if !app.show_stats {
+        help_text_items.push(Span::from("i: Stats | ").fg(Color::Green));
+    }
 
     if let Some(scan) = &app.scan
         && !scan.results.is_empty()
     {
         help_text_items.extend(vec![
             Span::from("n: Next Scan | ").fg(Color::Green),
+            Span::from(".: Repeat Scan | ").fg(Color::Green),
             Span::from("r: Refresh | ").fg(Color::Green),
         ]);
     }
 
     if app.ui.selected_widgets.scan_view_selected_widget == ScanViewWidget::ScanResults {
-        help_text_items.extend(vec![Span::from("w: Add to watchlist | ").fg(Color::Green)]);
+        help_text_items.extend(vec![
+            Span::from("w: Add to watchlist | ").fg(Color::Green),
+            Span::from("v: Changed first | ").fg(Color::Green),
+            Span::from("o: Group by module | ").fg(Color::Green),
+            Span::from("P: Valid pointers | ").fg(Color::Green),
+            Span::from("h: Matches per region | ").fg(Color::Green),
+            Span::from("a: Pin as A | ").fg(Color::Green),
+            Span::from("b: Compare A/B | ").fg(Color::Green),
+        ]);
     }
 
     if app.ui.selected_widgets.scan_view_selected_widget == ScanViewWidget::WatchList {
-        help_text_items.push(Span::from("d: Remove from watchlist | ").fg(Color::Green));
+        help_text_items.extend(vec![
+            Span::from("Space: Mark | ").fg(Color::Green),
+            Span::from("d: Remove from watchlist | ").fg(Color::Green),
+            Span::from("D: Clear | ").fg(Color::Green),
+            Span::from("e: Endianness | ").fg(Color::Green),
+            Span::from("C: Copy as table | ").fg(Color::Green),
+            Span::from("+/-: Step value | ").fg(Color::Green),
+        ]);
+    }
+
+    if app.scan.is_some() {
+        let recording = if app.recording.is_some() {
+            Span::from("R: Stop recording | ").fg(Color::Red)
+        } else {
+            Span::from("R: Record edits | ").fg(Color::Green)
+        };
+        help_text_items.push(recording);
+        help_text_items.push(Span::from("L: Export audit log | ").fg(Color::Green));
+        help_text_items.push(Span::from("I: Import addresses | ").fg(Color::Green));
+        help_text_items.push(Span::from("M: Reload maps | ").fg(Color::Green));
     }
 
     match app.ui.selected_widgets.scan_view_selected_widget {
         ScanViewWidget::ScanResults | ScanViewWidget::WatchList => {
             help_text_items.extend(vec![
+                Span::from("x: Hex/decimal | ").fg(Color::Green),
                 Span::from("c: Copy | ").fg(Color::Green),
                 Span::from("Enter/u: Update Value | ").fg(Color::Green),
             ]);
@@ -414,6 +813,261 @@ pub fn draw_scan_screen(frame: &mut Frame, app: &mut App) {
     frame.render_widget(help_bar, chunks[2]);
 }
 
+/// Hex digits addresses are padded to so they line up, 32-bit targets only need 8
+fn address_digits(app: &App) -> usize {
+    if app.pointer_width == 4 { 8 } else { 12 }
+}
+
+pub fn draw_memory_map_screen(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(100), Constraint::Length(2)])
+        .split(frame.area());
+
+    let digits = address_digits(app);
+    let items: Vec<ListItem> = app
+        .memory_map
+        .iter()
+        .map(|(region, stats)| {
+            let perms = format_perms(&region.perms);
+            let mut line = format!(
+                "0x{:0digits$x}-0x{:0digits$x} {perms} {:>10}",
+                region.start,
+                region.end,
+                format_bytes(region.end - region.start)
+            );
+            let mut style = Style::new();
+            if let Some(stats) = stats {
+                line.push_str(&format!(
+                    " | RSS {:>10} | Swap {:>10} | Shared {:>10} | Private {:>10}",
+                    format_bytes(stats.rss),
+                    format_bytes(stats.swap),
+                    format_bytes(stats.shared),
+                    format_bytes(stats.private),
+                ));
+                if stats.rss == 0 {
+                    style = style.fg(Color::DarkGray);
+                }
+            }
+            line.push_str(&format!(" | {}", region.path.as_deref().unwrap_or("")));
+            ListItem::new(Line::from(line)).style(style)
+        })
+        .collect();
+
+    let total_rss: u64 = app
+        .memory_map
+        .iter()
+        .filter_map(|(_, stats)| stats.map(|s| s.rss))
+        .sum();
+    let total_swap: u64 = app
+        .memory_map
+        .iter()
+        .filter_map(|(_, stats)| stats.map(|s| s.swap))
+        .sum();
+
+    let list_widget = List::new(items)
+        .highlight_style(Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ")
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(
+            Block::bordered()
+                .title(format!(
+                    "Memory Map ({} regions, RSS {}, Swap {})",
+                    app.memory_map.len(),
+                    format_bytes(total_rss),
+                    format_bytes(total_swap)
+                ))
+                .style(Style::default().fg(Color::Yellow)),
+        );
+    frame.render_stateful_widget(list_widget, chunks[0], &mut app.ui.list_states.memory_map);
+
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        chunks[0],
+        &mut app.ui.scroll_states.memory_map_vertical,
+    );
+
+    let help_text = Line::from(vec![
+        Span::from("↑/k: Up | ").fg(Color::Green),
+        Span::from("↓/j: Down | ").fg(Color::Green),
+        Span::from("r: Refresh | ").fg(Color::Green),
+        Span::from("Esc: Back | ").fg(Color::Green),
+        Span::from("q: Quit").fg(Color::Green),
+    ]);
+
+    let help_bar = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+
+    frame.render_widget(help_bar, chunks[1]);
+}
+
+pub fn draw_region_presets_screen(frame: &mut Frame, app: &mut App) {
+    draw_scan_screen(frame, app);
+
+    let area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app
+        .region_presets
+        .iter()
+        .map(|range| {
+            ListItem::new(Line::from(format!(
+                "{:<24} 0x{:x}-0x{:x} ({})",
+                range.name,
+                range.start,
+                range.end,
+                format_bytes(range.end - range.start)
+            )))
+        })
+        .collect();
+
+    let list_widget = List::new(items)
+        .highlight_style(Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ")
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(
+            Block::bordered()
+                .title(" Region Presets - Enter: Select | Esc: Back ")
+                .style(Style::default().bg(Color::DarkGray).fg(Color::White)),
+        );
+    frame.render_stateful_widget(list_widget, area, &mut app.ui.list_states.region_presets);
+}
+
+pub fn draw_match_density_screen(frame: &mut Frame, app: &mut App) {
+    draw_scan_screen(frame, app);
+
+    let area = centered_rect(70, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    // bars are relative to the densest region, which comes first
+    const BAR_WIDTH: usize = 20;
+    let max_count = app.match_density.first().map_or(1, |(_, count)| *count);
+    let items: Vec<ListItem> = app
+        .match_density
+        .iter()
+        .map(|(range, count)| {
+            let bar = "█".repeat((count * BAR_WIDTH).div_ceil(max_count));
+            ListItem::new(Line::from(vec![
+                Span::from(format!("{bar:<BAR_WIDTH$} ")).fg(Color::Red),
+                Span::from(format!(
+                    "{count:>8} {:<24} 0x{:x}-0x{:x} ({})",
+                    range.name,
+                    range.start,
+                    range.end,
+                    format_bytes(range.end - range.start)
+                )),
+            ]))
+        })
+        .collect();
+
+    let list_widget = List::new(items)
+        .highlight_style(Style::new().bg(Color::Blue).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ")
+        .highlight_spacing(HighlightSpacing::Always)
+        .block(
+            Block::bordered()
+                .title(" Matches per Region - Enter: Limit scan to region | Esc: Back ")
+                .style(Style::default().bg(Color::DarkGray).fg(Color::White)),
+        );
+    frame.render_stateful_widget(list_widget, area, &mut app.ui.list_states.match_density);
+}
+
+pub fn draw_clearing_watchlist_screen(frame: &mut Frame, app: &mut App) {
+    draw_scan_screen(frame, app);
+
+    let count = app.scan.as_ref().map_or(0, |scan| scan.watchlist.len());
+    let popup_block = Block::default()
+        .title(" Clear Watchlist ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::DarkGray).fg(Color::White));
+
+    let confirm_text = Text::from(vec![
+        Line::from(""),
+        Line::styled(
+            format!("Remove all {count} watchlist entries? (Y/N)"),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+
+    let confirm_paragraph = Paragraph::new(confirm_text)
+        .alignment(Alignment::Center)
+        .block(popup_block)
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(50, 20, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(confirm_paragraph, area);
+}
+
+pub fn draw_macos_setup_screen(frame: &mut Frame, app: &mut App) {
+    match app.state.screen_history.last() {
+        Some(CurrentScreen::Scan) => draw_scan_screen(frame, app),
+        _ => draw_process_list(frame, app),
+    }
+
+    let Some(setup) = app.macos_setup else {
+        return;
+    };
+
+    let check = |ok: bool| {
+        if ok {
+            Span::from("ok").fg(Color::Green)
+        } else {
+            Span::from("missing").fg(Color::Red)
+        }
+    };
+    let sip = match setup.sip_enabled {
+        Some(true) => Span::from("enabled").fg(Color::Yellow),
+        Some(false) => Span::from("disabled").fg(Color::Green),
+        None => Span::from("unknown"),
+    };
+
+    let mut lines = vec![
+        Line::from(vec![Span::from("Root: "), check(setup.is_root)]),
+        Line::from(vec![
+            Span::from("Debugger entitlement: "),
+            check(setup.debugger_entitlement),
+        ]),
+        Line::from(vec![Span::from("SIP: "), sip]),
+        Line::from(""),
+    ];
+    let steps = setup.steps();
+    if steps.is_empty() {
+        lines.push(Line::from(
+            "Everything is set up, the process may be protected or no longer running",
+        ));
+    }
+    for (i, step) in steps.into_iter().enumerate() {
+        lines.push(Line::from(format!("{}. {step}", i + 1)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "f: Copy sign command | Esc: Back",
+        Style::default().fg(Color::Green),
+    ));
+    if !app.app_message.msg.is_empty() {
+        lines.push(Line::from(app.app_message.msg.as_str()));
+    }
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .title(" macOS Setup ")
+                .borders(Borders::ALL)
+                .style(Style::default().bg(Color::DarkGray).fg(Color::White)),
+        )
+        .wrap(Wrap { trim: false });
+
+    let area = centered_rect(70, 50, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
 pub fn draw_exit_screen(frame: &mut Frame, _app: &mut App) {
     frame.render_widget(Clear, frame.area());
 
@@ -449,12 +1103,33 @@ pub fn draw_value_editing_screen(frame: &mut Frame, app: &mut App) {
     frame.render_widget(Clear, frame.area());
     let selected_value = app.selected_value.as_ref().unwrap();
 
+    // writes to read-only pages lift the protection for the write
+    let title = if selected_value.is_read_only() {
+        format!(
+            " Editing - 0x{:x} (read-only, Enter makes the page writable for the write) ",
+            selected_value.address
+        )
+    } else {
+        format!(" Editing - 0x{:x} ", selected_value.address)
+    };
     let popup_block = Block::default()
-        .title(format!(" Editing - 0x{:x} ", selected_value.address))
+        .title(title)
         .borders(Borders::ALL)
         .style(Style::default().bg(Color::DarkGray).fg(Color::White));
 
-    let value_input = Paragraph::new(app.ui.input_buffers.result_value.as_str())
+    // bit values get the bit numbers and a hint under the bits
+    let mut lines = vec![Line::from(app.ui.input_buffers.result_value.as_str())];
+    if app.is_editing_bits() {
+        lines.push(Line::from(Span::styled(
+            "76543210",
+            Style::default().fg(Color::Gray),
+        )));
+        lines.push(Line::from(Span::styled(
+            "<space> toggles the bit under the cursor",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    let value_input = Paragraph::new(lines)
         .style(Style::default().fg(Color::Yellow))
         .block(popup_block);
     let area = centered_rect(50, 30, frame.area());
@@ -465,6 +1140,25 @@ pub fn draw_value_editing_screen(frame: &mut Frame, app: &mut App) {
     frame.render_widget(value_input, area);
 }
 
+pub fn draw_import_addresses_screen(frame: &mut Frame, app: &mut App) {
+    frame.render_widget(Clear, frame.area());
+
+    let popup_block = Block::default()
+        .title(" Import addresses - path of a list, edit script or watchlist table ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::DarkGray).fg(Color::White));
+
+    let path_input = Paragraph::new(app.ui.input_buffers.import_path.as_str())
+        .style(Style::default().fg(Color::Yellow))
+        .block(popup_block);
+    let area = centered_rect(50, 30, frame.area());
+    frame.set_cursor_position(Position::new(
+        area.x + app.ui.character_index as u16 + 1,
+        area.y + 1,
+    ));
+    frame.render_widget(path_input, area);
+}
+
 pub fn draw_ui(frame: &mut Frame, app: &mut App) {
     match app.state.current_screen {
         CurrentScreen::ProcessList => {
@@ -473,9 +1167,27 @@ pub fn draw_ui(frame: &mut Frame, app: &mut App) {
         CurrentScreen::Scan => {
             draw_scan_screen(frame, app);
         }
+        CurrentScreen::MemoryMap => {
+            draw_memory_map_screen(frame, app);
+        }
+        CurrentScreen::RegionPresets => {
+            draw_region_presets_screen(frame, app);
+        }
+        CurrentScreen::MatchDensity => {
+            draw_match_density_screen(frame, app);
+        }
         CurrentScreen::ValueEditing => {
             draw_value_editing_screen(frame, app);
         }
+        CurrentScreen::ImportAddresses => {
+            draw_import_addresses_screen(frame, app);
+        }
+        CurrentScreen::ClearingWatchlist => {
+            draw_clearing_watchlist_screen(frame, app);
+        }
+        CurrentScreen::MacosSetup => {
+            draw_macos_setup_screen(frame, app);
+        }
         CurrentScreen::Exiting => {
             draw_exit_screen(frame, app);
         }
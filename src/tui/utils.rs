@@ -1,8 +1,13 @@
-use std::time::Instant;
+use std::{io::Write, time::Instant};
 
 use ratatui::widgets::{ListState, ScrollbarState};
 
 use super::app::Direction;
+use crate::core::{
+    mem::{MemoryRegionPerms, NamedRange},
+    scan::{Endianness, ScanResult, ScanStats},
+    script::Location,
+};
 
 pub mod cursor {
     pub fn move_cursor_left(input: &str, char_index: &mut usize) {
@@ -21,6 +26,20 @@ pub mod cursor {
         move_cursor_right(input, char_index);
     }
 
+    /// Flips the `0` or `1` under the cursor, the last one when the cursor is at the end
+    pub fn toggle_bit(input: &mut String, char_index: usize) {
+        let index = char_index.min(input.chars().count().saturating_sub(1));
+        *input = input
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '0' if i == index => '1',
+                '1' if i == index => '0',
+                c => c,
+            })
+            .collect();
+    }
+
     /// Returns the byte index based on the character position.
     ///
     /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
@@ -111,3 +130,122 @@ pub fn handle_list_navigation(
         }
     }
 }
+
+/// `rw`-style permissions of a region, `-` for a missing permission
+pub fn format_perms(perms: &[MemoryRegionPerms]) -> String {
+    format!(
+        "{}{}{}",
+        if perms.contains(&MemoryRegionPerms::Read) {
+            "r"
+        } else {
+            "-"
+        },
+        if perms.contains(&MemoryRegionPerms::Write) {
+            "w"
+        } else {
+            "-"
+        },
+        if perms.contains(&MemoryRegionPerms::Execute) {
+            "x"
+        } else {
+            "-"
+        },
+    )
+}
+
+/// Rings the terminal bell so the user notices the tool while playing the target
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+pub fn format_scan_stats(stats: &ScanStats, skip_swapped: bool) -> String {
+    let mut msg = format!(
+        "Scanned {} of {}",
+        format_bytes(stats.bytes_read),
+        format_bytes(stats.total_bytes)
+    );
+    if stats.swapped_bytes > 0 {
+        msg.push_str(&format!(
+            " ({} swapped{})",
+            format_bytes(stats.swapped_bytes),
+            if skip_swapped { ", skipped" } else { "" }
+        ));
+    }
+    msg
+}
+
+/// Markdown table of the watchlist, readable as is when pasted into plain text notes
+pub fn format_watchlist_table(watchlist: &[ScanResult], ranges: &[NamedRange]) -> String {
+    let mut table = String::from("| Address | Location | Type | Value |\n|---|---|---|---|\n");
+    for entry in watchlist {
+        let location = Location::new(entry.address, entry.path.as_deref(), ranges);
+        let mut value_type = entry.value_type.get_name().to_owned();
+        if entry.endianness == Endianness::Big {
+            value_type.push_str(" BE");
+        }
+        let value = entry.get_string().unwrap_or_default().replace('|', "\\|");
+
+        table.push_str(&format!(
+            "| 0x{:x} | {location} | {value_type} | {value} |\n",
+            entry.address
+        ));
+    }
+    table
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    pub fn test_format_watchlist_table() {
+        use super::*;
+        use crate::core::scan::ValueType;
+
+        let ranges = vec![NamedRange {
+            name: "game".to_owned(),
+            start: 0x40_0000,
+            end: 0x41_0000,
+        }];
+        let mut score = ScanResult::new(
+            0x40_2010,
+            ValueType::U32,
+            31337_u32.to_be_bytes().to_vec(),
+            vec![],
+            Some("/opt/game".to_owned()),
+        );
+        score.endianness = Endianness::Big;
+        let name = ScanResult::new(
+            0x5000_0010,
+            ValueType::String,
+            b"a|b".to_vec(),
+            vec![],
+            Some("[heap]".to_owned()),
+        );
+
+        assert_eq!(
+            format_watchlist_table(&[score, name], &ranges),
+            "| Address | Location | Type | Value |\n\
+             |---|---|---|---|\n\
+             | 0x402010 | game+0x2010 | u32 BE | 31337 |\n\
+             | 0x50000010 | 0x50000010 | string | a\\|b |\n"
+        );
+    }
+}